@@ -1,14 +1,20 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::RwLock;
 
+use crate::database::Database;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheEntry {
     pub latest_version: String,
     pub published_at: Option<DateTime<Utc>>,
     pub cached_at: DateTime<Utc>,
     pub ttl_minutes: i64,
+    /// 获取失败时缓存的错误信息，此时 `latest_version` 为空
+    #[serde(default)]
+    pub error: Option<String>,
 }
 
 impl CacheEntry {
@@ -21,14 +27,14 @@ impl CacheEntry {
 
 pub struct CacheManager {
     entries: RwLock<HashMap<String, CacheEntry>>,
-    default_ttl: i64,
+    default_ttl: AtomicI64,
 }
 
 impl CacheManager {
     pub fn new(default_ttl_minutes: i64) -> Self {
         Self {
             entries: RwLock::new(HashMap::new()),
-            default_ttl: default_ttl_minutes,
+            default_ttl: AtomicI64::new(default_ttl_minutes),
         }
     }
 
@@ -36,20 +42,47 @@ impl CacheManager {
         let entries = self.entries.read().ok()?;
         let entry = entries.get(key)?;
 
-        if entry.is_expired() {
+        if entry.is_expired() || entry.error.is_some() {
             return None;
         }
 
         Some(entry.clone())
     }
 
+    /// 未过期的失败结果
+    pub fn get_error(&self, key: &str) -> Option<String> {
+        let entries = self.entries.read().ok()?;
+        let entry = entries.get(key)?;
+
+        if entry.is_expired() {
+            return None;
+        }
+
+        entry.error.clone()
+    }
+
     pub fn set(&self, key: &str, latest_version: String, published_at: Option<DateTime<Utc>>) {
         if let Ok(mut entries) = self.entries.write() {
             let entry = CacheEntry {
                 latest_version,
                 published_at,
                 cached_at: Utc::now(),
-                ttl_minutes: self.default_ttl,
+                ttl_minutes: self.default_ttl.load(Ordering::Relaxed),
+                error: None,
+            };
+            entries.insert(key.to_string(), entry);
+        }
+    }
+
+    /// 缓存一次失败的结果，避免在 `ttl_minutes` 内重复请求同一个失败的数据源
+    pub fn set_error(&self, key: &str, message: &str, ttl_minutes: i64) {
+        if let Ok(mut entries) = self.entries.write() {
+            let entry = CacheEntry {
+                latest_version: String::new(),
+                published_at: None,
+                cached_at: Utc::now(),
+                ttl_minutes,
+                error: Some(message.to_string()),
             };
             entries.insert(key.to_string(), entry);
         }
@@ -67,11 +100,115 @@ impl CacheManager {
         }
     }
 
+    /// 从数据库恢复缓存，跳过已过期的条目，返回恢复的条目数
+    pub fn load_from_db(&self, db: &Database) -> rusqlite::Result<usize> {
+        let stored = db.get_cache_entries()?;
+        let mut loaded = 0;
+
+        if let Ok(mut entries) = self.entries.write() {
+            for (key, entry) in stored {
+                if !entry.is_expired() {
+                    entries.insert(key, entry);
+                    loaded += 1;
+                }
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// 将未过期的缓存写入数据库，失败结果只保存在内存中
+    pub fn save_to_db(&self, db: &Database) -> rusqlite::Result<()> {
+        let snapshot: Vec<(String, CacheEntry)> = match self.entries.read() {
+            Ok(entries) => entries
+                .iter()
+                .filter(|(_, entry)| !entry.is_expired() && entry.error.is_none())
+                .map(|(key, entry)| (key.clone(), entry.clone()))
+                .collect(),
+            Err(_) => return Ok(()),
+        };
+
+        db.replace_cache_entries(&snapshot)
+    }
+
+    /// 修改 TTL，同时作用于已有的成功结果
     pub fn set_ttl(&self, ttl_minutes: i64) {
-        // Note: This doesn't affect existing entries
-        // In a production app, you might want to update existing entries too
-        let _ = ttl_minutes;
+        if let Ok(mut entries) = self.entries.write() {
+            self.default_ttl.store(ttl_minutes, Ordering::Relaxed);
+            for entry in entries.values_mut().filter(|entry| entry.error.is_none()) {
+                entry.ttl_minutes = ttl_minutes;
+            }
+        }
     }
 }
 
 pub type CacheState = CacheManager;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_persist_and_reload() {
+        let db = Database::new(":memory:").unwrap();
+        let cache = CacheManager::new(30);
+        cache.set("fresh", "1.2.3".to_string(), Some(Utc::now()));
+        cache.entries.write().unwrap().insert(
+            "stale".to_string(),
+            CacheEntry {
+                latest_version: "0.9.0".to_string(),
+                published_at: None,
+                cached_at: Utc::now() - Duration::hours(2),
+                ttl_minutes: 30,
+                error: None,
+            },
+        );
+        cache.set_error("broken", "not found", 5);
+
+        cache.save_to_db(&db).unwrap();
+
+        let reloaded = CacheManager::new(30);
+        assert_eq!(reloaded.load_from_db(&db).unwrap(), 1);
+
+        let entry = reloaded.get("fresh").unwrap();
+        assert_eq!(entry.latest_version, "1.2.3");
+        assert!(entry.published_at.is_some());
+        assert!(reloaded.get("stale").is_none());
+        assert!(reloaded.get_error("broken").is_none());
+    }
+
+    #[test]
+    fn test_error_is_cached_until_expired() {
+        let cache = CacheManager::new(30);
+        cache.set("app", "1.0.0".to_string(), None);
+
+        cache.set_error("app", "Package not found", 5);
+        assert_eq!(cache.get_error("app").as_deref(), Some("Package not found"));
+        assert!(cache.get("app").is_none());
+
+        // 修改成功结果的 TTL 不影响失败结果
+        cache.set_ttl(24 * 60);
+        cache.entries.write().unwrap().get_mut("app").unwrap().cached_at =
+            Utc::now() - Duration::minutes(6);
+        assert!(cache.get_error("app").is_none());
+
+        cache.set("app", "1.0.1".to_string(), None);
+        assert!(cache.get_error("app").is_none());
+        assert_eq!(cache.get("app").unwrap().latest_version, "1.0.1");
+    }
+
+    #[test]
+    fn test_set_ttl_applies_to_existing_entries() {
+        let cache = CacheManager::new(24 * 60);
+        cache.set("app", "1.0.0".to_string(), None);
+        cache.entries.write().unwrap().get_mut("app").unwrap().cached_at =
+            Utc::now() - Duration::minutes(90);
+        assert!(cache.get("app").is_some());
+
+        cache.set_ttl(60);
+        assert!(cache.get("app").is_none());
+
+        cache.set("other", "2.0.0".to_string(), None);
+        assert_eq!(cache.get("other").unwrap().ttl_minutes, 60);
+    }
+}
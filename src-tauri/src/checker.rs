@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::cache::CacheState;
+use crate::database::Database;
+use crate::models::{AppSettings, CheckFailure, ReleaseDetails, Software, VersionCheckResult};
+use crate::services::{self, batch, http::HttpClient, local_version, RemoteVersion};
+
+/// 一批软件的检查结果
+#[derive(Debug)]
+pub struct CheckOutcome {
+    /// 包括缓存命中在内的成功结果
+    pub results: Vec<VersionCheckResult>,
+    pub failures: Vec<CheckFailure>,
+}
+
+/// 获取本地版本，未配置或获取失败时为 None
+pub fn get_local_version(software: &Software) -> Option<String> {
+    software
+        .local_version_config
+        .as_ref()
+        .and_then(|config| local_version::get_version(config).ok())
+}
+
+/// 远程获取前读取软件自己的数据源 token，钥匙串在释放数据库锁之后读取
+pub fn load_source_token(db: &Mutex<Database>, software: &mut Software) -> Result<(), String> {
+    let token = db
+        .lock()
+        .map_err(|e| e.to_string())?
+        .source_token(&software.id)
+        .map_err(|e| e.to_string())?;
+    software.source.auth_token = token.load();
+    Ok(())
+}
+
+/// 获取单个软件的远程版本并与本地版本比较
+async fn fetch_check_result(
+    http: &HttpClient,
+    settings: &AppSettings,
+    db: &Mutex<Database>,
+    mut software: Software,
+) -> Result<(VersionCheckResult, ReleaseDetails), CheckFailure> {
+    let fetch_result = match load_source_token(db, &mut software) {
+        Ok(()) => services::fetch_latest_version(http, &software.source, settings)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e),
+    };
+    let local_version = get_local_version(&software);
+
+    match fetch_result {
+        Ok(RemoteVersion {
+            version: latest_version,
+            published_at,
+            release_notes,
+            asset_url,
+        }) => {
+            let has_update = software.has_update(
+                settings.non_semver_strategy,
+                &latest_version,
+                &local_version,
+                published_at,
+            );
+            let change_kind = software.change_kind(
+                settings.non_semver_strategy,
+                &latest_version,
+                &local_version,
+                published_at,
+            );
+            Ok((
+                VersionCheckResult {
+                    software_id: software.id,
+                    latest_version,
+                    local_version,
+                    published_at,
+                    has_update,
+                    change_kind,
+                },
+                ReleaseDetails {
+                    release_notes,
+                    asset_url,
+                },
+            ))
+        }
+        Err(error) => Err(CheckFailure {
+            software_id: software.id,
+            name: software.name,
+            error,
+        }),
+    }
+}
+
+/// 按缓存拆分软件：返回缓存命中的结果和需要远程获取的软件，强制刷新时全部重新获取
+fn split_cached(
+    softwares: Vec<Software>,
+    cache: &CacheState,
+    settings: &AppSettings,
+    force_refresh: bool,
+) -> (Vec<VersionCheckResult>, Vec<Software>) {
+    let mut cached_results = Vec::new();
+    let mut need_fetch = Vec::new();
+
+    for software in softwares {
+        if force_refresh {
+            need_fetch.push(software);
+        } else if let Some(cached) = cache.get(&software.id) {
+            let local_version = get_local_version(&software);
+            let has_update = software.has_update(
+                settings.non_semver_strategy,
+                &cached.latest_version,
+                &local_version,
+                cached.published_at,
+            );
+            let change_kind = software.change_kind(
+                settings.non_semver_strategy,
+                &cached.latest_version,
+                &local_version,
+                cached.published_at,
+            );
+            cached_results.push(VersionCheckResult {
+                software_id: software.id.clone(),
+                latest_version: cached.latest_version,
+                local_version,
+                published_at: cached.published_at,
+                has_update,
+                change_kind,
+            });
+        } else if let Some(error) = cache.get_error(&software.id) {
+            // 最近失败过的数据源在错误缓存过期前跳过
+            eprintln!("Error checking {} (cached): {}", software.name, error);
+        } else {
+            need_fetch.push(software);
+        }
+    }
+
+    (cached_results, need_fetch)
+}
+
+/// 检查一批软件并把结果写回缓存和数据库，命令、调度器和命令行共用
+///
+/// 不强制刷新时先使用缓存；需要远程获取的软件按配置错开启动时间并发获取，
+/// 失败的软件写入错误缓存并记录错误，收到取消信号时只保存已完成的结果
+pub async fn check_softwares(
+    softwares: Vec<Software>,
+    force_refresh: bool,
+    db: &Mutex<Database>,
+    cache: &CacheState,
+    http: &HttpClient,
+    settings: &AppSettings,
+    cancel: watch::Receiver<bool>,
+) -> Result<CheckOutcome, String> {
+    // 先检查缓存，分离出需要远程获取的软件
+    let (cached_results, need_fetch) = split_cached(softwares, cache, settings, force_refresh);
+    let mut outcome = CheckOutcome {
+        results: cached_results,
+        failures: Vec::new(),
+    };
+
+    // 全部命中缓存时不写回数据库
+    if need_fetch.is_empty() {
+        return Ok(outcome);
+    }
+
+    let error_ttl = settings.cache.error_ttl_minutes as i64;
+    let max_jitter = Duration::from_secs(settings.network.check_jitter_seconds as u64);
+    let results = batch::run_staggered_cancellable(
+        need_fetch,
+        batch::MAX_CONCURRENT,
+        max_jitter,
+        cancel,
+        |software: Software| async move {
+            let result = fetch_check_result(http, settings, db, software).await;
+            if let Err(failure) = &result {
+                cache.set_error(&failure.software_id, &failure.error, error_ttl);
+            }
+            result
+        },
+    )
+    .await;
+
+    // 只有本次远程获取的软件才更新发布说明和附件地址
+    let mut remote_details = HashMap::new();
+    for result in results.into_iter().flatten() {
+        match result {
+            Ok((check_result, details)) => {
+                cache.set(
+                    &check_result.software_id,
+                    check_result.latest_version.clone(),
+                    check_result.published_at,
+                );
+                remote_details.insert(check_result.software_id.clone(), details);
+                outcome.results.push(check_result);
+            }
+            Err(failure) => {
+                eprintln!("Error checking {}: {}", failure.name, failure.error);
+                outcome.failures.push(failure);
+            }
+        }
+    }
+
+    // 批量更新数据库，在一个事务中完成，出错时全部回滚
+    let db = db.lock().map_err(|e| e.to_string())?;
+    db.apply_check_results(&outcome.results, &remote_details, &outcome.failures)
+        .map_err(|e| e.to_string())?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SourceConfig, SourceType};
+
+    #[test]
+    fn test_split_cached_force_refresh_fetches_all() {
+        let cache = CacheState::new(60);
+        let settings = AppSettings::default();
+        let softwares: Vec<_> = ["cached", "failed", "fresh"]
+            .into_iter()
+            .map(|id| {
+                Software::new(
+                    id.to_string(),
+                    id.to_string(),
+                    SourceConfig::new(SourceType::Npm, id),
+                )
+            })
+            .collect();
+        cache.set("cached", "1.0.0".to_string(), None);
+        cache.set_error("failed", "boom", 5);
+
+        let (cached, need_fetch) = split_cached(softwares.clone(), &cache, &settings, false);
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].software_id, "cached");
+        let ids: Vec<_> = need_fetch.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["fresh"]);
+
+        let (cached, need_fetch) = split_cached(softwares, &cache, &settings, true);
+        assert!(cached.is_empty());
+        let ids: Vec<_> = need_fetch.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["cached", "failed", "fresh"]);
+    }
+}
@@ -0,0 +1,305 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::cache::CacheManager;
+use crate::checker;
+use crate::database::Database;
+use crate::models::{AppSettings, Software};
+use crate::services::{batch, http::HttpClient};
+use crate::DATABASE_FILE;
+
+/// 与 tauri.conf.json 中的 identifier 保持一致，GUI 的数据目录以此命名
+const APP_IDENTIFIER: &str = "com.smile.app-version-gui";
+
+/// 单个软件的检查结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckRow {
+    pub name: String,
+    pub local_version: Option<String>,
+    pub latest_version: Option<String>,
+    pub has_update: bool,
+    pub error: Option<String>,
+}
+
+/// 未指定 `--db` 时使用 GUI 的数据库
+fn default_db_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_IDENTIFIER).join(DATABASE_FILE))
+}
+
+/// 读取 `--db <path>` 参数
+fn db_path_from_args(args: &[String]) -> Option<PathBuf> {
+    let index = args.iter().position(|arg| arg == "--db")?;
+    args.get(index + 1).map(PathBuf::from)
+}
+
+/// 检查所有启用的软件并写回数据库，与 `check_all_versions` 强制刷新时一致
+pub async fn check_all(
+    db: &Mutex<Database>,
+    http: &HttpClient,
+    settings: &AppSettings,
+) -> Vec<CheckRow> {
+    let loaded = db
+        .lock()
+        .map_err(|e| e.to_string())
+        .and_then(|db| db.get_all_softwares().map_err(|e| e.to_string()));
+    let softwares: Vec<Software> = match loaded {
+        Ok(softwares) => softwares.into_iter().filter(|s| s.enabled).collect(),
+        Err(e) => {
+            eprintln!("Failed to load softwares: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let cache = CacheManager::new(settings.cache.ttl_minutes as i64);
+    let outcome = match checker::check_softwares(
+        softwares.clone(),
+        true,
+        db,
+        &cache,
+        http,
+        settings,
+        batch::never_cancelled(),
+    )
+    .await
+    {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("Failed to save check results: {}", e);
+            return Vec::new();
+        }
+    };
+
+    softwares
+        .into_iter()
+        .filter_map(|software| {
+            if let Some(result) = outcome
+                .results
+                .iter()
+                .find(|r| r.software_id == software.id)
+            {
+                return Some(CheckRow {
+                    name: software.name,
+                    local_version: result.local_version.clone(),
+                    latest_version: Some(result.latest_version.clone()),
+                    has_update: result.has_update,
+                    error: None,
+                });
+            }
+            let failure = outcome
+                .failures
+                .iter()
+                .find(|f| f.software_id == software.id)?;
+            Some(CheckRow {
+                local_version: checker::get_local_version(&software),
+                name: software.name,
+                latest_version: software.latest_version,
+                has_update: false,
+                error: Some(failure.error.clone()),
+            })
+        })
+        .collect()
+}
+
+/// 输出 `name / local / latest / has_update` 表格
+pub fn format_table(rows: &[CheckRow]) -> String {
+    let header = ["NAME", "LOCAL", "LATEST", "UPDATE"];
+    let cells: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            let update = match &row.error {
+                Some(error) => format!("error: {}", error),
+                None if row.has_update => "yes".to_string(),
+                None => "no".to_string(),
+            };
+            [
+                row.name.clone(),
+                row.local_version.clone().unwrap_or_else(|| "-".to_string()),
+                row.latest_version
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                update,
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_line = |line: [&str; 4]| {
+        let text = format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            line[0],
+            line[1],
+            line[2],
+            line[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+        text.trim_end().to_string()
+    };
+
+    let mut lines = vec![format_line(header)];
+    for row in &cells {
+        lines.push(format_line([&row[0], &row[1], &row[2], &row[3]]));
+    }
+    lines.join("\n")
+}
+
+/// 有可用更新时退出码为 1
+pub fn exit_code(rows: &[CheckRow]) -> i32 {
+    if rows.iter().any(|row| row.has_update) {
+        1
+    } else {
+        0
+    }
+}
+
+/// `--check [--db <path>]`：检查后打印结果，返回进程退出码
+///
+/// 数据库无法打开时返回 2
+pub fn run(args: &[String]) -> i32 {
+    let Some(db_path) = db_path_from_args(args).or_else(default_db_path) else {
+        eprintln!("Cannot determine the data directory, pass --db <path>");
+        return 2;
+    };
+    let db = match Database::new(&db_path) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Failed to open database {}: {}", db_path.display(), e);
+            return 2;
+        }
+    };
+
+    let settings = db.get_settings().unwrap_or_default();
+    let http = match HttpClient::new(&settings.network) {
+        Ok(http) => http,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+
+    let db = Mutex::new(db);
+    let runtime = tokio::runtime::Runtime::new().expect("Failed to start tokio runtime");
+    let rows = runtime.block_on(check_all(&db, &http, &settings));
+
+    println!("{}", format_table(&rows));
+    exit_code(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LocalVersionConfig, NetworkConfig, SourceConfig, SourceType};
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn npm_software(id: &str, local: &str) -> Software {
+        let mut software = Software::new(
+            id.to_string(),
+            id.to_string(),
+            SourceConfig::new(SourceType::Npm, id),
+        );
+        software.local_version_config = Some(LocalVersionConfig {
+            command: "echo".to_string(),
+            args: vec![local.to_string()],
+            ..Default::default()
+        });
+        software
+    }
+
+    #[test]
+    fn test_db_path_from_args() {
+        let args = ["--check", "--db", "/tmp/a.db"].map(String::from);
+        assert_eq!(db_path_from_args(&args), Some(PathBuf::from("/tmp/a.db")));
+        assert_eq!(db_path_from_args(&args[..1]), None);
+    }
+
+    #[tokio::test]
+    async fn test_check_all_against_temp_db() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/react" => MockResponse::json(200, r#"{"dist-tags":{"latest":"18.3.1"}}"#),
+            "/lodash" => MockResponse::json(200, r#"{"dist-tags":{"latest":"4.17.21"}}"#),
+            _ => MockResponse::json(404, "{}"),
+        })
+        .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url());
+
+        let dir = std::env::temp_dir().join(format!("cli-check-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join(DATABASE_FILE);
+        {
+            let db = Database::new(&db_path).unwrap();
+            db.insert_software(&npm_software("react", "18.2.0"))
+                .unwrap();
+            db.insert_software(&npm_software("lodash", "4.17.21"))
+                .unwrap();
+            db.insert_software(&npm_software("missing", "1.0.0"))
+                .unwrap();
+        }
+
+        let db = Mutex::new(Database::new(&db_path).unwrap());
+        let mut rows = check_all(&db, &http, &AppSettings::default()).await;
+        rows.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].name, "lodash");
+        assert!(!rows[0].has_update);
+        assert!(rows[1].error.is_some());
+        assert_eq!(rows[2].latest_version.as_deref(), Some("18.3.1"));
+        assert_eq!(rows[2].local_version.as_deref(), Some("18.2.0"));
+        assert!(rows[2].has_update);
+        assert_eq!(exit_code(&rows), 1);
+        assert_eq!(exit_code(&rows[..2]), 0);
+
+        let stored = db.lock().unwrap().get_software("react").unwrap().unwrap();
+        assert_eq!(stored.latest_version.as_deref(), Some("18.3.1"));
+
+        let table = format_table(&rows);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines[0], "NAME     LOCAL    LATEST   UPDATE");
+        assert_eq!(lines[3], "react    18.2.0   18.3.1   yes");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_check_all_records_last_error() {
+        let available = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = available.clone();
+        let server = MockServer::start(move |_| {
+            if flag.load(std::sync::atomic::Ordering::SeqCst) {
+                MockResponse::json(200, r#"{"dist-tags":{"latest":"18.3.1"}}"#)
+            } else {
+                MockResponse::json(404, "{}")
+            }
+        })
+        .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url());
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        db.lock()
+            .unwrap()
+            .insert_software(&npm_software("react", "18.2.0"))
+            .unwrap();
+
+        check_all(&db, &http, &AppSettings::default()).await;
+        let failed = db.lock().unwrap().get_software("react").unwrap().unwrap();
+        assert!(failed.last_error.is_some());
+        assert!(failed.last_error_at.is_some());
+
+        available.store(true, std::sync::atomic::Ordering::SeqCst);
+        check_all(&db, &http, &AppSettings::default()).await;
+        let recovered = db.lock().unwrap().get_software("react").unwrap().unwrap();
+        assert_eq!(recovered.latest_version.as_deref(), Some("18.3.1"));
+        assert!(recovered.last_error.is_none());
+        assert!(recovered.last_error_at.is_none());
+    }
+}
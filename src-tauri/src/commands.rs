@@ -1,62 +1,101 @@
 use chrono::{DateTime, Utc};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
 use tauri::{AppHandle, State};
-use tokio::sync::Semaphore;
 use uuid::Uuid;
 
 use crate::cache::CacheState;
-use crate::database::DbState;
-use crate::models::{AppSettings, Software, SoftwareFormData, SourceType, VersionCheckResult};
+use crate::checker;
+use crate::database::{Database, DbState};
+use crate::metrics::MetricsState;
+use crate::models::{
+    AppSettings, BackoffStatus, BatchAddFailure, BatchAddResult, GithubRateLimit, ImportFailure,
+    ImportResult, NonSemverStrategy, NotificationChannel, NotificationLogEntry,
+    NotificationTestResult, ReleaseDetails, SchedulerStatus, SettingsState, Software,
+    SoftwareFormData, SourceConfig, VersionCheckResult, VersionHistoryEntry, VersionPreview,
+    WebhookConfig,
+};
+use crate::notification::{send_notification, send_webhook, DesktopNotification};
 use crate::scheduler::SchedulerState;
-use crate::services::{cargo, github, homebrew, local_version, npm, pypi};
-use crate::version::comparator;
+use crate::services::batch::{self, CancelSignal};
+use crate::services::{self, github, http::HttpClient, local_version, urls, RemoteVersion};
 
 // Software CRUD Commands
 
+/// 获取软件列表，`include_deleted` 为 true 时包括回收站中的软件
 #[tauri::command]
-pub async fn get_all_softwares(db: State<'_, DbState>) -> Result<Vec<Software>, String> {
+pub async fn get_all_softwares(
+    include_deleted: Option<bool>,
+    db: State<'_, DbState>,
+) -> Result<Vec<Software>, String> {
     let db = db.lock().map_err(|e| e.to_string())?;
-    db.get_all_softwares().map_err(|e| e.to_string())
+    db.get_softwares(include_deleted.unwrap_or(false))
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn add_software(
+pub async fn get_software(id: String, db: State<'_, DbState>) -> Result<Software, String> {
+    let db = db.lock().map_err(|e| e.to_string())?;
+    db.get_software(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Software not found".to_string())
+}
+
+/// 已存在相同数据源的软件时，不允许重复则返回错误
+fn ensure_unique_source(
+    db: &Database,
+    source: &SourceConfig,
+    allow_duplicate: bool,
+) -> Result<(), String> {
+    if allow_duplicate {
+        return Ok(());
+    }
+    match db
+        .find_by_source(&source.source_type, &source.identifier)
+        .map_err(|e| e.to_string())?
+    {
+        Some(existing) => Err(format!(
+            "Software '{}' already uses {} source '{}'",
+            existing.name,
+            source.source_type.as_str(),
+            source.identifier.trim()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// 校验数据源并写入数据库和缓存，`add_software` 与 `add_softwares` 共用
+async fn add_software_in(
+    db: &DbState,
+    cache: &CacheState,
+    http: &HttpClient,
+    settings: &AppSettings,
     form: SoftwareFormData,
-    db: State<'_, DbState>,
-    cache: State<'_, CacheState>,
-    settings: State<'_, AppSettings>,
+    allow_duplicate: bool,
 ) -> Result<Software, String> {
+    validate_local_version_config(&form)?;
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        ensure_unique_source(&db, &form.source, allow_duplicate)?;
+    }
+
     // 1. 先尝试获取版本信息（验证数据源有效性）
-    let github_token = settings.github_token.as_deref();
-    let (latest_version, published_at) = match form.source.source_type {
-        SourceType::GithubRelease => {
-            github::get_latest_release(&form.source.identifier, github_token).await?
-        }
-        SourceType::GithubTags => {
-            github::get_latest_tag(&form.source.identifier, github_token).await?
-        }
-        SourceType::Homebrew => {
-            let version = homebrew::get_version(&form.source.identifier).await?;
-            (version, None)
-        }
-        SourceType::Npm => {
-            npm::get_latest_version(&form.source.identifier).await?
-        }
-        SourceType::Pypi => {
-            pypi::get_latest_version(&form.source.identifier).await?
-        }
-        SourceType::Cargo => {
-            cargo::get_latest_version(&form.source.identifier).await?
-        }
-    };
+    let RemoteVersion {
+        version: latest_version,
+        published_at,
+        release_notes,
+        asset_url,
+    } = services::fetch_latest_version(http, &form.source, settings).await?;
 
     // 2. 获取本地版本（如果配置了）
-    let local_version = form.local_version_config.as_ref().and_then(|config| {
-        local_version::get_version(&config.command, config.version_arg.as_deref()).ok()
-    });
+    let local_version = form
+        .local_version_config
+        .as_ref()
+        .and_then(|config| local_version::get_version(config).ok());
 
     // 3. 版本获取成功，创建软件记录
-    let software = Software {
+    let mut software = Software {
         id: Uuid::new_v4().to_string(),
         name: form.name,
         source: form.source,
@@ -68,11 +107,28 @@ pub async fn add_software(
         enabled: true,
         last_notified_version: None,
         last_notified_at: None,
+        check_interval_minutes: form.check_interval_minutes,
+        release_notes,
+        tags: normalize_tags(form.tags),
+        sort_order: 0,
+        snoozed_until: None,
+        snoozed_version: None,
+        deleted_at: None,
+        asset_url,
+        last_error: None,
+        last_error_at: None,
+        seen_published_at: None,
     };
+    software.refresh_seen_published_at();
 
-    // 4. 插入数据库
-    let db = db.lock().map_err(|e| e.to_string())?;
-    db.insert_software(&software).map_err(|e| e.to_string())?;
+    // 4. 插入数据库，获取期间可能已有相同数据源的软件被添加，需要再次检查
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        ensure_unique_source(&db, &software.source, allow_duplicate)?;
+        db.insert_software(&software).map_err(|e| e.to_string())?;
+        db.record_version(&software.id, &latest_version, Utc::now())
+            .map_err(|e| e.to_string())?;
+    }
 
     // 5. 更新缓存
     cache.set(&software.id, latest_version, published_at);
@@ -80,53 +136,402 @@ pub async fn add_software(
     Ok(software)
 }
 
+#[tauri::command]
+pub async fn add_software(
+    form: SoftwareFormData,
+    allow_duplicate: bool,
+    db: State<'_, DbState>,
+    cache: State<'_, CacheState>,
+    settings: State<'_, SettingsState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<Software, String> {
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+    let http = HttpClient::new(&settings.network)?;
+    let software = add_software_in(&db, &cache, &http, &settings, form, allow_duplicate).await?;
+
+    scheduler.lock().await.reschedule();
+
+    Ok(software)
+}
+
+/// 并发校验并添加多个软件，逐条返回成功或失败
+///
+/// 同一批中数据源相同的软件只添加第一个，其余直接记为失败
+async fn add_softwares_in(
+    db: &DbState,
+    cache: &CacheState,
+    http: &HttpClient,
+    settings: &AppSettings,
+    forms: Vec<SoftwareFormData>,
+) -> BatchAddResult {
+    let mut first_by_source: HashMap<(&'static str, String), usize> = HashMap::new();
+    let mut unique = Vec::new();
+    let mut failed = Vec::new();
+    for (index, form) in forms.into_iter().enumerate() {
+        let key = (
+            form.source.source_type.as_str(),
+            form.source.identifier.trim().to_string(),
+        );
+        match first_by_source.get(&key) {
+            Some(first) => failed.push(BatchAddFailure {
+                index,
+                name: form.name,
+                error: format!("Same source as item {} in this batch", first + 1),
+            }),
+            None => {
+                first_by_source.insert(key, index);
+                unique.push((index, form));
+            }
+        }
+    }
+
+    let results = batch::run_staggered(
+        unique,
+        batch::MAX_CONCURRENT,
+        Duration::ZERO,
+        |(index, form): (usize, SoftwareFormData)| async move {
+            let name = form.name.clone();
+            let result = add_software_in(db, cache, http, settings, form, false).await;
+            result.map_err(|error| BatchAddFailure { index, name, error })
+        },
+    )
+    .await;
+
+    let mut added = Vec::new();
+    for result in results {
+        match result {
+            Ok(software) => added.push(software),
+            Err(failure) => failed.push(failure),
+        }
+    }
+    failed.sort_by_key(|failure| failure.index);
+    BatchAddResult { added, failed }
+}
+
+#[tauri::command]
+pub async fn add_softwares(
+    forms: Vec<SoftwareFormData>,
+    db: State<'_, DbState>,
+    cache: State<'_, CacheState>,
+    settings: State<'_, SettingsState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<BatchAddResult, String> {
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+    let http = HttpClient::new(&settings.network)?;
+    let result = add_softwares_in(&db, &cache, &http, &settings, forms).await;
+
+    if !result.added.is_empty() {
+        scheduler.lock().await.reschedule();
+    }
+
+    Ok(result)
+}
+
+/// 复制一个软件，名称后加 " (copy)"，不保留通知、暂缓和错误状态
+#[tauri::command]
+pub async fn duplicate_software(
+    id: String,
+    db: State<'_, DbState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<Software, String> {
+    let software = duplicate_software_in(&db, &id)?;
+    scheduler.lock().await.reschedule();
+    Ok(software)
+}
+
+fn duplicate_software_in(db: &Mutex<Database>, id: &str) -> Result<Software, String> {
+    let db = db.lock().map_err(|e| e.to_string())?;
+    let original = db
+        .get_software(id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Software not found")?;
+
+    let token = db.source_token(id).map_err(|e| e.to_string())?.load();
+    let mut copy = Software {
+        id: Uuid::new_v4().to_string(),
+        name: format!("{} (copy)", original.name),
+        last_notified_version: None,
+        last_notified_at: None,
+        snoozed_until: None,
+        snoozed_version: None,
+        deleted_at: None,
+        last_error: None,
+        last_error_at: None,
+        ..original
+    };
+    copy.source.auth_token = token;
+    db.insert_software(&copy).map_err(|e| e.to_string())?;
+    if let Some(latest_version) = &copy.latest_version {
+        db.record_version(&copy.id, latest_version, Utc::now())
+            .map_err(|e| e.to_string())?;
+    }
+
+    // 重新读取以获得插入时分配的排序位置
+    db.get_software(&copy.id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Software not found".to_string())
+}
+
 #[tauri::command]
 pub async fn update_software(
     id: String,
     form: SoftwareFormData,
     db: State<'_, DbState>,
+    cache: State<'_, CacheState>,
+    scheduler: State<'_, SchedulerState>,
 ) -> Result<Software, String> {
+    validate_local_version_config(&form)?;
+
+    let software = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+
+        let existing = db
+            .get_software(&id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Software not found")?;
+
+        let mut software = Software {
+            name: form.name,
+            source: form.source,
+            local_version_config: form.local_version_config,
+            check_interval_minutes: form.check_interval_minutes,
+            tags: normalize_tags(form.tags),
+            ..existing
+        };
+        // 切换为按发布时间比较时，以当前记录的发布为基准
+        software.refresh_seen_published_at();
+
+        db.update_software(&software).map_err(|e| e.to_string())?;
+        db.set_source_token(&software.id, software.source.auth_token.as_deref())
+            .map_err(|e| e.to_string())?;
+        software
+    };
+
+    // 数据源可能已修正，清除之前的失败记录
+    cache.invalidate(&software.id);
+    let scheduler = scheduler.lock().await;
+    scheduler.reset_backoff(&software.id);
+    scheduler.reschedule();
+
+    Ok(software)
+}
+
+/// 按给定的 ID 顺序重新排列软件，未列出的软件保持原有顺序排在后面
+#[tauri::command]
+pub async fn reorder_softwares(
+    ordered_ids: Vec<String>,
+    db: State<'_, DbState>,
+) -> Result<Vec<Software>, String> {
     let db = db.lock().map_err(|e| e.to_string())?;
+    db.reorder_softwares(&ordered_ids).map_err(|e| e.to_string())?;
+    db.get_all_softwares().map_err(|e| e.to_string())
+}
 
-    let existing = db
-        .get_software(&id)
-        .map_err(|e| e.to_string())?
-        .ok_or("Software not found")?;
+/// 按名称、数据源标识或最新版本搜索软件
+#[tauri::command]
+pub async fn search_softwares(
+    query: String,
+    db: State<'_, DbState>,
+) -> Result<Vec<Software>, String> {
+    let db = db.lock().map_err(|e| e.to_string())?;
+    db.search_softwares(&query).map_err(|e| e.to_string())
+}
 
-    let software = Software {
-        id: existing.id,
-        name: form.name,
-        source: form.source,
-        local_version_config: form.local_version_config,
-        latest_version: existing.latest_version,
-        local_version: existing.local_version,
-        published_at: existing.published_at,
-        last_checked_at: existing.last_checked_at,
-        enabled: existing.enabled,
-        last_notified_version: existing.last_notified_version,
-        last_notified_at: existing.last_notified_at,
+/// 获取带有指定标签的软件
+#[tauri::command]
+pub async fn get_softwares_by_tag(
+    tag: String,
+    db: State<'_, DbState>,
+) -> Result<Vec<Software>, String> {
+    let db = db.lock().map_err(|e| e.to_string())?;
+    db.get_softwares_by_tag(tag.trim()).map_err(|e| e.to_string())
+}
+
+/// 将所有软件导出为格式化的 JSON
+#[tauri::command]
+pub async fn export_softwares(db: State<'_, DbState>) -> Result<String, String> {
+    let db = db.lock().map_err(|e| e.to_string())?;
+    let mut softwares = db.get_all_softwares().map_err(|e| e.to_string())?;
+    // 导出的文件可能被分享，不包含数据源的 token
+    for software in &mut softwares {
+        software.source.auth_token = None;
+    }
+    serde_json::to_string_pretty(&softwares).map_err(|e| e.to_string())
+}
+
+/// 从 JSON 导入软件列表
+///
+/// `merge` 为 false 时替换现有列表；`validate` 为 true 时先像 `add_software` 一样请求远程版本，
+/// 跳过无法获取版本的条目
+#[tauri::command]
+pub async fn import_softwares(
+    json: String,
+    merge: bool,
+    validate: bool,
+    db: State<'_, DbState>,
+    settings: State<'_, SettingsState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<ImportResult, String> {
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+    let softwares: Vec<Software> =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid import file: {}", e))?;
+
+    let mut valid = Vec::with_capacity(softwares.len());
+    let mut failed = Vec::new();
+
+    if validate {
+        let http = HttpClient::new(&settings.network)?;
+        for software in softwares {
+            match services::fetch_latest_version(&http, &software.source, &settings).await {
+                Ok(_) => valid.push(software),
+                Err(e) => failed.push(ImportFailure {
+                    id: software.id,
+                    name: software.name,
+                    error: e.to_string(),
+                }),
+            }
+        }
+    } else {
+        valid = softwares;
+    }
+
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.import_softwares(&valid, merge).map_err(|e| e.to_string())?;
+    }
+
+    scheduler.lock().await.reschedule();
+
+    Ok(ImportResult {
+        imported: valid.len(),
+        failed,
+    })
+}
+
+#[tauri::command]
+pub async fn delete_software(
+    id: String,
+    db: State<'_, DbState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<(), String> {
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.delete_software(&id).map_err(|e| e.to_string())?;
+    }
+
+    scheduler.lock().await.reschedule();
+    Ok(())
+}
+
+/// 从回收站恢复软件
+#[tauri::command]
+pub async fn restore_software(
+    id: String,
+    db: State<'_, DbState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<Software, String> {
+    let software = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        if !db.restore_software(&id).map_err(|e| e.to_string())? {
+            return Err("Software is not in the trash".to_string());
+        }
+        db.get_software(&id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Software not found")?
     };
 
-    db.update_software(&software).map_err(|e| e.to_string())?;
+    scheduler.lock().await.reschedule();
     Ok(software)
 }
 
+/// 清空回收站，返回删除的软件数量
 #[tauri::command]
-pub async fn delete_software(id: String, db: State<'_, DbState>) -> Result<(), String> {
+pub async fn purge_deleted(db: State<'_, DbState>) -> Result<usize, String> {
     let db = db.lock().map_err(|e| e.to_string())?;
-    db.delete_software(&id).map_err(|e| e.to_string())
+    db.purge_deleted().map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn toggle_software(id: String, enabled: bool, db: State<'_, DbState>) -> Result<(), String> {
+pub async fn toggle_software(
+    id: String,
+    enabled: bool,
+    db: State<'_, DbState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<(), String> {
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        let mut software = db
+            .get_software(&id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Software not found")?;
+
+        software.enabled = enabled;
+        db.update_software(&software).map_err(|e| e.to_string())?;
+    }
+
+    scheduler.lock().await.reschedule();
+    Ok(())
+}
+
+/// 批量启用或禁用软件，返回实际更新的数量
+#[tauri::command]
+pub async fn toggle_softwares(
+    ids: Vec<String>,
+    enabled: bool,
+    db: State<'_, DbState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<usize, String> {
+    let updated = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.set_enabled(&ids, enabled).map_err(|e| e.to_string())?
+    };
+
+    scheduler.lock().await.reschedule();
+    Ok(updated)
+}
+
+/// 启用或禁用所有软件（不包括回收站中的），返回更新的数量
+#[tauri::command]
+pub async fn set_all_enabled(
+    enabled: bool,
+    db: State<'_, DbState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<usize, String> {
+    let updated = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.set_all_enabled(enabled).map_err(|e| e.to_string())?
+    };
+
+    scheduler.lock().await.reschedule();
+    Ok(updated)
+}
+
+/// 暂缓当前最新版本的更新提醒到 `until`，出现更新的版本后自动失效
+#[tauri::command]
+pub async fn snooze_software(
+    id: String,
+    until: DateTime<Utc>,
+    db: State<'_, DbState>,
+) -> Result<Software, String> {
     let db = db.lock().map_err(|e| e.to_string())?;
-    let mut software = db
-        .get_software(&id)
+    snooze_software_in(&db, &id, until)
+}
+
+fn snooze_software_in(db: &Database, id: &str, until: DateTime<Utc>) -> Result<Software, String> {
+    let software = db
+        .get_software(id)
         .map_err(|e| e.to_string())?
         .ok_or("Software not found")?;
+    let latest_version = software
+        .latest_version
+        .as_deref()
+        .ok_or("Latest version has not been checked yet")?;
 
-    software.enabled = enabled;
-    db.update_software(&software).map_err(|e| e.to_string())
+    db.set_snooze(id, Some(latest_version), Some(until))
+        .map_err(|e| e.to_string())?;
+    db.get_software(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Software not found".to_string())
 }
 
 // Version Check Commands
@@ -137,9 +542,10 @@ pub async fn check_version(
     force_refresh: bool,
     db: State<'_, DbState>,
     cache: State<'_, CacheState>,
-    settings: State<'_, AppSettings>,
+    settings: State<'_, SettingsState>,
 ) -> Result<VersionCheckResult, String> {
-    let software = {
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+    let mut software = {
         let db = db.lock().map_err(|e| e.to_string())?;
         db.get_software(&id)
             .map_err(|e| e.to_string())?
@@ -148,222 +554,335 @@ pub async fn check_version(
 
     // Check cache first
     if !force_refresh {
+        // 最近失败过的数据源在错误缓存过期前不再请求
+        if let Some(error) = cache.get_error(&id) {
+            return Err(error);
+        }
         if let Some(cached) = cache.get(&id) {
-            let local_version = get_local_version(&software);
-            let has_update = comparator::has_update(&cached.latest_version, &local_version);
+            let local_version = checker::get_local_version(&software);
+            let has_update = software.has_update(
+                settings.non_semver_strategy,
+                &cached.latest_version,
+                &local_version,
+                cached.published_at,
+            );
+            let change_kind = software.change_kind(
+                settings.non_semver_strategy,
+                &cached.latest_version,
+                &local_version,
+                cached.published_at,
+            );
             return Ok(VersionCheckResult {
                 software_id: id,
                 latest_version: cached.latest_version,
                 local_version,
                 published_at: cached.published_at,
                 has_update,
+                change_kind,
             });
         }
     }
 
     // Fetch from remote
-    let github_token = settings.github_token.as_deref();
-    let (latest_version, published_at) = match software.source.source_type {
-        SourceType::GithubRelease => {
-            github::get_latest_release(&software.source.identifier, github_token).await?
-        }
-        SourceType::GithubTags => {
-            github::get_latest_tag(&software.source.identifier, github_token).await?
-        }
-        SourceType::Homebrew => {
-            let version = homebrew::get_version(&software.source.identifier).await?;
-            (version, None)
-        }
-        SourceType::Npm => {
-            npm::get_latest_version(&software.source.identifier).await?
-        }
-        SourceType::Pypi => {
-            pypi::get_latest_version(&software.source.identifier).await?
-        }
-        SourceType::Cargo => {
-            cargo::get_latest_version(&software.source.identifier).await?
+    checker::load_source_token(&db, &mut software)?;
+    let http = HttpClient::new(&settings.network)?;
+    let RemoteVersion {
+        version: latest_version,
+        published_at,
+        release_notes,
+        asset_url,
+    } = match services::fetch_latest_version(&http, &software.source, &settings).await {
+        Ok(remote) => remote,
+        Err(e) => {
+            let message = e.to_string();
+            cache.set_error(&id, &message, settings.cache.error_ttl_minutes as i64);
+            let db = db.lock().map_err(|e| e.to_string())?;
+            db.set_last_error(&id, Some(&message), Some(Utc::now()))
+                .map_err(|e| e.to_string())?;
+            return Err(message);
         }
     };
 
     // Get local version
-    let local_version = get_local_version(&software);
+    let local_version = checker::get_local_version(&software);
 
     // Update cache
     cache.set(&id, latest_version.clone(), published_at);
 
-    // Update database
+    let has_update = software.has_update(
+        settings.non_semver_strategy,
+        &latest_version,
+        &local_version,
+        published_at,
+    );
+    let change_kind = software.change_kind(
+        settings.non_semver_strategy,
+        &latest_version,
+        &local_version,
+        published_at,
+    );
+    let result = VersionCheckResult {
+        software_id: id,
+        latest_version,
+        local_version,
+        published_at,
+        has_update,
+        change_kind,
+    };
+
+    // 与后台检查共用写回逻辑，在事务中重新读取软件，不覆盖获取期间的其他修改
+    let details = HashMap::from([(
+        result.software_id.clone(),
+        ReleaseDetails {
+            release_notes,
+            asset_url,
+        },
+    )]);
     {
         let db = db.lock().map_err(|e| e.to_string())?;
-        let mut updated_software = software.clone();
-        updated_software.latest_version = Some(latest_version.clone());
-        updated_software.local_version = local_version.clone();
-        updated_software.published_at = published_at;
-        updated_software.last_checked_at = Some(Utc::now());
-        db.update_software(&updated_software).map_err(|e| e.to_string())?;
+        db.apply_check_results(std::slice::from_ref(&result), &details, &[])
+            .map_err(|e| e.to_string())?;
     }
 
-    let has_update = comparator::has_update(&latest_version, &local_version);
+    Ok(result)
+}
+
+/// 校验数据源并返回解析到的最新版本，不写入数据库
+#[tauri::command]
+pub async fn validate_source(
+    source: SourceConfig,
+    settings: State<'_, SettingsState>,
+) -> Result<VersionPreview, String> {
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+    let http = HttpClient::new(&settings.network)?;
+    Ok(services::preview_source(&http, &source, &settings).await?)
+}
+
+/// 只重新读取本地版本，并与已记录的最新版本比较，不发起网络请求
+#[tauri::command]
+pub async fn refresh_local_version(
+    id: String,
+    db: State<'_, DbState>,
+    settings: State<'_, SettingsState>,
+) -> Result<VersionCheckResult, String> {
+    let strategy = settings.read().map_err(|e| e.to_string())?.non_semver_strategy;
+    refresh_local_version_in(&db, &id, strategy)
+}
+
+fn refresh_local_version_in(
+    db: &Mutex<Database>,
+    id: &str,
+    strategy: NonSemverStrategy,
+) -> Result<VersionCheckResult, String> {
+    let software = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.get_software(id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Software not found")?
+    };
+    let latest_version = software
+        .latest_version
+        .clone()
+        .ok_or("Latest version has not been checked yet")?;
+
+    // 执行本地命令时不持有数据库锁
+    let local_version = checker::get_local_version(&software);
 
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.update_local_version(id, local_version.as_deref())
+            .map_err(|e| e.to_string())?;
+    }
+
+    let published_at = software.published_at;
+    let has_update = software.has_update(strategy, &latest_version, &local_version, published_at);
+    let change_kind = software.change_kind(strategy, &latest_version, &local_version, published_at);
     Ok(VersionCheckResult {
-        software_id: id,
+        software_id: software.id,
+        has_update,
+        change_kind,
         latest_version,
         local_version,
-        published_at,
-        has_update,
+        published_at: software.published_at,
     })
 }
 
+/// 统计当前有可用更新的已启用软件数量，只使用数据库中记录的版本，不发起网络请求
+#[tauri::command]
+pub async fn count_updates(
+    db: State<'_, DbState>,
+    settings: State<'_, SettingsState>,
+) -> Result<usize, String> {
+    let strategy = settings.read().map_err(|e| e.to_string())?.non_semver_strategy;
+    let db = db.lock().map_err(|e| e.to_string())?;
+    count_updates_in(&db, strategy)
+}
+
+fn count_updates_in(db: &Database, strategy: NonSemverStrategy) -> Result<usize, String> {
+    let softwares = db.get_all_softwares().map_err(|e| e.to_string())?;
+    Ok(softwares
+        .iter()
+        .filter(|software| software.enabled)
+        .filter(|software| {
+            software.latest_version.as_deref().is_some_and(|latest| {
+                software.has_update(
+                    strategy,
+                    latest,
+                    &software.local_version,
+                    software.published_at,
+                )
+            })
+        })
+        .count())
+}
+
 #[tauri::command]
 pub async fn check_all_versions(
+    force_refresh: bool,
     db: State<'_, DbState>,
     cache: State<'_, CacheState>,
-    settings: State<'_, AppSettings>,
+    settings: State<'_, SettingsState>,
+    cancel: State<'_, CancelSignal>,
 ) -> Result<Vec<VersionCheckResult>, String> {
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+    let cancel = cancel.reset();
     let softwares = {
         let db = db.lock().map_err(|e| e.to_string())?;
         db.get_all_softwares().map_err(|e| e.to_string())?
     };
 
-    // 获取配置信息
-    let github_token = settings.github_token.clone();
-
     // 过滤启用的软件
     let enabled_softwares: Vec<_> = softwares.into_iter().filter(|s| s.enabled).collect();
 
-    if enabled_softwares.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    // 先检查缓存，分离出需要远程获取的软件
-    let mut cached_results = Vec::new();
-    let mut need_fetch = Vec::new();
+    let http = HttpClient::new(&settings.network)?;
+    let outcome = checker::check_softwares(
+        enabled_softwares,
+        force_refresh,
+        &db,
+        &cache,
+        &http,
+        &settings,
+        cancel,
+    )
+    .await?;
+    Ok(outcome.results)
+}
 
-    for software in enabled_softwares {
-        if let Some(cached) = cache.get(&software.id) {
-            let local_version = get_local_version(&software);
-            let has_update = comparator::has_update(&cached.latest_version, &local_version);
-            cached_results.push(VersionCheckResult {
-                software_id: software.id.clone(),
-                latest_version: cached.latest_version,
-                local_version,
-                published_at: cached.published_at,
-                has_update,
-            });
-        } else {
-            need_fetch.push(software);
-        }
-    }
+/// 筛选超过 `max_age_minutes` 分钟未检查的启用软件，从未检查过的也算
+fn stale_softwares(
+    softwares: Vec<Software>,
+    max_age_minutes: u32,
+    now: DateTime<Utc>,
+) -> Vec<Software> {
+    let max_age = chrono::Duration::minutes(max_age_minutes as i64);
+    softwares
+        .into_iter()
+        .filter(|s| {
+            s.enabled
+                && s.last_checked_at
+                    .is_none_or(|checked_at| now - checked_at > max_age)
+        })
+        .collect()
+}
 
-    // 如果没有需要获取的软件，直接返回缓存结果
-    if need_fetch.is_empty() {
-        return Ok(cached_results);
-    }
+/// 只检查超过 `max_age_minutes` 分钟未检查的启用软件，这些软件总是重新获取
+#[tauri::command]
+pub async fn check_stale_versions(
+    max_age_minutes: u32,
+    db: State<'_, DbState>,
+    cache: State<'_, CacheState>,
+    settings: State<'_, SettingsState>,
+    cancel: State<'_, CancelSignal>,
+) -> Result<Vec<VersionCheckResult>, String> {
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+    let cancel = cancel.reset();
+    let softwares = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.get_all_softwares().map_err(|e| e.to_string())?
+    };
 
-    // 并发数限制：避免 API 速率限制
-    // GitHub: 60次/小时（未认证）、5000次/小时（认证）
-    let max_concurrent = 5;
-    let semaphore = Arc::new(Semaphore::new(max_concurrent));
+    let stale = stale_softwares(softwares, max_age_minutes, Utc::now());
 
-    // 创建所有远程获取任务
-    let tasks: Vec<_> = need_fetch
-        .into_iter()
-        .map(|software| {
-            let sem = semaphore.clone();
-            let token = github_token.clone();
-
-            async move {
-                // 获取信号量许可
-                let _permit = sem.acquire().await.map_err(|e| e.to_string())?;
-
-                // 从远程获取版本
-                let fetch_result = fetch_remote_version(&software, token.as_deref()).await;
-
-                // 获取本地版本
-                let local_version = get_local_version(&software);
-
-                match fetch_result {
-                    Ok((latest_version, published_at)) => {
-                        let has_update = comparator::has_update(&latest_version, &local_version);
-                        Ok((
-                            software.id.clone(),
-                            VersionCheckResult {
-                                software_id: software.id,
-                                latest_version,
-                                local_version,
-                                published_at,
-                                has_update,
-                            },
-                        ))
-                    }
-                    Err(e) => Err(format!("Error checking {}: {}", software.name, e)),
-                }
-            }
-        })
-        .collect();
+    let http = HttpClient::new(&settings.network)?;
+    let outcome =
+        checker::check_softwares(stale, true, &db, &cache, &http, &settings, cancel).await?;
+    Ok(outcome.results)
+}
 
-    // 并发执行所有任务
-    let results = futures::future::join_all(tasks).await;
+/// 取消正在进行的"全部检查"，已完成的结果仍会保存
+#[tauri::command]
+pub async fn cancel_check(cancel: State<'_, CancelSignal>) -> Result<(), String> {
+    cancel.cancel();
+    Ok(())
+}
 
-    // 收集成功的结果并更新缓存
-    let mut all_results = cached_results;
-    for result in results {
-        match result {
-            Ok((id, check_result)) => {
-                // 更新缓存
-                cache.set(
-                    &id,
-                    check_result.latest_version.clone(),
-                    check_result.published_at,
-                );
-                all_results.push(check_result);
-            }
-            Err(e) => eprintln!("{}", e),
-        }
-    }
+/// 获取最新版本的发布说明，没有时返回空
+#[tauri::command]
+pub async fn get_release_notes(
+    id: String,
+    db: State<'_, DbState>,
+) -> Result<Option<String>, String> {
+    let db = db.lock().map_err(|e| e.to_string())?;
+    let software = db
+        .get_software(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Software not found")?;
+    Ok(software.release_notes)
+}
 
-    // 批量更新数据库
-    {
-        let db = db.lock().map_err(|e| e.to_string())?;
-        for result in &all_results {
-            if let Ok(Some(mut software)) = db.get_software(&result.software_id) {
-                software.latest_version = Some(result.latest_version.clone());
-                software.local_version = result.local_version.clone();
-                software.published_at = result.published_at;
-                software.last_checked_at = Some(Utc::now());
-                let _ = db.update_software(&software);
-            }
-        }
-    }
+/// 获取数据源的发布页面地址，供前端"打开发布页面"使用
+#[tauri::command]
+pub async fn get_source_url(
+    id: String,
+    db: State<'_, DbState>,
+    settings: State<'_, SettingsState>,
+) -> Result<String, String> {
+    let github_api_base_url = settings
+        .read()
+        .map_err(|e| e.to_string())?
+        .github_api_base_url()
+        .to_string();
+    let db = db.lock().map_err(|e| e.to_string())?;
+    let software = db
+        .get_software(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Software not found")?;
+    urls::release_url(&software.source, &github_api_base_url).ok_or_else(|| {
+        format!(
+            "No web page is available for {}",
+            software.source.source_type.as_str()
+        )
+    })
+}
 
-    Ok(all_results)
+/// 获取软件自己的数据源 token，软件列表中不包含钥匙串中的 token，编辑时再单独读取
+#[tauri::command]
+pub async fn get_source_token(
+    id: String,
+    db: State<'_, DbState>,
+) -> Result<Option<String>, String> {
+    let token = db
+        .lock()
+        .map_err(|e| e.to_string())?
+        .source_token(&id)
+        .map_err(|e| e.to_string())?;
+    Ok(token.load())
 }
 
-/// 从远程获取版本信息
-async fn fetch_remote_version(
-    software: &Software,
-    github_token: Option<&str>,
-) -> Result<(String, Option<DateTime<Utc>>), String> {
-    match software.source.source_type {
-        SourceType::GithubRelease => {
-            github::get_latest_release(&software.source.identifier, github_token).await
-        }
-        SourceType::GithubTags => {
-            github::get_latest_tag(&software.source.identifier, github_token).await
-        }
-        SourceType::Homebrew => {
-            let version = homebrew::get_version(&software.source.identifier).await?;
-            Ok((version, None))
-        }
-        SourceType::Npm => {
-            npm::get_latest_version(&software.source.identifier).await
-        }
-        SourceType::Pypi => {
-            pypi::get_latest_version(&software.source.identifier).await
-        }
-        SourceType::Cargo => {
-            cargo::get_latest_version(&software.source.identifier).await
-        }
-    }
+/// 查询 GitHub API 剩余限额
+#[tauri::command]
+pub async fn get_github_rate_limit(
+    settings: State<'_, SettingsState>,
+) -> Result<GithubRateLimit, String> {
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+    let http = HttpClient::new(&settings.network)?;
+    let rate_limit = github::get_rate_limit(
+        &http,
+        settings.github_api_base_url(),
+        settings.github_token.as_deref(),
+    )
+    .await?;
+    Ok(rate_limit)
 }
 
 #[tauri::command]
@@ -384,17 +903,121 @@ pub async fn get_settings(db: State<'_, DbState>) -> Result<AppSettings, String>
 pub async fn save_settings(
     new_settings: AppSettings,
     db: State<'_, DbState>,
+    cache: State<'_, CacheState>,
+    settings: State<'_, SettingsState>,
+    metrics: State<'_, MetricsState>,
+    app_handle: AppHandle,
 ) -> Result<(), String> {
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.save_settings(&new_settings).map_err(|e| e.to_string())?;
+    }
+
+    cache.set_ttl(new_settings.cache.ttl_minutes as i64);
+
+    // 之后的检查和调度都使用新的设置
+    *settings.write().map_err(|e| e.to_string())? = new_settings.clone();
+
+    let mut metrics = metrics.lock().await;
+    metrics.apply(&new_settings.metrics, app_handle).await
+}
+
+/// 查询最近发送的通知，`software_id` 为空时返回所有软件的记录
+#[tauri::command]
+pub async fn get_notification_history(
+    software_id: Option<String>,
+    limit: u32,
+    db: State<'_, DbState>,
+) -> Result<Vec<NotificationLogEntry>, String> {
+    let db = db.lock().map_err(|e| e.to_string())?;
+    db.get_notification_history(software_id.as_deref(), limit)
+        .map_err(|e| e.to_string())
+}
+
+/// 立即发送一条测试通知，检查通知权限和 webhook 地址是否可用
+///
+/// `channel` 为空时测试系统通知和已启用的 webhook，使用已保存的设置
+#[tauri::command]
+pub async fn send_test_notification(
+    channel: Option<String>,
+    settings: State<'_, SettingsState>,
+    app_handle: AppHandle,
+) -> Result<Vec<NotificationTestResult>, String> {
+    let channel = channel
+        .as_deref()
+        .map(|c| {
+            NotificationChannel::from_str(c)
+                .ok_or_else(|| format!("Unknown notification channel: {}", c))
+        })
+        .transpose()?;
+    let settings = settings.read().map_err(|e| e.to_string())?.clone();
+
+    let mut results = Vec::new();
+    if matches!(channel, None | Some(NotificationChannel::Desktop)) {
+        let notification = DesktopNotification {
+            body: "这是一条测试通知，收到说明系统通知可以正常显示".to_string(),
+            release_url: None,
+            covers: Vec::new(),
+        };
+        results.push(NotificationTestResult::new(
+            NotificationChannel::Desktop,
+            send_notification(&app_handle, &notification),
+        ));
+    }
+
+    let webhook = &settings.notification.webhook;
+    if channel == Some(NotificationChannel::Webhook) || (channel.is_none() && webhook.is_active())
+    {
+        let http = HttpClient::new(&settings.network)?;
+        results.push(NotificationTestResult::new(
+            NotificationChannel::Webhook,
+            send_test_webhook(&http, webhook).await,
+        ));
+    }
+
+    Ok(results)
+}
+
+/// 向配置的 webhook 发送一条示例更新通知
+async fn send_test_webhook(http: &HttpClient, webhook: &WebhookConfig) -> Result<(), String> {
+    let url = webhook.url.trim();
+    if url.is_empty() {
+        return Err("Webhook URL is not configured".to_string());
+    }
+    send_webhook(http, url, "示例软件", "1.0.0", Some("0.9.0")).await
+}
+
+/// 软件最新版本的变化历史，最新的在前
+#[tauri::command]
+pub async fn get_version_history(
+    id: String,
+    limit: u32,
+    db: State<'_, DbState>,
+) -> Result<Vec<VersionHistoryEntry>, String> {
     let db = db.lock().map_err(|e| e.to_string())?;
-    db.save_settings(&new_settings).map_err(|e| e.to_string())
+    db.get_version_history(&id, limit).map_err(|e| e.to_string())
 }
 
 // Helper functions
 
-fn get_local_version(software: &Software) -> Option<String> {
-    software.local_version_config.as_ref().and_then(|config| {
-        local_version::get_version(&config.command, config.version_arg.as_deref()).ok()
-    })
+/// 去掉标签首尾空白，丢弃空标签和重复标签
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+/// 提前检查用户填写的版本提取正则，避免保存后本地版本一直获取失败
+fn validate_local_version_config(form: &SoftwareFormData) -> Result<(), String> {
+    if let Some(config) = &form.local_version_config {
+        local_version::compile_version_regex(config.version_regex.as_deref())?;
+    }
+    Ok(())
 }
 
 // Scheduler Commands
@@ -418,3 +1041,476 @@ pub async fn update_scheduler(
 
     Ok(())
 }
+
+/// 暂停自动检查，不改变检查间隔
+#[tauri::command]
+pub async fn pause_scheduler(scheduler: State<'_, SchedulerState>) -> Result<(), String> {
+    scheduler.lock().await.pause();
+    Ok(())
+}
+
+/// 恢复自动检查
+#[tauri::command]
+pub async fn resume_scheduler(scheduler: State<'_, SchedulerState>) -> Result<(), String> {
+    scheduler.lock().await.resume();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn scheduler_status(
+    scheduler: State<'_, SchedulerState>,
+) -> Result<SchedulerStatus, String> {
+    Ok(scheduler.lock().await.status())
+}
+
+/// 下一次自动检查的时间
+#[tauri::command]
+pub async fn get_next_check_time(
+    scheduler: State<'_, SchedulerState>,
+) -> Result<Option<DateTime<Utc>>, String> {
+    Ok(scheduler.lock().await.next_check_time())
+}
+
+/// 查询连续检查失败、正在退避的软件
+#[tauri::command]
+pub async fn get_backoff_statuses(
+    db: State<'_, DbState>,
+    scheduler: State<'_, SchedulerState>,
+) -> Result<Vec<BackoffStatus>, String> {
+    let softwares = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.get_all_softwares().map_err(|e| e.to_string())?
+    };
+
+    let scheduler = scheduler.lock().await;
+    Ok(scheduler.backoff_statuses(&softwares))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::models::{CompareMode, LocalVersionConfig, SourceType};
+    use crate::version::VersionChange;
+
+    #[test]
+    fn test_refresh_local_version_rereads_command() {
+        let dir = std::env::temp_dir().join(format!("refresh-local-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let version_file = dir.join("version.txt");
+        std::fs::write(&version_file, "tool 1.0.0\n").unwrap();
+
+        let mut software = Software::new(
+            "tool".to_string(),
+            "Tool".to_string(),
+            SourceConfig::new(SourceType::GithubRelease, "owner/tool"),
+        );
+        software.latest_version = Some("1.1.0".to_string());
+        software.local_version_config = Some(LocalVersionConfig {
+            command: "sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                format!("cat '{}'", version_file.display()),
+            ],
+            ..LocalVersionConfig::default()
+        });
+
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        db.lock().unwrap().insert_software(&software).unwrap();
+
+        let strategy = NonSemverStrategy::default();
+        let result = refresh_local_version_in(&db, "tool", strategy).unwrap();
+        assert_eq!(result.local_version.as_deref(), Some("1.0.0"));
+        assert_eq!(result.latest_version, "1.1.0");
+        assert!(result.has_update);
+
+        // 模拟升级本地工具后命令输出发生变化
+        std::fs::write(&version_file, "tool 1.1.0\n").unwrap();
+        let result = refresh_local_version_in(&db, "tool", strategy).unwrap();
+        assert_eq!(result.local_version.as_deref(), Some("1.1.0"));
+        assert!(!result.has_update);
+        assert_eq!(result.change_kind, None);
+
+        let stored = db.lock().unwrap().get_software("tool").unwrap().unwrap();
+        assert_eq!(stored.local_version.as_deref(), Some("1.1.0"));
+        assert_eq!(stored.latest_version.as_deref(), Some("1.1.0"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_snooze_software() {
+        let db = Database::new(":memory:").unwrap();
+        let mut software = Software::new(
+            "tool".to_string(),
+            "Tool".to_string(),
+            SourceConfig::new(SourceType::Npm, "tool"),
+        );
+        software.local_version = Some("1.0.0".to_string());
+        db.insert_software(&software).unwrap();
+        let until = Utc::now() + chrono::Duration::days(3);
+
+        assert!(snooze_software_in(&db, "tool", until).is_err());
+        assert!(snooze_software_in(&db, "missing", until).is_err());
+
+        software.latest_version = Some("1.1.0".to_string());
+        db.update_software(&software).unwrap();
+        let snoozed = snooze_software_in(&db, "tool", until).unwrap();
+        assert_eq!(snoozed.snoozed_version.as_deref(), Some("1.1.0"));
+
+        let strategy = NonSemverStrategy::default();
+        assert!(!snoozed.has_update(strategy, "1.1.0", &snoozed.local_version, None));
+        assert!(snoozed.has_update(strategy, "1.2.0", &snoozed.local_version, None));
+    }
+
+    #[test]
+    fn test_compare_by_published_date() {
+        let db = Database::new(":memory:").unwrap();
+        let mut software = Software::new(
+            "nightly".to_string(),
+            "Nightly".to_string(),
+            SourceConfig {
+                compare_by: CompareMode::PublishedDate,
+                ..SourceConfig::new(SourceType::GithubTags, "owner/nightly")
+            },
+        );
+        let strategy = NonSemverStrategy::default();
+        let first = Utc::now() - chrono::Duration::days(2);
+        let later = Utc::now();
+
+        // 首次检查的发布作为基准
+        software.latest_version = Some("nightly".to_string());
+        software.published_at = Some(first);
+        software.refresh_seen_published_at();
+        assert_eq!(software.seen_published_at, Some(first));
+        assert!(!software.has_update(strategy, "nightly", &None, Some(first)));
+
+        // 版本号看起来没变，但发布时间更新了
+        assert!(software.has_update(strategy, "nightly", &None, Some(later)));
+        assert_eq!(
+            software.change_kind(strategy, "nightly", &None, Some(later)),
+            Some(VersionChange::Other)
+        );
+        software.source.compare_by = CompareMode::Version;
+        assert!(!software.has_update(strategy, "nightly", &None, Some(later)));
+        software.source.compare_by = CompareMode::PublishedDate;
+
+        software.published_at = Some(later);
+        db.insert_software(&software).unwrap();
+        assert_eq!(count_updates_in(&db, strategy).unwrap(), 1);
+
+        // 本地已是最新版本时视为已确认
+        software.local_version = Some("nightly".to_string());
+        assert!(!software.has_update(strategy, "nightly", &software.local_version, Some(later)));
+        software.refresh_seen_published_at();
+        assert_eq!(software.seen_published_at, Some(later));
+        db.update_software(&software).unwrap();
+        assert_eq!(count_updates_in(&db, strategy).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_count_updates() {
+        let db = Database::new(":memory:").unwrap();
+        let add = |id: &str, latest: Option<&str>, local: Option<&str>, enabled: bool| {
+            let mut software = Software::new(
+                id.to_string(),
+                id.to_string(),
+                SourceConfig::new(SourceType::Npm, id),
+            );
+            software.latest_version = latest.map(str::to_string);
+            software.local_version = local.map(str::to_string);
+            software.enabled = enabled;
+            db.insert_software(&software).unwrap();
+        };
+        add("outdated", Some("2.0.0"), Some("1.0.0"), true);
+        add("prefixed", Some("v1.3.0"), Some("1.2.0"), true);
+        add("current", Some("1.0.0"), Some("1.0.0"), true);
+        add("no-local", Some("1.0.0"), None, true);
+        add("unchecked", None, Some("1.0.0"), true);
+        add("disabled", Some("2.0.0"), Some("1.0.0"), false);
+        add("snoozed", Some("3.0.0"), Some("1.0.0"), true);
+        add("trashed", Some("2.0.0"), Some("1.0.0"), true);
+        let until = Utc::now() + chrono::Duration::days(1);
+        db.set_snooze("snoozed", Some("3.0.0"), Some(until)).unwrap();
+        db.delete_software("trashed").unwrap();
+
+        let strategy = NonSemverStrategy::default();
+        assert_eq!(count_updates_in(&db, strategy).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_refresh_local_version_requires_latest() {
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        let software = Software::new(
+            "tool".to_string(),
+            "Tool".to_string(),
+            SourceConfig::new(SourceType::Npm, "tool"),
+        );
+        db.lock().unwrap().insert_software(&software).unwrap();
+
+        assert!(refresh_local_version_in(&db, "tool", NonSemverStrategy::default()).is_err());
+        assert!(refresh_local_version_in(&db, "missing", NonSemverStrategy::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_stale_versions_fetches_only_stale() {
+        use crate::models::NetworkConfig;
+        use crate::test_support::{MockResponse, MockServer};
+
+        let server =
+            MockServer::start(|_| MockResponse::json(200, r#"{"dist-tags":{"latest":"2.0.0"}}"#))
+                .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url());
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        let cache = CacheState::new(60);
+        let settings = AppSettings::default();
+        let now = Utc::now();
+
+        let seeds = [
+            ("recent", Some(now - chrono::Duration::minutes(10)), true),
+            ("stale", Some(now - chrono::Duration::hours(2)), true),
+            ("never", None, true),
+            ("disabled", Some(now - chrono::Duration::hours(2)), false),
+        ];
+        {
+            let db = db.lock().unwrap();
+            for (id, last_checked_at, enabled) in seeds {
+                let mut software = Software::new(
+                    id.to_string(),
+                    id.to_string(),
+                    SourceConfig::new(SourceType::Npm, id),
+                );
+                software.last_checked_at = last_checked_at;
+                software.enabled = enabled;
+                db.insert_software(&software).unwrap();
+            }
+        }
+        // 缓存中的结果也不会阻止过期软件重新获取
+        cache.set("stale", "1.0.0".to_string(), None);
+
+        let softwares = db.lock().unwrap().get_all_softwares().unwrap();
+        let stale = stale_softwares(softwares, 60, now);
+        let cancel = CancelSignal::new();
+        let results =
+            checker::check_softwares(stale, true, &db, &cache, &http, &settings, cancel.reset())
+                .await
+                .unwrap()
+                .results;
+
+        let mut paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+        paths.sort();
+        assert_eq!(paths, ["/never", "/stale"]);
+        let mut ids: Vec<_> = results.iter().map(|r| r.software_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["never", "stale"]);
+
+        let db = db.lock().unwrap();
+        let stale = db.get_software("stale").unwrap().unwrap();
+        assert_eq!(stale.latest_version.as_deref(), Some("2.0.0"));
+        let recent = db.get_software("recent").unwrap().unwrap();
+        assert_eq!(recent.latest_version, None);
+    }
+
+    #[test]
+    fn test_ensure_unique_source() {
+        let db = Database::new(":memory:").unwrap();
+        let source = SourceConfig::new(SourceType::GithubRelease, "cli/tool");
+        assert!(ensure_unique_source(&db, &source, false).is_ok());
+
+        let software = Software::new("tool".to_string(), "Tool".to_string(), source.clone());
+        db.insert_software(&software).unwrap();
+
+        let err = ensure_unique_source(&db, &source, false).unwrap_err();
+        assert!(err.contains("Tool"), "{}", err);
+        assert!(ensure_unique_source(&db, &source, true).is_ok());
+
+        let other = SourceConfig {
+            source_type: SourceType::GithubTags,
+            ..source
+        };
+        assert!(ensure_unique_source(&db, &other, false).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_softwares_reports_each_item() {
+        use crate::models::NetworkConfig;
+        use crate::test_support::{MockResponse, MockServer};
+
+        let server = MockServer::start(|req| {
+            if req.path == "/react" {
+                MockResponse::json(200, r#"{"dist-tags":{"latest":"18.3.1"}}"#)
+            } else {
+                MockResponse::json(404, r#"{"error":"Not found"}"#)
+            }
+        })
+        .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url());
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        let cache = CacheState::new(60);
+
+        let form = |name: &str| SoftwareFormData {
+            name: name.to_string(),
+            source: SourceConfig::new(SourceType::Npm, name),
+            local_version_config: None,
+            check_interval_minutes: None,
+            tags: Vec::new(),
+        };
+
+        let result = add_softwares_in(
+            &db,
+            &cache,
+            &http,
+            &AppSettings::default(),
+            vec![form("react"), form("missing-package")],
+        )
+        .await;
+
+        assert_eq!(result.added.len(), 1);
+        let added = &result.added[0];
+        assert_eq!(added.latest_version.as_deref(), Some("18.3.1"));
+        assert_eq!(cache.get(&added.id).unwrap().latest_version, "18.3.1");
+
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].index, 1);
+        assert_eq!(result.failed[0].name, "missing-package");
+
+        assert_eq!(db.lock().unwrap().get_all_softwares().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_add_softwares_skips_duplicate_forms() {
+        use crate::models::NetworkConfig;
+        use crate::test_support::{MockResponse, MockServer};
+
+        let server =
+            MockServer::start(|_| MockResponse::json(200, r#"{"dist-tags":{"latest":"18.3.1"}}"#))
+                .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url());
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        let cache = CacheState::new(60);
+
+        let form = |identifier: &str| SoftwareFormData {
+            name: "React".to_string(),
+            source: SourceConfig::new(SourceType::Npm, identifier),
+            local_version_config: None,
+            check_interval_minutes: None,
+            tags: Vec::new(),
+        };
+
+        let result = add_softwares_in(
+            &db,
+            &cache,
+            &http,
+            &AppSettings::default(),
+            vec![form("react"), form(" react ")],
+        )
+        .await;
+
+        assert_eq!(result.added.len(), 1);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].index, 1);
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(db.lock().unwrap().get_all_softwares().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_test_webhook() {
+        use crate::models::NetworkConfig;
+        use crate::test_support::{MockResponse, MockServer};
+
+        let server = MockServer::start(|_| MockResponse::json(200, "{}")).await;
+        let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+        let webhook = WebhookConfig {
+            url: format!("{}/hooks/test", server.url()),
+            enabled: false,
+        };
+
+        send_test_webhook(&http, &webhook).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/hooks/test");
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["software"], "示例软件");
+    }
+
+    #[tokio::test]
+    async fn test_send_test_webhook_failure() {
+        use crate::models::NetworkConfig;
+        use crate::test_support::{MockResponse, MockServer};
+
+        let server = MockServer::start(|_| MockResponse::json(404, "{}")).await;
+        let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+        let webhook = WebhookConfig {
+            url: server.url().to_string(),
+            enabled: true,
+        };
+        let result = NotificationTestResult::new(
+            NotificationChannel::Webhook,
+            send_test_webhook(&http, &webhook).await,
+        );
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("Webhook error: 404 Not Found"));
+
+        let unconfigured = send_test_webhook(&http, &WebhookConfig::default()).await;
+        assert_eq!(unconfigured.unwrap_err(), "Webhook URL is not configured");
+    }
+
+    #[test]
+    fn test_duplicate_software() {
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        let mut software = Software::new(
+            "tool".to_string(),
+            "Tool".to_string(),
+            SourceConfig::new(SourceType::GithubRelease, "owner/tool"),
+        );
+        software.latest_version = Some("2.0.0".to_string());
+        software.last_notified_version = Some("2.0.0".to_string());
+        software.last_notified_at = Some(Utc::now());
+        software.tags = vec!["cli".to_string()];
+        {
+            let db = db.lock().unwrap();
+            db.insert_software(&software).unwrap();
+            db.set_snooze("tool", Some("2.0.0"), Some(Utc::now() + chrono::Duration::days(1)))
+                .unwrap();
+            db.set_last_error("tool", Some("timeout"), Some(Utc::now()))
+                .unwrap();
+        }
+        let stored = db.lock().unwrap().get_software("tool").unwrap().unwrap();
+        assert!(stored.snoozed_until.is_some());
+
+        let copy = duplicate_software_in(&db, "tool").unwrap();
+
+        assert_ne!(copy.id, "tool");
+        assert_eq!(copy.name, "Tool (copy)");
+        assert_eq!(copy.source.identifier, "owner/tool");
+        assert_eq!(copy.latest_version.as_deref(), Some("2.0.0"));
+        assert_eq!(copy.tags, vec!["cli"]);
+        assert_eq!(copy.last_notified_version, None);
+        assert_eq!(copy.last_notified_at, None);
+        assert_eq!(copy.snoozed_until, None);
+        assert_eq!(copy.snoozed_version, None);
+        assert_eq!(copy.last_error, None);
+
+        let db = db.lock().unwrap();
+        assert_eq!(db.get_all_softwares().unwrap().len(), 2);
+        let original = db.get_software("tool").unwrap().unwrap();
+        assert_eq!(original.last_notified_version.as_deref(), Some("2.0.0"));
+        assert!(copy.sort_order > original.sort_order);
+    }
+
+    #[test]
+    fn test_duplicate_missing_software() {
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        assert_eq!(
+            duplicate_software_in(&db, "missing").unwrap_err(),
+            "Software not found"
+        );
+    }
+}
@@ -1,22 +1,653 @@
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, Result};
+use rusqlite::{params, Connection, OptionalExtension, Result, Row};
+use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
-use crate::models::{AppSettings, LocalVersionConfig, NotificationConfig, Software, SourceConfig, SourceType, ThemeMode};
+use crate::cache::CacheEntry;
+use crate::models::{
+    AppSettings, CheckFailure, CompareMode, LocalVersionConfig, LocalVersionSource,
+    NonSemverStrategy, NotificationChannel, NotificationLogEntry, NotificationStyle,
+    ReleaseChannel, ReleaseDetails, Software, SourceConfig, SourceType, ThemeMode,
+    VersionCheckResult, VersionFrom, VersionHistoryEntry,
+};
+use crate::secrets::{self, SecretStore};
+
+/// 数据库迁移列表，第 N 个迁移执行后 schema 版本为 N
+///
+/// 只能在末尾追加新迁移，不要修改或删除已发布的迁移
+const MIGRATIONS: &[fn(&Connection) -> Result<()>] = &[
+    migrate_v1_notification_fields,
+    migrate_v2_cache_entries,
+    migrate_v3_check_interval,
+    migrate_v4_notification_log,
+    migrate_v5_local_version_regex,
+    migrate_v6_local_version_timeout,
+    migrate_v7_local_version_command,
+    migrate_v8_local_version_file,
+    migrate_v9_include_prerelease,
+    migrate_v10_release_notes,
+    migrate_v11_tags,
+    migrate_v12_sort_order,
+    migrate_v13_version_history,
+    migrate_v14_snooze,
+    migrate_v15_version_filter,
+    migrate_v16_soft_delete,
+    migrate_v17_required_asset,
+    migrate_v18_tag_prefix,
+    migrate_v19_last_error,
+    migrate_v20_local_version_shell,
+    migrate_v21_source_auth_token,
+    migrate_v22_compare_by,
+    migrate_v23_version_from,
+    migrate_v24_channel,
+    migrate_v25_source_auth_token_keychain,
+];
+
+/// v1: 添加通知相关字段
+fn migrate_v1_notification_fields(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "last_notified_version", "TEXT")?;
+    add_column_if_missing(conn, "softwares", "last_notified_at", "TEXT")
+}
+
+/// v2: 持久化版本缓存
+fn migrate_v2_cache_entries(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cache_entries (
+            key TEXT PRIMARY KEY,
+            latest_version TEXT NOT NULL,
+            published_at TEXT,
+            cached_at TEXT NOT NULL,
+            ttl_minutes INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v3: 单独的检查间隔
+fn migrate_v3_check_interval(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "check_interval_minutes", "INTEGER")
+}
+
+/// v4: 通知历史
+fn migrate_v4_notification_log(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS notification_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            software_id TEXT NOT NULL,
+            version TEXT NOT NULL,
+            sent_at TEXT NOT NULL,
+            channel TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_notification_log_software
+         ON notification_log (software_id, sent_at)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v5: 自定义本地版本提取正则
+fn migrate_v5_local_version_regex(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "local_version_regex", "TEXT")
+}
+
+/// v6: 本地命令超时时间
+fn migrate_v6_local_version_timeout(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "local_version_timeout_seconds", "INTEGER")
+}
+
+/// v7: 本地命令的参数列表、工作目录和环境变量（列表和映射以 JSON 存储）
+fn migrate_v7_local_version_command(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "local_version_args", "TEXT")?;
+    add_column_if_missing(conn, "softwares", "local_version_working_dir", "TEXT")?;
+    add_column_if_missing(conn, "softwares", "local_version_env", "TEXT")
+}
+
+/// v8: 从文件读取本地版本
+fn migrate_v8_local_version_file(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "local_version_file_path", "TEXT")?;
+    add_column_if_missing(conn, "softwares", "local_version_json_pointer", "TEXT")
+}
+
+/// v9: GitHub Release 是否包含预发布版本
+fn migrate_v9_include_prerelease(conn: &Connection) -> Result<()> {
+    add_column_if_missing(
+        conn,
+        "softwares",
+        "source_include_prerelease",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+/// v10: 最新版本的发布说明
+fn migrate_v10_release_notes(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "release_notes", "TEXT")
+}
+
+/// v11: 分组标签（JSON 数组）
+fn migrate_v11_tags(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "tags", "TEXT")
+}
+
+/// v12: 自定义显示顺序，已有的软件按插入顺序排列
+fn migrate_v12_sort_order(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "sort_order", "INTEGER NOT NULL DEFAULT 0")?;
+    conn.execute("UPDATE softwares SET sort_order = rowid", [])?;
+    Ok(())
+}
+
+/// v13: 最新版本的变化历史
+fn migrate_v13_version_history(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS version_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            software_id TEXT NOT NULL,
+            version TEXT NOT NULL,
+            observed_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_version_history_software
+         ON version_history (software_id, observed_at)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v14: 暂缓提醒
+fn migrate_v14_snooze(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "snoozed_until", "TEXT")?;
+    add_column_if_missing(conn, "softwares", "snoozed_version", "TEXT")?;
+    Ok(())
+}
+
+/// v15: 数据源的版本筛选正则
+fn migrate_v15_version_filter(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "source_version_filter_regex", "TEXT")?;
+    add_column_if_missing(conn, "softwares", "source_version_exclude_regex", "TEXT")?;
+    Ok(())
+}
+
+/// v16: 回收站
+fn migrate_v16_soft_delete(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "deleted_at", "TEXT")
+}
+
+/// v17: GitHub Release 的附件要求和匹配的附件地址
+fn migrate_v17_required_asset(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "source_required_asset", "TEXT")?;
+    add_column_if_missing(conn, "softwares", "asset_url", "TEXT")?;
+    Ok(())
+}
+
+/// v18: monorepo 的 tag 前缀
+fn migrate_v18_tag_prefix(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "source_tag_prefix", "TEXT")
+}
+
+/// v19: 最近一次检查失败的原因
+fn migrate_v19_last_error(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "last_error", "TEXT")?;
+    add_column_if_missing(conn, "softwares", "last_error_at", "TEXT")?;
+    Ok(())
+}
+
+/// v20: 通过 shell 执行本地版本命令
+fn migrate_v20_local_version_shell(conn: &Connection) -> Result<()> {
+    add_column_if_missing(
+        conn,
+        "softwares",
+        "local_version_use_shell",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+/// v21: 单个数据源的 GitHub token
+fn migrate_v21_source_auth_token(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "source_auth_token", "TEXT")
+}
+
+/// v22: 按发布时间判断更新
+fn migrate_v22_compare_by(conn: &Connection) -> Result<()> {
+    add_column_if_missing(
+        conn,
+        "softwares",
+        "source_compare_by",
+        "TEXT NOT NULL DEFAULT 'version'",
+    )?;
+    add_column_if_missing(conn, "softwares", "seen_published_at", "TEXT")?;
+    Ok(())
+}
+
+/// v23: GitHub Release 版本号的来源字段和提取正则
+fn migrate_v23_version_from(conn: &Connection) -> Result<()> {
+    add_column_if_missing(
+        conn,
+        "softwares",
+        "source_version_from",
+        "TEXT NOT NULL DEFAULT 'tag-name'",
+    )?;
+    add_column_if_missing(conn, "softwares", "source_version_extract_regex", "TEXT")?;
+    Ok(())
+}
+
+/// v24: 跟踪的发布渠道
+fn migrate_v24_channel(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "softwares", "source_channel", "TEXT NOT NULL DEFAULT 'any'")
+}
+
+/// v25: 单个数据源的 token 改为保存在钥匙串中，`source_auth_token` 只在钥匙串不可用时使用
+fn migrate_v25_source_auth_token_keychain(conn: &Connection) -> Result<()> {
+    add_column_if_missing(
+        conn,
+        "softwares",
+        "source_auth_token_in_keychain",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+/// 添加列（如果不存在）
+///
+/// 早期版本在没有版本号的情况下已经添加过部分列，迁移需要兼容这种情况
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let column_exists: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+        params![table, column],
+        |row| row.get::<_, i32>(0).map(|count| count > 0),
+    )?;
+
+    if !column_exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// 查询 `Software` 时使用的列，需要与 `row_to_software` 保持一致
+const SOFTWARE_COLUMNS: &str = "id, name, source_type, source_identifier, local_command,
+    local_version_arg, latest_version, local_version, published_at, last_checked_at, enabled,
+    last_notified_version, last_notified_at, check_interval_minutes, local_version_regex,
+    local_version_timeout_seconds, local_version_args, local_version_working_dir,
+    local_version_env, local_version_file_path, local_version_json_pointer,
+    source_include_prerelease, release_notes, tags, sort_order, snoozed_until, snoozed_version,
+    source_version_filter_regex, source_version_exclude_regex, deleted_at,
+    source_required_asset, asset_url, source_tag_prefix, last_error, last_error_at,
+    local_version_use_shell, source_auth_token, source_compare_by, seen_published_at,
+    source_version_from, source_version_extract_regex, source_channel,
+    source_auth_token_in_keychain";
+
+/// 本地命令参数列表的 JSON，为空时存 NULL
+fn local_version_args_json(software: &Software) -> Option<String> {
+    software
+        .local_version_config
+        .as_ref()
+        .filter(|c| !c.args.is_empty())
+        .and_then(|c| serde_json::to_string(&c.args).ok())
+}
+
+/// 标签列表的 JSON，为空时存 NULL
+fn tags_json(software: &Software) -> Option<String> {
+    if software.tags.is_empty() {
+        return None;
+    }
+    serde_json::to_string(&software.tags).ok()
+}
+
+/// 本地命令环境变量的 JSON
+fn local_version_env_json(software: &Software) -> Option<String> {
+    software
+        .local_version_config
+        .as_ref()
+        .and_then(|c| c.env.as_ref())
+        .and_then(|env| serde_json::to_string(env).ok())
+}
+
+/// 从文件读取本地版本时的文件路径
+fn local_version_file_path(software: &Software) -> Option<&String> {
+    match software.local_version_config.as_ref().map(|c| &c.source) {
+        Some(LocalVersionSource::FileRead { path, .. }) => Some(path),
+        _ => None,
+    }
+}
+
+/// 读取文件或解析命令 JSON 输出时使用的 JSON 路径
+fn local_version_json_pointer(software: &Software) -> Option<&String> {
+    match software.local_version_config.as_ref().map(|c| &c.source) {
+        Some(LocalVersionSource::FileRead { json_pointer, .. }) => json_pointer.as_ref(),
+        Some(LocalVersionSource::CommandJson { json_pointer }) => Some(json_pointer),
+        _ => None,
+    }
+}
+
+/// 转义 LIKE 通配符，配合 `ESCAPE '\'` 使用
+fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 将 `softwares` 表的一行转换为 `Software`，保存在钥匙串中的数据源 token 不在这里读取
+fn row_to_software(row: &Row) -> Result<Software> {
+    let source_type_str: String = row.get("source_type")?;
+    let source_type = SourceType::from_str(&source_type_str)
+        .unwrap_or(SourceType::GithubRelease);
+
+    let local_command: Option<String> = row.get("local_command")?;
+    let local_version_arg: Option<String> = row.get("local_version_arg")?;
+    let local_version_regex: Option<String> = row.get("local_version_regex")?;
+    let local_version_timeout: Option<u32> = row.get("local_version_timeout_seconds")?;
+    let local_version_args: Option<String> = row.get("local_version_args")?;
+    let local_version_working_dir: Option<String> = row.get("local_version_working_dir")?;
+    let local_version_env: Option<String> = row.get("local_version_env")?;
+    let local_version_use_shell = row.get::<_, i32>("local_version_use_shell")? != 0;
+    let local_version_json_pointer: Option<String> = row.get("local_version_json_pointer")?;
+    let local_version_source = match row.get::<_, Option<String>>("local_version_file_path")? {
+        Some(path) => LocalVersionSource::FileRead {
+            path,
+            json_pointer: local_version_json_pointer,
+        },
+        None => match local_version_json_pointer {
+            Some(json_pointer) => LocalVersionSource::CommandJson { json_pointer },
+            None => LocalVersionSource::Command,
+        },
+    };
+    let has_local_version =
+        local_command.is_some() || local_version_source != LocalVersionSource::Command;
+    let local_version_config = has_local_version.then(|| LocalVersionConfig {
+        source: local_version_source,
+        command: local_command.unwrap_or_default(),
+        version_arg: local_version_arg,
+        version_regex: local_version_regex,
+        timeout_seconds: local_version_timeout,
+        args: local_version_args
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        working_dir: local_version_working_dir,
+        env: local_version_env.and_then(|json| serde_json::from_str(&json).ok()),
+        use_shell: local_version_use_shell,
+    });
+
+    let published_at_str: Option<String> = row.get("published_at")?;
+    let published_at = published_at_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    let last_checked_at_str: Option<String> = row.get("last_checked_at")?;
+    let last_checked_at = last_checked_at_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    let last_notified_at_str: Option<String> = row.get("last_notified_at")?;
+    let last_notified_at = last_notified_at_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    let snoozed_until_str: Option<String> = row.get("snoozed_until")?;
+    let snoozed_until = snoozed_until_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    let deleted_at_str: Option<String> = row.get("deleted_at")?;
+    let deleted_at = deleted_at_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    let last_error_at_str: Option<String> = row.get("last_error_at")?;
+    let last_error_at = last_error_at_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    let seen_published_at_str: Option<String> = row.get("seen_published_at")?;
+    let seen_published_at = seen_published_at_str
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    let compare_by: String = row.get("source_compare_by")?;
+    let version_from: String = row.get("source_version_from")?;
+    let channel: String = row.get("source_channel")?;
+
+    Ok(Software {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        source: SourceConfig {
+            source_type,
+            identifier: row.get("source_identifier")?,
+            include_prerelease: row.get::<_, i32>("source_include_prerelease")? != 0,
+            version_filter_regex: row.get("source_version_filter_regex")?,
+            version_exclude_regex: row.get("source_version_exclude_regex")?,
+            required_asset: row.get("source_required_asset")?,
+            tag_prefix: row.get("source_tag_prefix")?,
+            auth_token: row.get("source_auth_token")?,
+            compare_by: CompareMode::from_str(&compare_by).unwrap_or_default(),
+            version_from: VersionFrom::from_str(&version_from).unwrap_or_default(),
+            version_extract_regex: row.get("source_version_extract_regex")?,
+            channel: ReleaseChannel::from_str(&channel).unwrap_or_default(),
+        },
+        local_version_config,
+        latest_version: row.get("latest_version")?,
+        local_version: row.get("local_version")?,
+        published_at,
+        last_checked_at,
+        enabled: row.get::<_, i32>("enabled")? != 0,
+        last_notified_version: row.get("last_notified_version")?,
+        last_notified_at,
+        check_interval_minutes: row.get("check_interval_minutes")?,
+        release_notes: row.get("release_notes")?,
+        asset_url: row.get("asset_url")?,
+        tags: row
+            .get::<_, Option<String>>("tags")?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        sort_order: row.get("sort_order")?,
+        snoozed_until,
+        snoozed_version: row.get("snoozed_version")?,
+        deleted_at,
+        last_error: row.get("last_error")?,
+        last_error_at,
+        seen_published_at,
+    })
+}
 
 pub struct Database {
     conn: Connection,
+    secrets: Arc<dyn SecretStore>,
+}
+
+/// 保存在系统钥匙串中、而不是 settings 表中的设置项
+const GITHUB_TOKEN_KEY: &str = "github_token";
+const BITBUCKET_TOKEN_KEY: &str = "bitbucket_token";
+const GITEA_TOKEN_KEY: &str = "gitea_token";
+
+/// 软件自己的数据源 token 在钥匙串中的键名
+fn source_token_key(id: &str) -> String {
+    format!("source_token:{}", id)
+}
+
+/// 读取钥匙串中的凭据，读取失败时记录日志并视为没有
+fn read_secret(secrets: &dyn SecretStore, key: &str) -> Option<String> {
+    secrets.get(key).unwrap_or_else(|e| {
+        eprintln!("[Secrets] Failed to read {} from keychain: {}", key, e);
+        None
+    })
+}
+
+/// 软件自己的数据源 token，列表查询不读取钥匙串，远程获取前再通过 `load` 读取
+pub enum SourceToken {
+    /// 没有 token，或钥匙串不可用时明文保存在数据库中的 token
+    Stored(Option<String>),
+    /// 保存在钥匙串中的 token 及其键名
+    Keychain(Arc<dyn SecretStore>, String),
+}
+
+impl SourceToken {
+    /// 读取 token，不需要持有数据库锁
+    pub fn load(self) -> Option<String> {
+        match self {
+            SourceToken::Stored(token) => token,
+            SourceToken::Keychain(secrets, key) => read_secret(secrets.as_ref(), &key),
+        }
+    }
 }
 
 impl Database {
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
         let conn = Connection::open(path)?;
-        let db = Database { conn };
+        let db = Database {
+            conn,
+            secrets: secrets::default_store().into(),
+        };
         db.init_tables()?;
+        for key in [GITHUB_TOKEN_KEY, BITBUCKET_TOKEN_KEY, GITEA_TOKEN_KEY] {
+            db.move_plaintext_secret(key)?;
+        }
+        db.move_plaintext_source_tokens()?;
         Ok(db)
     }
 
+    /// 把旧版本明文保存在 settings 表中的凭据移入钥匙串，钥匙串不可用时保留原值
+    fn move_plaintext_secret(&self, key: &str) -> Result<()> {
+        let value: Option<String> = self
+            .conn
+            .query_row("SELECT value FROM settings WHERE key = ?1", params![key], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        let Some(value) = value else {
+            return Ok(());
+        };
+        match self.secrets.set(key, &value) {
+            Ok(()) => {
+                self.conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+            }
+            Err(e) => eprintln!("[Secrets] Failed to move {} to keychain: {}", key, e),
+        }
+        Ok(())
+    }
+
+    /// 将凭据保存到钥匙串，钥匙串不可用时退回明文保存在 settings 表
+    fn save_secret(&self, key: &str, value: &str) -> Result<()> {
+        match self.secrets.set(key, value) {
+            Ok(()) => {
+                self.conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+            }
+            Err(e) => {
+                eprintln!("[Secrets] Keychain unavailable, storing {} in database: {}", key, e);
+                self.conn.execute(
+                    "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                    params![key, value],
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_secret(&self, key: &str) -> Option<String> {
+        read_secret(self.secrets.as_ref(), key)
+    }
+
+    /// 从钥匙串和 settings 表中同时删除凭据，避免清空后又读到旧值
+    fn delete_secret(&self, key: &str) -> Result<()> {
+        if let Err(e) = self.secrets.delete(key) {
+            eprintln!("[Secrets] Failed to delete {} from keychain: {}", key, e);
+        }
+        self.conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+
+    /// 把旧版本明文保存的数据源 token 移入钥匙串，钥匙串不可用时保留原值
+    fn move_plaintext_source_tokens(&self) -> Result<()> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, source_auth_token FROM softwares
+             WHERE source_auth_token IS NOT NULL AND source_auth_token_in_keychain = 0",
+        )?;
+        let tokens = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>>>()?;
+        for (id, token) in tokens {
+            let key = source_token_key(&id);
+            match self.secrets.set(&key, &token) {
+                Ok(()) => {
+                    self.conn.execute(
+                        "UPDATE softwares SET source_auth_token = NULL,
+                         source_auth_token_in_keychain = 1 WHERE id = ?1",
+                        params![id],
+                    )?;
+                }
+                Err(e) => eprintln!("[Secrets] Failed to move {} to keychain: {}", key, e),
+            }
+        }
+        Ok(())
+    }
+
+    /// 保存软件自己的数据源 token，返回 `source_auth_token` 和 `source_auth_token_in_keychain` 列的值
+    ///
+    /// token 没有变化时不写钥匙串
+    fn store_source_token(&self, id: &str, token: Option<&str>) -> Result<(Option<String>, bool)> {
+        let (plaintext, in_keychain) = self
+            .conn
+            .query_row(
+                "SELECT source_auth_token, source_auth_token_in_keychain FROM softwares
+                 WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, i32>(1)? != 0)),
+            )
+            .optional()?
+            .unwrap_or((None, false));
+        let key = source_token_key(id);
+
+        if in_keychain {
+            if token.is_some() && self.read_secret(&key).as_deref() == token {
+                return Ok((None, true));
+            }
+        } else if token.is_some() && plaintext.as_deref() == token {
+            return Ok((plaintext, false));
+        }
+
+        match token {
+            Some(token) => match self.secrets.set(&key, token) {
+                Ok(()) => Ok((None, true)),
+                Err(e) => {
+                    eprintln!("[Secrets] Keychain unavailable, storing {} in database: {}", key, e);
+                    Ok((Some(token.to_string()), false))
+                }
+            },
+            None => {
+                if in_keychain {
+                    if let Err(e) = self.secrets.delete(&key) {
+                        eprintln!("[Secrets] Failed to delete {} from keychain: {}", key, e);
+                    }
+                }
+                Ok((None, false))
+            }
+        }
+    }
+
+    /// 满足 `condition` 且 token 保存在钥匙串中的软件 ID，删除这些软件后用于清理钥匙串
+    fn ids_with_keychain_token(&self, condition: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id FROM softwares WHERE source_auth_token_in_keychain = 1 AND {}",
+            condition
+        ))?;
+        let ids = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        ids.collect()
+    }
+
+    fn delete_source_tokens(&self, ids: &[String]) {
+        for id in ids {
+            let key = source_token_key(id);
+            if let Err(e) = self.secrets.delete(&key) {
+                eprintln!("[Secrets] Failed to delete {} from keychain: {}", key, e);
+            }
+        }
+    }
+
     fn init_tables(&self) -> Result<()> {
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS softwares (
@@ -43,100 +674,128 @@ impl Database {
             [],
         )?;
 
-        // 数据库迁移：添加通知相关字段
-        self.migrate_add_notification_fields()?;
+        self.run_migrations()?;
 
         Ok(())
     }
 
-    /// 数据库迁移：添加通知相关字段
-    fn migrate_add_notification_fields(&self) -> Result<()> {
-        // 检查 last_notified_version 列是否存在
-        let column_exists: bool = self.conn.query_row(
-            "SELECT COUNT(*) FROM pragma_table_info('softwares') WHERE name='last_notified_version'",
-            [],
-            |row| row.get::<_, i32>(0).map(|count| count > 0),
-        )?;
+    /// 依次执行尚未应用的迁移，每个迁移完成后更新 `user_version`
+    fn run_migrations(&self) -> Result<()> {
+        let current = self.current_schema_version()? as usize;
 
-        if !column_exists {
-            // 添加新列
-            self.conn.execute(
-                "ALTER TABLE softwares ADD COLUMN last_notified_version TEXT",
-                [],
-            )?;
-            self.conn.execute(
-                "ALTER TABLE softwares ADD COLUMN last_notified_at TEXT",
-                [],
-            )?;
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(current) {
+            let tx = self.conn.unchecked_transaction()?;
+            migration(&tx)?;
+            tx.pragma_update(None, "user_version", (index + 1) as u32)?;
+            tx.commit()?;
         }
 
         Ok(())
     }
 
+    /// 当前数据库的 schema 版本（`PRAGMA user_version`）
+    pub fn current_schema_version(&self) -> Result<u32> {
+        self.conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+    }
+
+    /// 所有软件，不包括回收站中的
     pub fn get_all_softwares(&self) -> Result<Vec<Software>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, name, source_type, source_identifier, local_command, local_version_arg,
-                    latest_version, local_version, published_at, last_checked_at, enabled,
-                    last_notified_version, last_notified_at
-             FROM softwares ORDER BY name"
-        )?;
+        self.get_softwares(false)
+    }
 
-        let software_iter = stmt.query_map([], |row| {
-            let source_type_str: String = row.get(2)?;
-            let source_type = SourceType::from_str(&source_type_str)
-                .unwrap_or(SourceType::GithubRelease);
+    /// `include_deleted` 为 true 时同时返回回收站中的软件
+    pub fn get_softwares(&self, include_deleted: bool) -> Result<Vec<Software>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM softwares WHERE ?1 OR deleted_at IS NULL ORDER BY sort_order, name",
+            SOFTWARE_COLUMNS
+        ))?;
 
-            let local_command: Option<String> = row.get(4)?;
-            let local_version_arg: Option<String> = row.get(5)?;
-            let local_version_config = local_command.map(|cmd| LocalVersionConfig {
-                command: cmd,
-                version_arg: local_version_arg,
-            });
+        let software_iter = stmt.query_map(params![include_deleted], row_to_software)?;
 
-            let published_at_str: Option<String> = row.get(8)?;
-            let published_at = published_at_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
+        software_iter.collect()
+    }
 
-            let last_checked_at_str: Option<String> = row.get(9)?;
-            let last_checked_at = last_checked_at_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
+    /// 带有指定标签的软件
+    pub fn get_softwares_by_tag(&self, tag: &str) -> Result<Vec<Software>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM softwares
+             WHERE deleted_at IS NULL
+               AND EXISTS (SELECT 1 FROM json_each(softwares.tags) WHERE value = ?1)
+             ORDER BY sort_order, name",
+            SOFTWARE_COLUMNS
+        ))?;
 
-            let last_notified_at_str: Option<String> = row.get(12)?;
-            let last_notified_at = last_notified_at_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
+        let software_iter = stmt.query_map(params![tag], row_to_software)?;
 
-            Ok(Software {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                source: SourceConfig {
-                    source_type,
-                    identifier: row.get(3)?,
-                },
-                local_version_config,
-                latest_version: row.get(6)?,
-                local_version: row.get(7)?,
-                published_at,
-                last_checked_at,
-                enabled: row.get::<_, i32>(10)? != 0,
-                last_notified_version: row.get(11)?,
-                last_notified_at,
-            })
-        })?;
+        software_iter.collect()
+    }
+
+    /// 按名称、数据源标识或最新版本搜索（不区分大小写）
+    pub fn search_softwares(&self, query: &str) -> Result<Vec<Software>> {
+        let pattern = format!("%{}%", escape_like(query.trim()));
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM softwares
+             WHERE deleted_at IS NULL
+               AND (name LIKE ?1 ESCAPE '\\'
+                OR source_identifier LIKE ?1 ESCAPE '\\'
+                OR latest_version LIKE ?1 ESCAPE '\\')
+             ORDER BY sort_order, name",
+            SOFTWARE_COLUMNS
+        ))?;
+
+        let software_iter = stmt.query_map(params![pattern], row_to_software)?;
 
         software_iter.collect()
     }
 
     pub fn get_software(&self, id: &str) -> Result<Option<Software>> {
-        let softwares = self.get_all_softwares()?;
-        Ok(softwares.into_iter().find(|s| s.id == id))
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM softwares WHERE id = ?1", SOFTWARE_COLUMNS),
+                params![id],
+                row_to_software,
+            )
+            .optional()
+    }
+
+    /// 查找数据源类型和标识都相同的软件
+    pub fn find_by_source(
+        &self,
+        source_type: &SourceType,
+        identifier: &str,
+    ) -> Result<Option<Software>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM softwares
+                     WHERE source_type = ?1 AND source_identifier = ?2
+                       AND deleted_at IS NULL
+                     ORDER BY sort_order LIMIT 1",
+                    SOFTWARE_COLUMNS
+                ),
+                params![source_type.as_str(), identifier.trim()],
+                row_to_software,
+            )
+            .optional()
     }
 
     pub fn insert_software(&self, software: &Software) -> Result<()> {
+        let (auth_token, in_keychain) =
+            self.store_source_token(&software.id, software.source.auth_token.as_deref())?;
         self.conn.execute(
             "INSERT INTO softwares (id, name, source_type, source_identifier, local_command,
              local_version_arg, latest_version, local_version, published_at, last_checked_at, enabled,
-             last_notified_version, last_notified_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+             last_notified_version, last_notified_at, check_interval_minutes, local_version_regex,
+             local_version_timeout_seconds, local_version_args, local_version_working_dir,
+             local_version_env, local_version_file_path, local_version_json_pointer,
+             source_include_prerelease, release_notes, tags, source_version_filter_regex,
+             source_version_exclude_regex, source_required_asset, asset_url, source_tag_prefix,
+             local_version_use_shell, source_auth_token, source_compare_by, seen_published_at,
+             source_version_from, source_version_extract_regex, source_channel,
+             source_auth_token_in_keychain, sort_order)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+             ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33,
+             ?34, ?35, ?36, ?37, (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM softwares))",
             params![
                 software.id,
                 software.name,
@@ -151,17 +810,50 @@ impl Database {
                 software.enabled as i32,
                 software.last_notified_version,
                 software.last_notified_at.map(|dt| dt.to_rfc3339()),
+                software.check_interval_minutes,
+                software.local_version_config.as_ref().and_then(|c| c.version_regex.as_ref()),
+                software.local_version_config.as_ref().and_then(|c| c.timeout_seconds),
+                local_version_args_json(software),
+                software.local_version_config.as_ref().and_then(|c| c.working_dir.as_ref()),
+                local_version_env_json(software),
+                local_version_file_path(software),
+                local_version_json_pointer(software),
+                software.source.include_prerelease as i32,
+                software.release_notes,
+                tags_json(software),
+                software.source.version_filter_regex,
+                software.source.version_exclude_regex,
+                software.source.required_asset,
+                software.asset_url,
+                software.source.tag_prefix,
+                software.local_version_config.as_ref().is_some_and(|c| c.use_shell) as i32,
+                auth_token,
+                software.source.compare_by.as_str(),
+                software.seen_published_at.map(|dt| dt.to_rfc3339()),
+                software.source.version_from.as_str(),
+                software.source.version_extract_regex,
+                software.source.channel.as_str(),
+                in_keychain as i32,
             ],
         )?;
         Ok(())
     }
 
+    /// 更新软件的配置和状态，不改动数据源 token，token 由 `set_source_token` 单独保存
     pub fn update_software(&self, software: &Software) -> Result<()> {
         self.conn.execute(
             "UPDATE softwares SET name = ?2, source_type = ?3, source_identifier = ?4,
              local_command = ?5, local_version_arg = ?6, latest_version = ?7, local_version = ?8,
              published_at = ?9, last_checked_at = ?10, enabled = ?11,
-             last_notified_version = ?12, last_notified_at = ?13
+             last_notified_version = ?12, last_notified_at = ?13, check_interval_minutes = ?14,
+             local_version_regex = ?15, local_version_timeout_seconds = ?16,
+             local_version_args = ?17, local_version_working_dir = ?18, local_version_env = ?19,
+             local_version_file_path = ?20, local_version_json_pointer = ?21,
+             source_include_prerelease = ?22, release_notes = ?23, tags = ?24,
+             source_version_filter_regex = ?25, source_version_exclude_regex = ?26,
+             source_required_asset = ?27, asset_url = ?28, source_tag_prefix = ?29,
+             local_version_use_shell = ?30, source_compare_by = ?31, seen_published_at = ?32,
+             source_version_from = ?33, source_version_extract_regex = ?34, source_channel = ?35
              WHERE id = ?1",
             params![
                 software.id,
@@ -177,97 +869,636 @@ impl Database {
                 software.enabled as i32,
                 software.last_notified_version,
                 software.last_notified_at.map(|dt| dt.to_rfc3339()),
+                software.check_interval_minutes,
+                software.local_version_config.as_ref().and_then(|c| c.version_regex.as_ref()),
+                software.local_version_config.as_ref().and_then(|c| c.timeout_seconds),
+                local_version_args_json(software),
+                software.local_version_config.as_ref().and_then(|c| c.working_dir.as_ref()),
+                local_version_env_json(software),
+                local_version_file_path(software),
+                local_version_json_pointer(software),
+                software.source.include_prerelease as i32,
+                software.release_notes,
+                tags_json(software),
+                software.source.version_filter_regex,
+                software.source.version_exclude_regex,
+                software.source.required_asset,
+                software.asset_url,
+                software.source.tag_prefix,
+                software.local_version_config.as_ref().is_some_and(|c| c.use_shell) as i32,
+                software.source.compare_by.as_str(),
+                software.seen_published_at.map(|dt| dt.to_rfc3339()),
+                software.source.version_from.as_str(),
+                software.source.version_extract_regex,
+                software.source.channel.as_str(),
             ],
         )?;
         Ok(())
     }
 
-    pub fn delete_software(&self, id: &str) -> Result<()> {
-        self.conn.execute("DELETE FROM softwares WHERE id = ?1", params![id])?;
+    /// 保存软件自己的数据源 token，`None` 表示清除
+    pub fn set_source_token(&self, id: &str, token: Option<&str>) -> Result<()> {
+        let (auth_token, in_keychain) = self.store_source_token(id, token)?;
+        self.conn.execute(
+            "UPDATE softwares SET source_auth_token = ?2, source_auth_token_in_keychain = ?3
+             WHERE id = ?1",
+            params![id, auth_token, in_keychain as i32],
+        )?;
         Ok(())
     }
 
-    pub fn get_settings(&self) -> Result<AppSettings> {
-        let mut stmt = self.conn.prepare("SELECT key, value FROM settings")?;
-        let rows = stmt.query_map([], |row| {
-            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
-        })?;
+    /// 软件自己的数据源 token，保存在钥匙串中时在调用 `SourceToken::load` 时才读取
+    pub fn source_token(&self, id: &str) -> Result<SourceToken> {
+        let (plaintext, in_keychain) = self
+            .conn
+            .query_row(
+                "SELECT source_auth_token, source_auth_token_in_keychain FROM softwares
+                 WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, i32>(1)? != 0)),
+            )
+            .optional()?
+            .unwrap_or((None, false));
+        Ok(if in_keychain {
+            SourceToken::Keychain(Arc::clone(&self.secrets), source_token_key(id))
+        } else {
+            SourceToken::Stored(plaintext)
+        })
+    }
 
-        let mut settings = AppSettings::default();
-        for row in rows {
-            let (key, value) = row?;
-            match key.as_str() {
-                "cache_ttl_minutes" => {
-                    settings.cache.ttl_minutes = value.parse().unwrap_or(30);
-                }
-                "auto_refresh_enabled" => {
-                    settings.cache.auto_refresh_enabled = value == "true";
-                }
-                "auto_refresh_interval" => {
-                    settings.cache.auto_refresh_interval = value.parse().unwrap_or(60);
-                }
-                "github_token" => {
-                    settings.github_token = Some(value);
-                }
-                "theme" => {
-                    settings.theme = match value.as_str() {
-                        "light" => ThemeMode::Light,
-                        "dark" => ThemeMode::Dark,
-                        _ => ThemeMode::System,
-                    };
-                }
-                // 通知配置
-                "notification_enabled" => {
-                    settings.notification.enabled = value == "true";
-                }
-                "notification_major" => {
-                    settings.notification.notify_on_major = value == "true";
-                }
-                "notification_minor" => {
-                    settings.notification.notify_on_minor = value == "true";
-                }
-                "notification_patch" => {
-                    settings.notification.notify_on_patch = value == "true";
-                }
-                "notification_prerelease" => {
-                    settings.notification.notify_on_prerelease = value == "true";
-                }
-                "notification_silent_start" => {
-                    settings.notification.silent_start_hour = value.parse().ok();
-                }
-                "notification_silent_end" => {
-                    settings.notification.silent_end_hour = value.parse().ok();
+    /// 只更新最近一次通知的版本和时间，不改动其他字段
+    pub fn set_notified(&self, id: &str, version: &str, at: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE softwares SET last_notified_version = ?2, last_notified_at = ?3 WHERE id = ?1",
+            params![id, version, at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// 只更新本地版本，不改动其他字段
+    pub fn update_local_version(&self, id: &str, local_version: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE softwares SET local_version = ?2 WHERE id = ?1",
+            params![id, local_version],
+        )?;
+        Ok(())
+    }
+
+    /// 批量导入软件
+    ///
+    /// `merge` 为 true 时按 id 更新已有记录并插入新记录，否则先清空表；整个过程在一个事务中完成
+    pub fn import_softwares(&self, softwares: &[Software], merge: bool) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        let mut removed_tokens = Vec::new();
+        if !merge {
+            removed_tokens = self.ids_with_keychain_token("1")?;
+            self.conn.execute("DELETE FROM softwares", [])?;
+        }
+
+        for software in softwares {
+            if let Some(existing) = self.get_software(&software.id)? {
+                // 导出文件不包含 token，导入自己的备份时保留已保存的 token
+                self.update_software(software)?;
+                if software.source.auth_token.is_some() {
+                    self.set_source_token(&software.id, software.source.auth_token.as_deref())?;
                 }
-                "notification_test_mode" => {
-                    settings.notification.test_mode = value == "true";
+                // 导入的软件应当可见，回收站中的同 ID 软件随之恢复
+                if existing.deleted_at.is_some() {
+                    self.restore_software(&software.id)?;
                 }
-                _ => {}
+            } else {
+                self.insert_software(software)?;
             }
         }
 
-        Ok(settings)
+        tx.commit()?;
+        // 重新导入且带有 token 的软件已经覆盖了钥匙串中的旧值
+        removed_tokens.retain(|id| {
+            !softwares
+                .iter()
+                .any(|software| &software.id == id && software.source.auth_token.is_some())
+        });
+        self.delete_source_tokens(&removed_tokens);
+        Ok(())
     }
 
-    pub fn save_settings(&self, settings: &AppSettings) -> Result<()> {
-        let upsert = |key: &str, value: &str| -> Result<()> {
-            self.conn.execute(
-                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
-                params![key, value],
-            )?;
+    /// 在一个事务中执行 `f`，`f` 返回错误时回滚其中的所有写入
+    pub fn in_transaction<T>(&self, f: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        let tx = self.conn.unchecked_transaction()?;
+        let value = f(self)?;
+        tx.commit()?;
+        Ok(value)
+    }
+
+    /// 在一个事务中写回一批检查结果，出错时全部回滚
+    ///
+    /// 成功的软件更新版本和检查时间并记录版本历史，`details` 中只有本次远程获取的软件；
+    /// 失败的软件只记录错误
+    pub fn apply_check_results(
+        &self,
+        results: &[VersionCheckResult],
+        details: &HashMap<String, ReleaseDetails>,
+        failures: &[CheckFailure],
+    ) -> Result<()> {
+        self.in_transaction(|db| {
+            let now = Utc::now();
+            for result in results {
+                let Some(mut software) = db.get_software(&result.software_id)? else {
+                    continue;
+                };
+                software.latest_version = Some(result.latest_version.clone());
+                software.local_version = result.local_version.clone();
+                software.published_at = result.published_at;
+                if let Some(details) = details.get(&result.software_id) {
+                    software.release_notes = details.release_notes.clone();
+                    software.asset_url = details.asset_url.clone();
+                }
+                software.last_checked_at = Some(now);
+                software.refresh_seen_published_at();
+                db.update_check_result(&software)?;
+                db.record_version(&software.id, &result.latest_version, now)?;
+            }
+            for failure in failures {
+                db.set_last_error(&failure.software_id, Some(&failure.error), Some(now))?;
+            }
             Ok(())
-        };
+        })
+    }
 
-        upsert("cache_ttl_minutes", &settings.cache.ttl_minutes.to_string())?;
-        upsert("auto_refresh_enabled", &settings.cache.auto_refresh_enabled.to_string())?;
-        upsert("auto_refresh_interval", &settings.cache.auto_refresh_interval.to_string())?;
-        upsert("theme", match settings.theme {
-            ThemeMode::Light => "light",
-            ThemeMode::Dark => "dark",
-            ThemeMode::System => "system",
-        })?;
+    /// 只写回检查相关的列，并清除上次的错误
+    fn update_check_result(&self, software: &Software) -> Result<()> {
+        self.conn.execute(
+            "UPDATE softwares SET latest_version = ?2, local_version = ?3, published_at = ?4,
+             release_notes = ?5, asset_url = ?6, last_checked_at = ?7, seen_published_at = ?8,
+             last_error = NULL, last_error_at = NULL
+             WHERE id = ?1",
+            params![
+                software.id,
+                software.latest_version,
+                software.local_version,
+                software.published_at.map(|dt| dt.to_rfc3339()),
+                software.release_notes,
+                software.asset_url,
+                software.last_checked_at.map(|dt| dt.to_rfc3339()),
+                software.seen_published_at.map(|dt| dt.to_rfc3339()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 在一个事务中批量启用或禁用软件，返回实际更新的数量，不存在的 ID 被忽略
+    pub fn set_enabled(&self, ids: &[String], enabled: bool) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut updated = 0;
+        {
+            let mut stmt = tx.prepare("UPDATE softwares SET enabled = ?2 WHERE id = ?1")?;
+            for id in ids {
+                updated += stmt.execute(params![id, enabled as i32])?;
+            }
+        }
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    /// 启用或禁用所有不在回收站中的软件，返回更新的数量
+    pub fn set_all_enabled(&self, enabled: bool) -> Result<usize> {
+        self.conn.execute(
+            "UPDATE softwares SET enabled = ?1 WHERE deleted_at IS NULL",
+            params![enabled as i32],
+        )
+    }
+
+    /// 按 `ordered_ids` 重写显示顺序，未列出的软件按原顺序排在后面
+    pub fn reorder_softwares(&self, ordered_ids: &[String]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        let mut ids: Vec<String> = Vec::new();
+        for id in ordered_ids {
+            if !ids.contains(id) && self.get_software(id)?.is_some() {
+                ids.push(id.clone());
+            }
+        }
+        for software in self.get_all_softwares()? {
+            if !ids.contains(&software.id) {
+                ids.push(software.id);
+            }
+        }
+
+        for (index, id) in ids.iter().enumerate() {
+            self.conn.execute(
+                "UPDATE softwares SET sort_order = ?2 WHERE id = ?1",
+                params![id, index as i64 + 1],
+            )?;
+        }
+
+        tx.commit()
+    }
+
+    /// 暂缓 `version` 的提醒到 `until`，两者都为 `None` 时取消暂缓
+    pub fn set_snooze(
+        &self,
+        id: &str,
+        version: Option<&str>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE softwares SET snoozed_version = ?2, snoozed_until = ?3 WHERE id = ?1",
+            params![id, version, until.map(|dt| dt.to_rfc3339())],
+        )?;
+        Ok(())
+    }
+
+    /// 记录最近一次检查失败的原因，检查成功时传入 `None` 清除
+    pub fn set_last_error(
+        &self,
+        id: &str,
+        error: Option<&str>,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE softwares SET last_error = ?2, last_error_at = ?3 WHERE id = ?1",
+            params![id, error, at.map(|dt| dt.to_rfc3339())],
+        )?;
+        Ok(())
+    }
+
+    /// 移入回收站，配置和历史记录保留到 `purge_deleted`
+    pub fn delete_software(&self, id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE softwares SET deleted_at = ?2 WHERE id = ?1 AND deleted_at IS NULL",
+            params![id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// 从回收站恢复，返回是否有软件被恢复
+    pub fn restore_software(&self, id: &str) -> Result<bool> {
+        let restored = self.conn.execute(
+            "UPDATE softwares SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+        )?;
+        Ok(restored > 0)
+    }
+
+    /// 清空回收站，同时删除这些软件的通知记录和版本历史，返回删除的软件数量
+    pub fn purge_deleted(&self) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        for table in ["notification_log", "version_history"] {
+            self.conn.execute(
+                &format!(
+                    "DELETE FROM {} WHERE software_id IN
+                     (SELECT id FROM softwares WHERE deleted_at IS NOT NULL)",
+                    table
+                ),
+                [],
+            )?;
+        }
+        let removed_tokens = self.ids_with_keychain_token("deleted_at IS NOT NULL")?;
+        let purged = self
+            .conn
+            .execute("DELETE FROM softwares WHERE deleted_at IS NOT NULL", [])?;
+
+        tx.commit()?;
+        self.delete_source_tokens(&removed_tokens);
+        Ok(purged)
+    }
+
+    /// 记录一次成功发送的通知
+    pub fn insert_notification_log(
+        &self,
+        software_id: &str,
+        version: &str,
+        channel: NotificationChannel,
+        sent_at: DateTime<Utc>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO notification_log (software_id, version, sent_at, channel)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![software_id, version, sent_at.to_rfc3339(), channel.as_str()],
+        )?;
+        Ok(())
+    }
+
+    /// 最近的通知记录（最新的在前），可按软件过滤
+    pub fn get_notification_history(
+        &self,
+        software_id: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<NotificationLogEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, software_id, version, sent_at, channel FROM notification_log
+             WHERE ?1 IS NULL OR software_id = ?1
+             ORDER BY sent_at DESC, id DESC
+             LIMIT ?2",
+        )?;
+
+        let entries = stmt.query_map(params![software_id, limit], |row| {
+            let sent_at_str: String = row.get(3)?;
+            let sent_at = DateTime::parse_from_rfc3339(&sent_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+            let channel_str: String = row.get(4)?;
+            let channel = NotificationChannel::from_str(&channel_str)
+                .unwrap_or(NotificationChannel::Desktop);
+
+            Ok(NotificationLogEntry {
+                id: row.get(0)?,
+                software_id: row.get(1)?,
+                version: row.get(2)?,
+                sent_at,
+                channel,
+            })
+        })?;
+
+        entries.collect()
+    }
+
+    /// 记录观察到的最新版本，与上一条记录相同时跳过，返回是否写入了新记录
+    pub fn record_version(
+        &self,
+        software_id: &str,
+        version: &str,
+        observed_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let last: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT version FROM version_history WHERE software_id = ?1
+                 ORDER BY observed_at DESC, id DESC LIMIT 1",
+                params![software_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        if last.as_deref() == Some(version) {
+            return Ok(false);
+        }
+
+        self.conn.execute(
+            "INSERT INTO version_history (software_id, version, observed_at)
+             VALUES (?1, ?2, ?3)",
+            params![software_id, version, observed_at.to_rfc3339()],
+        )?;
+        Ok(true)
+    }
+
+    /// 软件的版本历史（最新的在前）
+    pub fn get_version_history(
+        &self,
+        software_id: &str,
+        limit: u32,
+    ) -> Result<Vec<VersionHistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, software_id, version, observed_at FROM version_history
+             WHERE software_id = ?1
+             ORDER BY observed_at DESC, id DESC
+             LIMIT ?2",
+        )?;
+
+        let entries = stmt.query_map(params![software_id, limit], |row| {
+            let observed_at_str: String = row.get(3)?;
+            let observed_at = DateTime::parse_from_rfc3339(&observed_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+            Ok(VersionHistoryEntry {
+                id: row.get(0)?,
+                software_id: row.get(1)?,
+                version: row.get(2)?,
+                observed_at,
+            })
+        })?;
+
+        entries.collect()
+    }
+
+    /// 读取持久化的缓存条目（包括已过期的）
+    pub fn get_cache_entries(&self) -> Result<Vec<(String, CacheEntry)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT key, latest_version, published_at, cached_at, ttl_minutes FROM cache_entries",
+        )?;
+
+        let entries = stmt.query_map([], |row| {
+            let published_at_str: Option<String> = row.get(2)?;
+            let published_at = published_at_str
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc));
+
+            let cached_at_str: String = row.get(3)?;
+            let cached_at = DateTime::parse_from_rfc3339(&cached_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+            let entry = CacheEntry {
+                latest_version: row.get(1)?,
+                published_at,
+                cached_at,
+                ttl_minutes: row.get(4)?,
+                error: None,
+            };
+            Ok((row.get(0)?, entry))
+        })?;
+
+        entries.collect()
+    }
+
+    /// 用给定条目替换全部持久化缓存
+    pub fn replace_cache_entries(&self, entries: &[(String, CacheEntry)]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.conn.execute("DELETE FROM cache_entries", [])?;
 
-        if let Some(ref token) = settings.github_token {
-            upsert("github_token", token)?;
+        for (key, entry) in entries {
+            self.conn.execute(
+                "INSERT INTO cache_entries
+                 (key, latest_version, published_at, cached_at, ttl_minutes)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    key,
+                    entry.latest_version,
+                    entry.published_at.map(|dt| dt.to_rfc3339()),
+                    entry.cached_at.to_rfc3339(),
+                    entry.ttl_minutes,
+                ],
+            )?;
+        }
+
+        tx.commit()
+    }
+
+    pub fn get_settings(&self) -> Result<AppSettings> {
+        let mut stmt = self.conn.prepare("SELECT key, value FROM settings")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut settings = AppSettings::default();
+        for row in rows {
+            let (key, value) = row?;
+            match key.as_str() {
+                "cache_ttl_minutes" => {
+                    settings.cache.ttl_minutes = value.parse().unwrap_or(30);
+                }
+                "cache_error_ttl_minutes" => {
+                    settings.cache.error_ttl_minutes = value.parse().unwrap_or(5);
+                }
+                "auto_refresh_enabled" => {
+                    settings.cache.auto_refresh_enabled = value == "true";
+                }
+                "auto_refresh_interval" => {
+                    settings.cache.auto_refresh_interval = value.parse().unwrap_or(60);
+                }
+                "check_on_startup" => {
+                    settings.cache.check_on_startup = value == "true";
+                }
+                // 钥匙串不可用时退回明文保存的 token
+                "github_token" => {
+                    settings.github_token = Some(value);
+                }
+                "github_api_base_url" => {
+                    settings.github_api_base_url = Some(value);
+                }
+                "bitbucket_token" => {
+                    settings.bitbucket_token = Some(value);
+                }
+                "gitea_token" => {
+                    settings.gitea_token = Some(value);
+                }
+                "theme" => {
+                    settings.theme = ThemeMode::from_str(&value).unwrap_or_default();
+                }
+                "non_semver_strategy" => {
+                    settings.non_semver_strategy =
+                        NonSemverStrategy::from_str(&value).unwrap_or_default();
+                }
+                // 通知配置
+                "notification_enabled" => {
+                    settings.notification.enabled = value == "true";
+                }
+                "notification_major" => {
+                    settings.notification.notify_on_major = value == "true";
+                }
+                "notification_minor" => {
+                    settings.notification.notify_on_minor = value == "true";
+                }
+                "notification_patch" => {
+                    settings.notification.notify_on_patch = value == "true";
+                }
+                "notification_prerelease" => {
+                    settings.notification.notify_on_prerelease = value == "true";
+                }
+                "notification_silent_start" => {
+                    settings.notification.silent_start_hour = value.parse().ok();
+                }
+                "notification_silent_end" => {
+                    settings.notification.silent_end_hour = value.parse().ok();
+                }
+                "notification_silent_start_minute" => {
+                    settings.notification.silent_start_minute = value.parse().ok();
+                }
+                "notification_silent_end_minute" => {
+                    settings.notification.silent_end_minute = value.parse().ok();
+                }
+                "notification_test_mode" => {
+                    settings.notification.test_mode = value == "true";
+                }
+                "notification_webhook_enabled" => {
+                    settings.notification.webhook.enabled = value == "true";
+                }
+                "notification_webhook_url" => {
+                    settings.notification.webhook.url = value;
+                }
+                "notification_style" => {
+                    settings.notification.style =
+                        NotificationStyle::from_str(&value).unwrap_or_default();
+                }
+                "notification_max_per_hour" => {
+                    settings.notification.max_per_hour = value.parse().unwrap_or(20);
+                }
+                "notification_max_per_software_per_hour" => {
+                    settings.notification.max_per_software_per_hour = value.parse().unwrap_or(2);
+                }
+                // 网络配置
+                "request_timeout_seconds" => {
+                    settings.network.request_timeout_seconds = value.parse().unwrap_or(15);
+                }
+                "check_jitter_seconds" => {
+                    settings.network.check_jitter_seconds = value.parse().unwrap_or(0);
+                }
+                "max_retries" => {
+                    settings.network.max_retries = value.parse().unwrap_or(2);
+                }
+                "retry_base_delay_ms" => {
+                    settings.network.retry_base_delay_ms = value.parse().unwrap_or(500);
+                }
+                "user_agent" => {
+                    settings.network.user_agent = Some(value);
+                }
+                "contact" => {
+                    settings.network.contact = Some(value);
+                }
+                "metrics_enabled" => {
+                    settings.metrics.enabled = value == "true";
+                }
+                "metrics_port" => {
+                    settings.metrics.port = value.parse().unwrap_or(9464);
+                }
+                "metrics_bind_address" => {
+                    settings.metrics.bind_address = value;
+                }
+                _ => {}
+            }
+        }
+
+        for (key, token) in [
+            (GITHUB_TOKEN_KEY, &mut settings.github_token),
+            (BITBUCKET_TOKEN_KEY, &mut settings.bitbucket_token),
+            (GITEA_TOKEN_KEY, &mut settings.gitea_token),
+        ] {
+            if let Some(value) = self.read_secret(key) {
+                *token = Some(value);
+            }
+        }
+
+        Ok(settings)
+    }
+
+    pub fn save_settings(&self, settings: &AppSettings) -> Result<()> {
+        let upsert = |key: &str, value: &str| -> Result<()> {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                params![key, value],
+            )?;
+            Ok(())
+        };
+
+        upsert("cache_ttl_minutes", &settings.cache.ttl_minutes.to_string())?;
+        upsert("cache_error_ttl_minutes", &settings.cache.error_ttl_minutes.to_string())?;
+        upsert("auto_refresh_enabled", &settings.cache.auto_refresh_enabled.to_string())?;
+        upsert("auto_refresh_interval", &settings.cache.auto_refresh_interval.to_string())?;
+        upsert("check_on_startup", &settings.cache.check_on_startup.to_string())?;
+        upsert("theme", settings.theme.as_str())?;
+        upsert("non_semver_strategy", settings.non_semver_strategy.as_str())?;
+
+        for (key, token) in [
+            (GITHUB_TOKEN_KEY, &settings.github_token),
+            (BITBUCKET_TOKEN_KEY, &settings.bitbucket_token),
+            (GITEA_TOKEN_KEY, &settings.gitea_token),
+        ] {
+            match token {
+                Some(token) => self.save_secret(key, token)?,
+                None => self.delete_secret(key)?,
+            }
+        }
+
+        match settings.github_api_base_url {
+            Some(ref url) => upsert("github_api_base_url", url)?,
+            None => {
+                self.conn
+                    .execute("DELETE FROM settings WHERE key = 'github_api_base_url'", [])?;
+            }
         }
 
         // 通知配置
@@ -277,16 +1508,1195 @@ impl Database {
         upsert("notification_patch", &settings.notification.notify_on_patch.to_string())?;
         upsert("notification_prerelease", &settings.notification.notify_on_prerelease.to_string())?;
 
-        if let Some(hour) = settings.notification.silent_start_hour {
-            upsert("notification_silent_start", &hour.to_string())?;
-        }
-        if let Some(hour) = settings.notification.silent_end_hour {
-            upsert("notification_silent_end", &hour.to_string())?;
+        // 关闭静默时段时存空字符串，读取时解析为 None，而不是回到默认的 22:00-8:00
+        for (key, value) in [
+            ("notification_silent_start", settings.notification.silent_start_hour),
+            ("notification_silent_end", settings.notification.silent_end_hour),
+            ("notification_silent_start_minute", settings.notification.silent_start_minute),
+            ("notification_silent_end_minute", settings.notification.silent_end_minute),
+        ] {
+            upsert(key, &value.map(|v| v.to_string()).unwrap_or_default())?;
         }
         upsert("notification_test_mode", &settings.notification.test_mode.to_string())?;
+        upsert("notification_webhook_enabled", &settings.notification.webhook.enabled.to_string())?;
+        upsert("notification_webhook_url", &settings.notification.webhook.url)?;
+        upsert("notification_style", settings.notification.style.as_str())?;
+        upsert("notification_max_per_hour", &settings.notification.max_per_hour.to_string())?;
+        upsert(
+            "notification_max_per_software_per_hour",
+            &settings.notification.max_per_software_per_hour.to_string(),
+        )?;
+
+        // 网络配置
+        upsert("request_timeout_seconds", &settings.network.request_timeout_seconds.to_string())?;
+        upsert("check_jitter_seconds", &settings.network.check_jitter_seconds.to_string())?;
+        upsert("max_retries", &settings.network.max_retries.to_string())?;
+        upsert("retry_base_delay_ms", &settings.network.retry_base_delay_ms.to_string())?;
+        for (key, value) in [
+            ("user_agent", &settings.network.user_agent),
+            ("contact", &settings.network.contact),
+        ] {
+            match value {
+                Some(value) => upsert(key, value)?,
+                None => {
+                    self.conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+                }
+            }
+        }
+
+        // 指标服务
+        upsert("metrics_enabled", &settings.metrics.enabled.to_string())?;
+        upsert("metrics_port", &settings.metrics.port.to_string())?;
+        upsert("metrics_bind_address", &settings.metrics.bind_address)?;
 
         Ok(())
     }
 }
 
 pub type DbState = Mutex<Database>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NotificationConfig, WebhookConfig};
+    use crate::secrets::MemoryStore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+    fn test_db() -> Database {
+        Database::new(":memory:").expect("Failed to open in-memory database")
+    }
+
+    fn test_software(id: &str, name: &str) -> Software {
+        Software::new(
+            id.to_string(),
+            name.to_string(),
+            SourceConfig::new(SourceType::GithubRelease, "owner/repo"),
+        )
+    }
+
+    /// 构造迁移机制引入之前的旧数据库（user_version 为 0）
+    fn legacy_database(with_notification_columns: bool) -> Database {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE softwares (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                source_type TEXT NOT NULL,
+                source_identifier TEXT NOT NULL,
+                local_command TEXT,
+                local_version_arg TEXT,
+                latest_version TEXT,
+                local_version TEXT,
+                published_at TEXT,
+                last_checked_at TEXT,
+                enabled INTEGER DEFAULT 1
+            )",
+            [],
+        )
+        .unwrap();
+
+        if with_notification_columns {
+            conn.execute("ALTER TABLE softwares ADD COLUMN last_notified_version TEXT", [])
+                .unwrap();
+            conn.execute("ALTER TABLE softwares ADD COLUMN last_notified_at TEXT", [])
+                .unwrap();
+        }
+
+        conn.execute(
+            "INSERT INTO softwares (id, name, source_type, source_identifier)
+             VALUES ('a', 'App', 'npm', 'react')",
+            [],
+        )
+        .unwrap();
+
+        Database {
+            conn,
+            secrets: secrets::default_store().into(),
+        }
+    }
+
+    #[test]
+    fn test_new_database_is_current() {
+        let db = test_db();
+        assert_eq!(db.current_schema_version().unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_legacy_database() {
+        let db = legacy_database(false);
+        db.init_tables().unwrap();
+
+        assert_eq!(db.current_schema_version().unwrap(), SCHEMA_VERSION);
+        let software = db.get_software("a").unwrap().unwrap();
+        assert_eq!(software.source.source_type, SourceType::Npm);
+        assert!(software.last_notified_version.is_none());
+    }
+
+    #[test]
+    fn test_migrate_database_with_existing_columns() {
+        let db = legacy_database(true);
+        db.init_tables().unwrap();
+
+        assert_eq!(db.current_schema_version().unwrap(), SCHEMA_VERSION);
+        assert!(db.get_software("a").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_migrations_run_once() {
+        let db = test_db();
+        db.init_tables().unwrap();
+        db.init_tables().unwrap();
+        assert_eq!(db.current_schema_version().unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_theme_round_trip() {
+        let db = test_db();
+        for theme in [ThemeMode::Light, ThemeMode::Dark, ThemeMode::System] {
+            let settings = AppSettings {
+                theme: theme.clone(),
+                ..AppSettings::default()
+            };
+            db.save_settings(&settings).unwrap();
+            assert_eq!(db.get_settings().unwrap().theme, theme);
+        }
+    }
+
+    #[test]
+    fn test_notification_config_round_trip() {
+        let db = test_db();
+        let defaults = NotificationConfig::default();
+        let flipped = NotificationConfig {
+            enabled: !defaults.enabled,
+            notify_on_major: !defaults.notify_on_major,
+            notify_on_minor: !defaults.notify_on_minor,
+            notify_on_patch: !defaults.notify_on_patch,
+            notify_on_prerelease: !defaults.notify_on_prerelease,
+            silent_start_hour: Some(22),
+            silent_end_hour: Some(7),
+            silent_start_minute: Some(30),
+            silent_end_minute: Some(45),
+            test_mode: !defaults.test_mode,
+            webhook: WebhookConfig {
+                url: "https://hooks.example.com/abc".to_string(),
+                enabled: true,
+            },
+            style: NotificationStyle::Digest,
+            max_per_hour: 5,
+            max_per_software_per_hour: 0,
+        };
+        let mut settings = AppSettings {
+            notification: flipped.clone(),
+            ..AppSettings::default()
+        };
+        db.save_settings(&settings).unwrap();
+        assert_eq!(db.get_settings().unwrap().notification, flipped);
+
+        // 关闭静默时段后不再恢复旧值或默认值
+        settings.notification.silent_start_hour = None;
+        settings.notification.silent_end_hour = None;
+        settings.notification.silent_start_minute = None;
+        db.save_settings(&settings).unwrap();
+        assert_eq!(db.get_settings().unwrap().notification, settings.notification);
+
+        settings.notification = defaults.clone();
+        db.save_settings(&settings).unwrap();
+        assert_eq!(db.get_settings().unwrap().notification, defaults);
+    }
+
+    /// 模拟没有可用钥匙串的环境，例如未运行 Secret Service 的 Linux
+    struct UnavailableStore;
+
+    impl SecretStore for UnavailableStore {
+        fn get(&self, _key: &str) -> std::result::Result<Option<String>, String> {
+            Err("no keychain".to_string())
+        }
+
+        fn set(&self, _key: &str, _value: &str) -> std::result::Result<(), String> {
+            Err("no keychain".to_string())
+        }
+
+        fn delete(&self, _key: &str) -> std::result::Result<(), String> {
+            Err("no keychain".to_string())
+        }
+    }
+
+    fn db_with_store(secrets: Box<dyn SecretStore>) -> Database {
+        let db = Database {
+            conn: Connection::open_in_memory().unwrap(),
+            secrets: secrets.into(),
+        };
+        db.init_tables().unwrap();
+        db
+    }
+
+    fn plaintext_setting(db: &Database, key: &str) -> Option<String> {
+        db.conn
+            .query_row("SELECT value FROM settings WHERE key = ?1", params![key], |row| {
+                row.get(0)
+            })
+            .optional()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_github_token_kept_out_of_settings_table() {
+        let db = db_with_store(Box::new(MemoryStore::default()));
+        let settings = AppSettings {
+            github_token: Some("ghp_secret".to_string()),
+            ..AppSettings::default()
+        };
+        db.save_settings(&settings).unwrap();
+
+        assert_eq!(plaintext_setting(&db, GITHUB_TOKEN_KEY), None);
+        assert_eq!(db.secrets.get(GITHUB_TOKEN_KEY).unwrap().as_deref(), Some("ghp_secret"));
+        assert_eq!(db.get_settings().unwrap().github_token.as_deref(), Some("ghp_secret"));
+    }
+
+    #[test]
+    fn test_forge_tokens_kept_out_of_settings_table() {
+        let db = db_with_store(Box::new(MemoryStore::default()));
+        let mut settings = AppSettings {
+            bitbucket_token: Some("user:app-password".to_string()),
+            gitea_token: Some("gitea_secret".to_string()),
+            ..AppSettings::default()
+        };
+        db.save_settings(&settings).unwrap();
+
+        for key in [BITBUCKET_TOKEN_KEY, GITEA_TOKEN_KEY] {
+            assert_eq!(plaintext_setting(&db, key), None);
+        }
+        let loaded = db.get_settings().unwrap();
+        assert_eq!(loaded.bitbucket_token.as_deref(), Some("user:app-password"));
+        assert_eq!(loaded.gitea_token.as_deref(), Some("gitea_secret"));
+
+        settings.gitea_token = None;
+        db.save_settings(&settings).unwrap();
+        assert_eq!(db.secrets.get(GITEA_TOKEN_KEY).unwrap(), None);
+        assert_eq!(db.get_settings().unwrap().gitea_token, None);
+    }
+
+    fn plaintext_source_token(db: &Database, id: &str) -> Option<String> {
+        db.conn
+            .query_row(
+                "SELECT source_auth_token FROM softwares WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_source_token_kept_in_secret_store() {
+        let db = db_with_store(Box::new(MemoryStore::default()));
+        let mut software = test_software("1", "Private");
+        software.source.auth_token = Some("ghp_private".to_string());
+        db.insert_software(&software).unwrap();
+
+        assert_eq!(plaintext_source_token(&db, "1"), None);
+        assert_eq!(db.secrets.get("source_token:1").unwrap().as_deref(), Some("ghp_private"));
+        // 查询软件时不读取钥匙串，远程获取前再单独读取
+        assert_eq!(db.get_software("1").unwrap().unwrap().source.auth_token, None);
+        assert_eq!(db.get_all_softwares().unwrap()[0].source.auth_token, None);
+        assert_eq!(db.source_token("1").unwrap().load().as_deref(), Some("ghp_private"));
+
+        // 更新其他字段时保留 token
+        software.source.auth_token = None;
+        software.name = "Renamed".to_string();
+        db.update_software(&software).unwrap();
+        assert_eq!(db.secrets.get("source_token:1").unwrap().as_deref(), Some("ghp_private"));
+
+        db.set_source_token("1", None).unwrap();
+        assert_eq!(db.secrets.get("source_token:1").unwrap(), None);
+        assert_eq!(db.source_token("1").unwrap().load(), None);
+
+        // 清空回收站时一并删除钥匙串中的 token
+        db.set_source_token("1", Some("ghp_private")).unwrap();
+        db.delete_software("1").unwrap();
+        db.purge_deleted().unwrap();
+        assert_eq!(db.secrets.get("source_token:1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_plaintext_source_token_moved_to_secret_store() {
+        let db = db_with_store(Box::new(MemoryStore::default()));
+        db.insert_software(&test_software("1", "Legacy")).unwrap();
+        db.conn
+            .execute("UPDATE softwares SET source_auth_token = 'ghp_legacy' WHERE id = '1'", [])
+            .unwrap();
+
+        db.move_plaintext_source_tokens().unwrap();
+        assert_eq!(plaintext_source_token(&db, "1"), None);
+        assert_eq!(db.source_token("1").unwrap().load().as_deref(), Some("ghp_legacy"));
+    }
+
+    #[test]
+    fn test_source_token_falls_back_to_database() {
+        let db = db_with_store(Box::new(UnavailableStore));
+        let mut software = test_software("1", "Private");
+        software.source.auth_token = Some("ghp_private".to_string());
+        db.insert_software(&software).unwrap();
+
+        assert_eq!(plaintext_source_token(&db, "1").as_deref(), Some("ghp_private"));
+        assert_eq!(
+            db.get_software("1").unwrap().unwrap().source.auth_token.as_deref(),
+            Some("ghp_private")
+        );
+        assert_eq!(db.source_token("1").unwrap().load().as_deref(), Some("ghp_private"));
+    }
+
+    /// 记录读取次数的凭据存储
+    struct CountingStore {
+        inner: MemoryStore,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl SecretStore for CountingStore {
+        fn get(&self, key: &str) -> std::result::Result<Option<String>, String> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.get(key)
+        }
+
+        fn set(&self, key: &str, value: &str) -> std::result::Result<(), String> {
+            self.inner.set(key, value)
+        }
+
+        fn delete(&self, key: &str) -> std::result::Result<(), String> {
+            self.inner.delete(key)
+        }
+    }
+
+    #[test]
+    fn test_listing_softwares_does_not_read_keychain() {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let db = db_with_store(Box::new(CountingStore {
+            inner: MemoryStore::default(),
+            reads: Arc::clone(&reads),
+        }));
+        let mut software = test_software("1", "Private");
+        software.source.auth_token = Some("ghp_private".to_string());
+        db.insert_software(&software).unwrap();
+        reads.store(0, Ordering::SeqCst);
+
+        // 指标抓取和界面刷新只查询软件列表
+        db.get_all_softwares().unwrap();
+        db.get_software("1").unwrap();
+        let token = db.source_token("1").unwrap();
+        assert_eq!(reads.load(Ordering::SeqCst), 0);
+
+        assert_eq!(token.load().as_deref(), Some("ghp_private"));
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_cleared_github_token_removed() {
+        let db = db_with_store(Box::new(MemoryStore::default()));
+        let mut settings = AppSettings {
+            github_token: Some("ghp_secret".to_string()),
+            ..AppSettings::default()
+        };
+        db.save_settings(&settings).unwrap();
+
+        settings.github_token = None;
+        db.save_settings(&settings).unwrap();
+        assert_eq!(db.secrets.get(GITHUB_TOKEN_KEY).unwrap(), None);
+        assert_eq!(plaintext_setting(&db, GITHUB_TOKEN_KEY), None);
+        assert_eq!(db.get_settings().unwrap().github_token, None);
+    }
+
+    #[test]
+    fn test_plaintext_github_token_moved_to_secret_store() {
+        let db = db_with_store(Box::new(MemoryStore::default()));
+        db.conn
+            .execute(
+                "INSERT INTO settings (key, value) VALUES ('github_token', 'ghp_legacy')",
+                [],
+            )
+            .unwrap();
+
+        db.move_plaintext_secret(GITHUB_TOKEN_KEY).unwrap();
+        assert_eq!(plaintext_setting(&db, GITHUB_TOKEN_KEY), None);
+        assert_eq!(db.get_settings().unwrap().github_token.as_deref(), Some("ghp_legacy"));
+
+        // 已迁移后再次执行不做任何事
+        db.move_plaintext_secret(GITHUB_TOKEN_KEY).unwrap();
+        assert_eq!(db.get_settings().unwrap().github_token.as_deref(), Some("ghp_legacy"));
+    }
+
+    #[test]
+    fn test_github_token_falls_back_to_settings_table() {
+        let db = db_with_store(Box::new(UnavailableStore));
+        let settings = AppSettings {
+            github_token: Some("ghp_secret".to_string()),
+            ..AppSettings::default()
+        };
+        db.save_settings(&settings).unwrap();
+        assert_eq!(plaintext_setting(&db, GITHUB_TOKEN_KEY).as_deref(), Some("ghp_secret"));
+
+        // 迁移失败时保留明文，不丢失 token
+        db.move_plaintext_secret(GITHUB_TOKEN_KEY).unwrap();
+        assert_eq!(db.get_settings().unwrap().github_token.as_deref(), Some("ghp_secret"));
+    }
+
+    #[test]
+    fn test_non_semver_strategy_round_trip() {
+        let db = test_db();
+        assert_eq!(
+            db.get_settings().unwrap().non_semver_strategy,
+            NonSemverStrategy::TreatDifferentAsUnknown
+        );
+        for strategy in [
+            NonSemverStrategy::Optimistic,
+            NonSemverStrategy::LexicographicGreater,
+            NonSemverStrategy::TreatDifferentAsUnknown,
+        ] {
+            let settings = AppSettings {
+                non_semver_strategy: strategy,
+                ..AppSettings::default()
+            };
+            db.save_settings(&settings).unwrap();
+            assert_eq!(db.get_settings().unwrap().non_semver_strategy, strategy);
+        }
+    }
+
+    #[test]
+    fn test_get_software_by_id() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "Alpha")).unwrap();
+        db.insert_software(&test_software("b", "Beta")).unwrap();
+
+        let software = db.get_software("b").unwrap().unwrap();
+        assert_eq!(software.id, "b");
+        assert_eq!(software.name, "Beta");
+        assert_eq!(software.source.identifier, "owner/repo");
+        assert!(software.enabled);
+
+        assert!(db.get_software("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_import_merge_updates_and_inserts() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "Alpha")).unwrap();
+        db.insert_software(&test_software("b", "Beta")).unwrap();
+
+        let imported = vec![test_software("a", "Alpha Renamed"), test_software("c", "Gamma")];
+        db.import_softwares(&imported, true).unwrap();
+
+        let names: Vec<String> = db
+            .get_all_softwares()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["Alpha Renamed", "Beta", "Gamma"]);
+    }
+
+    #[test]
+    fn test_import_merge_keeps_auth_token() {
+        let db = test_db();
+        let mut software = test_software("a", "Alpha");
+        software.source.auth_token = Some("ghp_private".to_string());
+        db.insert_software(&software).unwrap();
+
+        // 导出时会清空 token
+        let mut exported = software.clone();
+        exported.source.auth_token = None;
+        db.import_softwares(&[exported], true).unwrap();
+        assert_eq!(db.source_token("a").unwrap().load().as_deref(), Some("ghp_private"));
+
+        let mut replaced = software;
+        replaced.source.auth_token = Some("ghp_new".to_string());
+        db.import_softwares(&[replaced], true).unwrap();
+        assert_eq!(db.source_token("a").unwrap().load().as_deref(), Some("ghp_new"));
+    }
+
+    #[test]
+    fn test_import_merge_restores_trashed() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "Alpha")).unwrap();
+        db.delete_software("a").unwrap();
+
+        db.import_softwares(&[test_software("a", "Alpha Imported")], true)
+            .unwrap();
+
+        let softwares = db.get_all_softwares().unwrap();
+        assert_eq!(softwares.len(), 1);
+        assert_eq!(softwares[0].name, "Alpha Imported");
+        assert_eq!(softwares[0].deleted_at, None);
+    }
+
+    #[test]
+    fn test_import_replace_clears_table() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "Alpha")).unwrap();
+        db.insert_software(&test_software("b", "Beta")).unwrap();
+
+        db.import_softwares(&[test_software("c", "Gamma")], false).unwrap();
+
+        let softwares = db.get_all_softwares().unwrap();
+        assert_eq!(softwares.len(), 1);
+        assert_eq!(softwares[0].id, "c");
+    }
+
+    #[test]
+    fn test_notification_history_order() {
+        let db = test_db();
+        let first = Utc::now() - chrono::Duration::minutes(10);
+        let second = Utc::now();
+
+        db.insert_notification_log("a", "1.1.0", NotificationChannel::Desktop, first)
+            .unwrap();
+        db.insert_notification_log("a", "1.2.0", NotificationChannel::Webhook, second)
+            .unwrap();
+        db.insert_notification_log("b", "3.0.0", NotificationChannel::Desktop, first)
+            .unwrap();
+
+        let history = db.get_notification_history(Some("a"), 10).unwrap();
+        let versions: Vec<&str> = history.iter().map(|e| e.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.2.0", "1.1.0"]);
+        assert_eq!(history[0].channel, NotificationChannel::Webhook);
+        assert_eq!(history[1].sent_at.timestamp(), first.timestamp());
+
+        assert_eq!(db.get_notification_history(None, 10).unwrap().len(), 3);
+        assert_eq!(db.get_notification_history(None, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_notification_state_round_trip() {
+        let db = test_db();
+        let notified_at = Utc::now();
+
+        let mut software = test_software("a", "App");
+        software.last_notified_version = Some("2.0.0".to_string());
+        software.last_notified_at = Some(notified_at);
+        db.insert_software(&software).unwrap();
+
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.last_notified_version.as_deref(), Some("2.0.0"));
+        assert_eq!(
+            loaded.last_notified_at.map(|dt| dt.timestamp()),
+            Some(notified_at.timestamp())
+        );
+
+        software.last_notified_version = Some("2.1.0".to_string());
+        db.update_software(&software).unwrap();
+
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.last_notified_version.as_deref(), Some("2.1.0"));
+    }
+
+    #[test]
+    fn test_set_notified_keeps_other_fields() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "App")).unwrap();
+
+        // 通知期间用户修改了软件，写回通知记录时不能覆盖
+        let mut edited = db.get_software("a").unwrap().unwrap();
+        edited.name = "Renamed".to_string();
+        db.update_software(&edited).unwrap();
+
+        let notified_at = Utc::now();
+        db.set_notified("a", "3.0.0", notified_at).unwrap();
+
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.name, "Renamed");
+        assert_eq!(loaded.last_notified_version.as_deref(), Some("3.0.0"));
+        assert_eq!(
+            loaded.last_notified_at.map(|dt| dt.timestamp()),
+            Some(notified_at.timestamp())
+        );
+    }
+
+    #[test]
+    fn test_local_version_config_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Git");
+        software.local_version_config = Some(LocalVersionConfig {
+            source: LocalVersionSource::Command,
+            command: "git".to_string(),
+            version_arg: None,
+            version_regex: Some(r"git version (\S+)".to_string()),
+            timeout_seconds: Some(3),
+            args: vec!["version".to_string(), "--build-options".to_string()],
+            working_dir: Some("/opt/tools".to_string()),
+            env: Some(HashMap::from([("LANG".to_string(), "C".to_string())])),
+            use_shell: true,
+        });
+        db.insert_software(&software).unwrap();
+
+        let loaded = db.get_software("a").unwrap().unwrap();
+        let config = loaded.local_version_config.unwrap();
+        assert_eq!(config.command, "git");
+        assert_eq!(config.version_regex.as_deref(), Some(r"git version (\S+)"));
+        assert_eq!(config.timeout_seconds, Some(3));
+        assert_eq!(config.args, vec!["version", "--build-options"]);
+        assert_eq!(config.working_dir.as_deref(), Some("/opt/tools"));
+        assert_eq!(
+            config.env.and_then(|env| env.get("LANG").cloned()),
+            Some("C".to_string())
+        );
+        assert!(config.use_shell);
+    }
+
+    #[test]
+    fn test_local_version_file_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        software.local_version_config = Some(LocalVersionConfig {
+            source: LocalVersionSource::FileRead {
+                path: "/opt/tool/package.json".to_string(),
+                json_pointer: Some("$.version".to_string()),
+            },
+            ..Default::default()
+        });
+        db.insert_software(&software).unwrap();
+
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(
+            loaded.local_version_config.map(|c| c.source),
+            Some(LocalVersionSource::FileRead {
+                path: "/opt/tool/package.json".to_string(),
+                json_pointer: Some("$.version".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_local_version_command_json_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        software.local_version_config = Some(LocalVersionConfig {
+            source: LocalVersionSource::CommandJson {
+                json_pointer: "/version".to_string(),
+            },
+            command: "tool".to_string(),
+            args: vec!["version".to_string(), "--json".to_string()],
+            ..Default::default()
+        });
+        db.insert_software(&software).unwrap();
+
+        let config = db.get_software("a").unwrap().unwrap().local_version_config.unwrap();
+        assert_eq!(
+            config.source,
+            LocalVersionSource::CommandJson {
+                json_pointer: "/version".to_string(),
+            }
+        );
+        assert_eq!(config.command, "tool");
+    }
+
+    #[test]
+    fn test_compare_by_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        db.insert_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.compare_by, CompareMode::Version);
+        assert!(loaded.seen_published_at.is_none());
+
+        let seen = Utc::now();
+        software.source.compare_by = CompareMode::PublishedDate;
+        software.seen_published_at = Some(seen);
+        db.update_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.compare_by, CompareMode::PublishedDate);
+        assert_eq!(loaded.seen_published_at.map(|dt| dt.timestamp()), Some(seen.timestamp()));
+    }
+
+    #[test]
+    fn test_version_from_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        db.insert_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.version_from, VersionFrom::TagName);
+        assert_eq!(loaded.source.version_extract_regex, None);
+
+        software.source.version_from = VersionFrom::ReleaseName;
+        software.source.version_extract_regex = Some(r"Release (\S+)".to_string());
+        db.update_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.version_from, VersionFrom::ReleaseName);
+        assert_eq!(
+            loaded.source.version_extract_regex.as_deref(),
+            Some(r"Release (\S+)")
+        );
+    }
+
+    #[test]
+    fn test_channel_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        db.insert_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.channel, ReleaseChannel::Any);
+
+        software.source.channel = ReleaseChannel::Stable;
+        db.update_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.channel, ReleaseChannel::Stable);
+    }
+
+    #[test]
+    fn test_include_prerelease_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        db.insert_software(&software).unwrap();
+        assert!(!db.get_software("a").unwrap().unwrap().source.include_prerelease);
+
+        software.source.include_prerelease = true;
+        db.update_software(&software).unwrap();
+        assert!(db.get_software("a").unwrap().unwrap().source.include_prerelease);
+    }
+
+    #[test]
+    fn test_snooze_round_trip() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "App")).unwrap();
+        let until = Utc::now() + chrono::Duration::days(7);
+
+        db.set_snooze("a", Some("2.0.0"), Some(until)).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.snoozed_version.as_deref(), Some("2.0.0"));
+        assert_eq!(loaded.snoozed_until.map(|dt| dt.timestamp()), Some(until.timestamp()));
+
+        // 普通更新不会清除暂缓状态
+        let mut software = test_software("a", "App");
+        software.latest_version = Some("2.0.0".to_string());
+        db.update_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.snoozed_version.as_deref(), Some("2.0.0"));
+
+        db.set_snooze("a", None, None).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert!(loaded.snoozed_version.is_none());
+        assert!(loaded.snoozed_until.is_none());
+    }
+
+    #[test]
+    fn test_last_error_set_and_cleared() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "App")).unwrap();
+        let failed_at = Utc::now();
+
+        db.set_last_error("a", Some("npm returned 503"), Some(failed_at)).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.last_error.as_deref(), Some("npm returned 503"));
+        assert_eq!(loaded.last_error_at.map(|dt| dt.timestamp()), Some(failed_at.timestamp()));
+
+        // 保存检查结果不会覆盖失败记录，需要显式清除
+        let mut software = loaded.clone();
+        software.latest_version = Some("2.0.0".to_string());
+        db.update_software(&software).unwrap();
+        assert!(db.get_software("a").unwrap().unwrap().last_error.is_some());
+
+        db.set_last_error("a", None, None).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert!(loaded.last_error.is_none());
+        assert!(loaded.last_error_at.is_none());
+    }
+
+    #[test]
+    fn test_version_filter_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        software.source.version_filter_regex = Some(r"^v\d+".to_string());
+        db.insert_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.version_filter_regex.as_deref(), Some(r"^v\d+"));
+        assert!(loaded.source.version_exclude_regex.is_none());
+
+        software.source.version_exclude_regex = Some("nightly".to_string());
+        db.update_software(&software).unwrap();
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.version_exclude_regex.as_deref(), Some("nightly"));
+    }
+
+    #[test]
+    fn test_required_asset_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        software.source.required_asset = Some("*-linux-x86_64.tar.gz".to_string());
+        software.asset_url = Some("https://example.com/tool-linux-x86_64.tar.gz".to_string());
+        db.insert_software(&software).unwrap();
+
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.source.required_asset, software.source.required_asset);
+        assert_eq!(loaded.asset_url, software.asset_url);
+    }
+
+    #[test]
+    fn test_delete_and_restore() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "App")).unwrap();
+        db.insert_software(&test_software("b", "Tool")).unwrap();
+        db.record_version("a", "1.0.0", Utc::now()).unwrap();
+
+        db.delete_software("a").unwrap();
+        let ids: Vec<_> = db.get_all_softwares().unwrap().into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["b"]);
+        assert!(db.search_softwares("App").unwrap().is_empty());
+        assert_eq!(db.get_softwares(true).unwrap().len(), 2);
+        assert!(db.get_software("a").unwrap().unwrap().deleted_at.is_some());
+
+        assert!(db.restore_software("a").unwrap());
+        assert!(!db.restore_software("a").unwrap());
+        let restored = db.get_software("a").unwrap().unwrap();
+        assert!(restored.deleted_at.is_none());
+        assert_eq!(db.get_all_softwares().unwrap().len(), 2);
+        assert_eq!(db.get_version_history("a", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_delete_and_purge() {
+        let db = test_db();
+        db.insert_software(&test_software("a", "App")).unwrap();
+        db.insert_software(&test_software("b", "Tool")).unwrap();
+        db.record_version("a", "1.0.0", Utc::now()).unwrap();
+        db.record_version("b", "2.0.0", Utc::now()).unwrap();
+
+        db.delete_software("a").unwrap();
+        assert_eq!(db.purge_deleted().unwrap(), 1);
+        assert!(db.get_software("a").unwrap().is_none());
+        assert!(!db.restore_software("a").unwrap());
+        assert!(db.get_version_history("a", 10).unwrap().is_empty());
+        assert_eq!(db.get_version_history("b", 10).unwrap().len(), 1);
+        assert_eq!(db.get_softwares(true).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_release_notes_round_trip() {
+        let db = test_db();
+
+        let mut software = test_software("a", "Tool");
+        software.release_notes = Some("## Changes\n- Fixed crash".to_string());
+        db.insert_software(&software).unwrap();
+
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.release_notes.as_deref(), Some("## Changes\n- Fixed crash"));
+    }
+
+    #[test]
+    fn test_tags_round_trip_and_filter() {
+        let db = test_db();
+
+        let mut work = test_software("a", "Editor");
+        work.tags = vec!["work".to_string(), "dev tools".to_string()];
+        db.insert_software(&work).unwrap();
+
+        let mut personal = test_software("b", "Player");
+        personal.tags = vec!["personal".to_string()];
+        db.insert_software(&personal).unwrap();
+
+        db.insert_software(&test_software("c", "Untagged")).unwrap();
+
+        let loaded = db.get_software("a").unwrap().unwrap();
+        assert_eq!(loaded.tags, vec!["work", "dev tools"]);
+        assert!(db.get_software("c").unwrap().unwrap().tags.is_empty());
+
+        let tagged = db.get_softwares_by_tag("dev tools").unwrap();
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].id, "a");
+        assert!(db.get_softwares_by_tag("missing").unwrap().is_empty());
+
+        work.tags = vec!["personal".to_string()];
+        db.update_software(&work).unwrap();
+        let ids: Vec<String> = db
+            .get_softwares_by_tag("personal")
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_in_transaction_is_all_or_nothing() {
+        let db = test_db();
+        let ids: Vec<String> = (0..20).map(|i| format!("app-{}", i)).collect();
+        for id in &ids {
+            db.insert_software(&test_software(id, id)).unwrap();
+        }
+        let update_all = |db: &Database, fail_at: Option<usize>| -> Result<()> {
+            for (index, id) in ids.iter().enumerate() {
+                if Some(index) == fail_at {
+                    return Err(rusqlite::Error::QueryReturnedNoRows);
+                }
+                let mut software = db.get_software(id)?.unwrap();
+                software.latest_version = Some("2.0.0".to_string());
+                db.update_software(&software)?;
+                db.record_version(id, "2.0.0", Utc::now())?;
+            }
+            Ok(())
+        };
+        let updated = |db: &Database| {
+            db.get_all_softwares()
+                .unwrap()
+                .iter()
+                .filter(|s| s.latest_version.as_deref() == Some("2.0.0"))
+                .count()
+        };
+
+        // 中途失败时，之前的更新也被回滚
+        assert!(db.in_transaction(|db| update_all(db, Some(10))).is_err());
+        assert_eq!(updated(&db), 0);
+        assert!(db.get_version_history("app-0", 10).unwrap().is_empty());
+
+        db.in_transaction(|db| update_all(db, None)).unwrap();
+        assert_eq!(updated(&db), ids.len());
+    }
+
+    #[test]
+    fn test_apply_check_results() {
+        let db = test_db();
+        for id in ["fetched", "cached", "failed"] {
+            let mut software = test_software(id, id);
+            software.release_notes = Some("old notes".to_string());
+            db.insert_software(&software).unwrap();
+        }
+        db.set_source_token("fetched", Some("ghp_private")).unwrap();
+        let result = |id: &str| VersionCheckResult {
+            software_id: id.to_string(),
+            latest_version: "2.0.0".to_string(),
+            local_version: Some("1.0.0".to_string()),
+            published_at: None,
+            has_update: true,
+            change_kind: None,
+        };
+        let details = HashMap::from([(
+            "fetched".to_string(),
+            ReleaseDetails {
+                release_notes: Some("new notes".to_string()),
+                asset_url: Some("https://example.com/app.tar.gz".to_string()),
+            },
+        )]);
+        let failures = [CheckFailure {
+            software_id: "failed".to_string(),
+            name: "failed".to_string(),
+            error: "Network error".to_string(),
+        }];
+
+        db.apply_check_results(&[result("fetched"), result("cached")], &details, &failures)
+            .unwrap();
+
+        let fetched = db.get_software("fetched").unwrap().unwrap();
+        assert_eq!(fetched.latest_version.as_deref(), Some("2.0.0"));
+        assert_eq!(fetched.local_version.as_deref(), Some("1.0.0"));
+        assert_eq!(fetched.release_notes.as_deref(), Some("new notes"));
+        assert!(fetched.last_checked_at.is_some());
+        assert_eq!(db.get_version_history("fetched", 10).unwrap().len(), 1);
+        // 只写回检查相关的列，不影响数据源 token
+        assert_eq!(db.source_token("fetched").unwrap().load().as_deref(), Some("ghp_private"));
+
+        // 缓存命中的结果不覆盖发布说明
+        let cached = db.get_software("cached").unwrap().unwrap();
+        assert_eq!(cached.latest_version.as_deref(), Some("2.0.0"));
+        assert_eq!(cached.release_notes.as_deref(), Some("old notes"));
+
+        let failed = db.get_software("failed").unwrap().unwrap();
+        assert_eq!(failed.latest_version, None);
+        assert_eq!(failed.last_error.as_deref(), Some("Network error"));
+        assert!(failed.last_error_at.is_some());
+    }
+
+    #[test]
+    fn test_set_enabled_in_bulk() {
+        let db = test_db();
+        for id in ["a", "b", "c", "d"] {
+            db.insert_software(&test_software(id, id)).unwrap();
+        }
+        let enabled = |db: &Database| -> Vec<String> {
+            db.get_all_softwares()
+                .unwrap()
+                .into_iter()
+                .filter(|s| s.enabled)
+                .map(|s| s.id)
+                .collect()
+        };
+
+        let ids = ["a".to_string(), "c".to_string(), "missing".to_string()];
+        assert_eq!(db.set_enabled(&ids, false).unwrap(), 2);
+        assert_eq!(enabled(&db), vec!["b", "d"]);
+
+        assert_eq!(db.set_enabled(&ids[..1], true).unwrap(), 1);
+        assert_eq!(enabled(&db), vec!["a", "b", "d"]);
+
+        // 回收站中的软件不受影响
+        db.delete_software("d").unwrap();
+        assert_eq!(db.set_all_enabled(false).unwrap(), 3);
+        assert!(enabled(&db).is_empty());
+        assert!(db.get_software("d").unwrap().unwrap().enabled);
+
+        assert_eq!(db.set_all_enabled(true).unwrap(), 3);
+        assert_eq!(enabled(&db), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_reorder_softwares() {
+        let db = test_db();
+        for (id, name) in [("a", "Alpha"), ("b", "Beta"), ("c", "Gamma")] {
+            db.insert_software(&test_software(id, name)).unwrap();
+        }
+
+        let ids = |db: &Database| -> Vec<String> {
+            db.get_all_softwares()
+                .unwrap()
+                .into_iter()
+                .map(|s| s.id)
+                .collect()
+        };
+        // 默认按添加顺序
+        assert_eq!(ids(&db), vec!["a", "b", "c"]);
+
+        db.reorder_softwares(&["c".to_string(), "a".to_string(), "b".to_string()])
+            .unwrap();
+        assert_eq!(ids(&db), vec!["c", "a", "b"]);
+
+        // 未列出的软件保持原顺序排在后面，新增的软件排在最后
+        db.reorder_softwares(&["b".to_string()]).unwrap();
+        db.insert_software(&test_software("d", "Delta")).unwrap();
+        assert_eq!(ids(&db), vec!["b", "c", "a", "d"]);
+
+        // 编辑不影响顺序
+        let mut software = db.get_software("c").unwrap().unwrap();
+        software.name = "Zeta".to_string();
+        db.update_software(&software).unwrap();
+        assert_eq!(ids(&db), vec!["b", "c", "a", "d"]);
+    }
+
+    #[test]
+    fn test_search_softwares() {
+        let db = test_db();
+
+        let mut editor = test_software("a", "Visual Studio Code");
+        editor.source.identifier = "microsoft/vscode".to_string();
+        editor.latest_version = Some("1.89.0".to_string());
+        db.insert_software(&editor).unwrap();
+
+        let mut runtime = test_software("b", "Node");
+        runtime.source.identifier = "nodejs/node".to_string();
+        runtime.latest_version = Some("22.2.0".to_string());
+        db.insert_software(&runtime).unwrap();
+
+        let mut special = test_software("c", "100% Pure_Tool");
+        special.source.identifier = "owner/pure".to_string();
+        db.insert_software(&special).unwrap();
+
+        let ids = |query: &str| -> Vec<String> {
+            db.search_softwares(query)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.id)
+                .collect()
+        };
+
+        // 名称（不区分大小写）、数据源标识、最新版本
+        assert_eq!(ids("studio"), vec!["a"]);
+        assert_eq!(ids("NODEJS"), vec!["b"]);
+        assert_eq!(ids("22.2"), vec!["b"]);
+
+        // 通配符按字面匹配
+        assert_eq!(ids("%"), vec!["c"]);
+        assert_eq!(ids("o_e"), Vec::<String>::new());
+        assert_eq!(ids("e_t"), vec!["c"]);
+    }
+
+    #[test]
+    fn test_find_by_source() {
+        let db = test_db();
+
+        let mut tool = test_software("a", "Tool");
+        tool.source.identifier = "cli/tool".to_string();
+        db.insert_software(&tool).unwrap();
+
+        let found = db
+            .find_by_source(&tool.source.source_type, " cli/tool ")
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, "a");
+
+        assert!(db
+            .find_by_source(&tool.source.source_type, "cli/other")
+            .unwrap()
+            .is_none());
+        assert!(db
+            .find_by_source(&SourceType::Npm, "cli/tool")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_version_history_records_changes() {
+        let db = test_db();
+        let first = Utc::now() - chrono::Duration::hours(2);
+        let second = first + chrono::Duration::hours(1);
+
+        // 两次检查得到不同的版本，中间重复的结果不记录
+        assert!(db.record_version("a", "1.0.0", first).unwrap());
+        assert!(!db.record_version("a", "1.0.0", first + chrono::Duration::minutes(30)).unwrap());
+        assert!(db.record_version("a", "1.1.0", second).unwrap());
+        db.record_version("b", "2.0.0", second).unwrap();
+
+        let history = db.get_version_history("a", 10).unwrap();
+        let versions: Vec<&str> = history.iter().map(|e| e.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.1.0", "1.0.0"]);
+        assert_eq!(history[1].observed_at.timestamp(), first.timestamp());
+
+        assert_eq!(db.get_version_history("a", 1).unwrap().len(), 1);
+        assert!(db.get_version_history("missing", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_github_api_base_url_round_trip() {
+        let db = test_db();
+        let mut settings = AppSettings {
+            github_api_base_url: Some("https://ghe.corp.example.com/api/v3".to_string()),
+            ..AppSettings::default()
+        };
+        db.save_settings(&settings).unwrap();
+
+        let loaded = db.get_settings().unwrap();
+        assert_eq!(
+            loaded.github_api_base_url(),
+            "https://ghe.corp.example.com/api/v3"
+        );
+
+        settings.github_api_base_url = None;
+        db.save_settings(&settings).unwrap();
+        assert_eq!(
+            db.get_settings().unwrap().github_api_base_url(),
+            "https://api.github.com"
+        );
+    }
+
+    #[test]
+    fn test_user_agent_round_trip() {
+        let db = test_db();
+        let mut settings = AppSettings::default();
+        settings.network.user_agent = Some("my-mirror/1.0".to_string());
+        settings.network.contact = Some("ops@example.com".to_string());
+        db.save_settings(&settings).unwrap();
+
+        let loaded = db.get_settings().unwrap();
+        assert_eq!(loaded.network.user_agent.as_deref(), Some("my-mirror/1.0"));
+        assert_eq!(loaded.network.contact.as_deref(), Some("ops@example.com"));
+
+        settings.network.user_agent = None;
+        db.save_settings(&settings).unwrap();
+        let loaded = db.get_settings().unwrap();
+        assert_eq!(loaded.network.user_agent, None);
+        assert_eq!(loaded.network.contact.as_deref(), Some("ops@example.com"));
+    }
+}
@@ -1,19 +1,53 @@
-use std::sync::{Arc, Mutex};
-use tauri::Manager;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, RunEvent};
 
 mod cache;
+mod checker;
+mod cli;
 mod commands;
 mod database;
+mod metrics;
 mod models;
 mod notification;
 mod scheduler;
+mod secrets;
 mod services;
+#[cfg(test)]
+mod test_support;
 mod version;
 
-use cache::CacheManager;
-use database::Database;
-use models::AppSettings;
+use cache::{CacheManager, CacheState};
+use database::{Database, DbState};
+use metrics::{MetricsServer, MetricsState};
+use models::SettingsState;
+use notification::NotificationRateLimiter;
 use scheduler::{BackgroundScheduler, SchedulerState};
+use services::batch::CancelSignal;
+
+/// 应用数据目录下的数据库文件名
+const DATABASE_FILE: &str = "app_version.db";
+
+/// 缓存定期写入磁盘的间隔
+const CACHE_PERSIST_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// 将内存中的缓存写入数据库
+fn persist_cache(app: &AppHandle) {
+    let cache = app.state::<CacheState>();
+    let db = app.state::<DbState>();
+    let Ok(db) = db.lock() else {
+        return;
+    };
+
+    if let Err(e) = cache.save_to_db(&db) {
+        eprintln!("[Cache] Failed to persist cache: {}", e);
+    }
+}
+
+/// 不启动界面，检查所有软件后打印结果，返回进程退出码（有更新时为 1）
+pub fn run_check(args: &[String]) -> i32 {
+    cli::run(args)
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -31,50 +65,112 @@ pub fn run() {
             // Create directory if it doesn't exist
             std::fs::create_dir_all(&app_data_dir).expect("Failed to create app data directory");
 
-            let db_path = app_data_dir.join("app_version.db");
+            let db_path = app_data_dir.join(DATABASE_FILE);
             let db = Database::new(&db_path).expect("Failed to initialize database");
 
             // Load settings from database
             let settings = db.get_settings().unwrap_or_default();
 
-            // Initialize cache with TTL from settings
+            // Initialize cache with TTL from settings, restoring entries from the last run
             let cache = CacheManager::new(settings.cache.ttl_minutes as i64);
+            if let Err(e) = cache.load_from_db(&db) {
+                eprintln!("[Cache] Failed to load cache: {}", e);
+            }
 
             // Initialize scheduler
             let scheduler: SchedulerState = Arc::new(tokio::sync::Mutex::new(BackgroundScheduler::new()));
 
             app.manage(Mutex::new(db));
             app.manage(cache);
-            app.manage(settings.clone());
+            app.manage::<SettingsState>(RwLock::new(settings.clone()));
             app.manage(scheduler.clone());
+            app.manage(CancelSignal::new());
+            app.manage(Mutex::new(NotificationRateLimiter::default()));
+
+            // 指标服务默认关闭，启动失败不影响应用
+            let mut metrics_server = MetricsServer::default();
+            if let Err(e) = metrics_server.start(&settings.metrics, app.handle().clone()) {
+                eprintln!("[Metrics] {}", e);
+            }
+            app.manage::<MetricsState>(tokio::sync::Mutex::new(metrics_server));
 
-            // Start scheduler if auto-refresh is enabled
-            if settings.cache.auto_refresh_enabled && settings.cache.auto_refresh_interval > 0 {
-                let app_handle = app.handle().clone();
-                let scheduler_clone = scheduler.clone();
-                let interval = settings.cache.auto_refresh_interval;
+            // Periodically persist the cache so it survives crashes
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(CACHE_PERSIST_INTERVAL);
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    persist_cache(&app_handle);
+                }
+            });
 
-                tauri::async_runtime::spawn(async move {
+            // 先执行启动检查，再按自动刷新设置启动调度器，避免同一批软件被检查两次
+            let auto_refresh =
+                settings.cache.auto_refresh_enabled && settings.cache.auto_refresh_interval > 0;
+            let cache_config = settings.cache.clone();
+            let app_handle = app.handle().clone();
+            let scheduler_clone = scheduler.clone();
+            let interval = settings.cache.auto_refresh_interval;
+
+            tauri::async_runtime::spawn(async move {
+                scheduler::run_startup_check(&app_handle, &cache_config, &scheduler_clone).await;
+                if auto_refresh {
                     let mut scheduler = scheduler_clone.lock().await;
                     scheduler.start(interval, app_handle);
-                });
-            }
+                }
+            });
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::get_all_softwares,
+            commands::get_software,
+            commands::get_softwares_by_tag,
+            commands::reorder_softwares,
+            commands::search_softwares,
             commands::add_software,
+            commands::add_softwares,
+            commands::duplicate_software,
             commands::update_software,
             commands::delete_software,
+            commands::restore_software,
+            commands::purge_deleted,
+            commands::export_softwares,
+            commands::import_softwares,
             commands::toggle_software,
+            commands::toggle_softwares,
+            commands::set_all_enabled,
+            commands::snooze_software,
             commands::check_version,
             commands::check_all_versions,
+            commands::check_stale_versions,
+            commands::cancel_check,
+            commands::refresh_local_version,
+            commands::count_updates,
+            commands::validate_source,
+            commands::get_release_notes,
+            commands::get_source_url,
+            commands::get_source_token,
+            commands::get_github_rate_limit,
             commands::clear_cache,
             commands::get_settings,
             commands::save_settings,
+            commands::get_notification_history,
+            commands::send_test_notification,
+            commands::get_version_history,
             commands::update_scheduler,
+            commands::pause_scheduler,
+            commands::resume_scheduler,
+            commands::scheduler_status,
+            commands::get_next_check_time,
+            commands::get_backoff_statuses,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                persist_cache(app);
+            }
+        });
 }
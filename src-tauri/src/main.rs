@@ -2,5 +2,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // `--check [--db <path>]` 用于 cron/CI 等无界面场景
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--check") {
+        std::process::exit(app_version_gui_lib::run_check(&args));
+    }
+
     app_version_gui_lib::run()
 }
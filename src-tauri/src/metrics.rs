@@ -0,0 +1,250 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::database::DbState;
+use crate::models::{MetricsConfig, NonSemverStrategy, SettingsState, Software};
+
+/// 请求头的最大长度，超过后直接断开
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// 转义 Prometheus 标签值中的反斜杠、引号和换行
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// 按 Prometheus 文本格式输出已启用软件的更新状态
+pub fn render_metrics(softwares: &[Software], strategy: NonSemverStrategy) -> String {
+    let softwares: Vec<_> = softwares.iter().filter(|s| s.enabled).collect();
+    let labels = |software: &Software| {
+        format!(
+            "name=\"{}\",source=\"{}\"",
+            escape_label(&software.name),
+            software.source.source_type.as_str()
+        )
+    };
+    let mut out = String::new();
+
+    out.push_str("# HELP app_version_update_available Whether a newer version is available.\n");
+    out.push_str("# TYPE app_version_update_available gauge\n");
+    for software in &softwares {
+        let has_update = software.latest_version.as_deref().is_some_and(|latest| {
+            software.has_update(
+                strategy,
+                latest,
+                &software.local_version,
+                software.published_at,
+            )
+        });
+        out.push_str(&format!(
+            "app_version_update_available{{{}}} {}\n",
+            labels(software),
+            u8::from(has_update)
+        ));
+    }
+
+    out.push_str("# HELP app_version_last_check_timestamp Unix time of the last version check.\n");
+    out.push_str("# TYPE app_version_last_check_timestamp gauge\n");
+    for software in &softwares {
+        if let Some(checked_at) = software.last_checked_at {
+            out.push_str(&format!(
+                "app_version_last_check_timestamp{{{}}} {}\n",
+                labels(software),
+                checked_at.timestamp()
+            ));
+        }
+    }
+
+    out
+}
+
+/// 读取请求行，只关心方法和路径
+async fn read_request_line(stream: &mut TcpStream) -> Option<String> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 || buffer.len() + n > MAX_REQUEST_BYTES {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+
+    let text = String::from_utf8_lossy(&buffer);
+    text.lines().next().map(str::to_string)
+}
+
+async fn handle_connection<F>(mut stream: TcpStream, render: &F)
+where
+    F: Fn() -> String,
+{
+    let Some(request_line) = read_request_line(&mut stream).await else {
+        return;
+    };
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let (status, body) = match (method, path) {
+        ("GET", "/metrics") => ("200 OK", render()),
+        ("GET", _) => ("404 Not Found", "not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "method not allowed\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\n\
+         Content-Type: text/plain; version=0.0.4\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// 在 `listener` 上提供 `/metrics`，每次请求调用 `render` 生成内容
+pub async fn serve<F>(listener: TcpListener, render: F)
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    let render = Arc::new(render);
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let render = render.clone();
+        tokio::spawn(async move { handle_connection(stream, render.as_ref()).await });
+    }
+}
+
+/// 从数据库读取当前状态生成指标
+///
+/// 比较策略取自内存中的设置，软件列表不包含钥匙串中的数据源 token，抓取时不读取设置表和钥匙串
+fn render_from_app(app: &AppHandle) -> String {
+    let strategy = app
+        .state::<SettingsState>()
+        .read()
+        .map(|settings| settings.non_semver_strategy)
+        .unwrap_or_default();
+    let db = app.state::<DbState>();
+    let Ok(db) = db.lock() else {
+        return String::new();
+    };
+    let softwares = db.get_all_softwares().unwrap_or_default();
+    render_metrics(&softwares, strategy)
+}
+
+/// 指标服务的运行状态，设置变化时重启
+#[derive(Default)]
+pub struct MetricsServer {
+    task: Option<JoinHandle<()>>,
+    /// 当前生效的配置，启动失败时为 None，下次保存设置时重试
+    config: Option<MetricsConfig>,
+}
+
+impl MetricsServer {
+    /// 配置变化时重启服务，未启用时只停止
+    pub async fn apply(&mut self, config: &MetricsConfig, app: AppHandle) -> Result<(), String> {
+        if self.config.as_ref() == Some(config) {
+            return Ok(());
+        }
+        if let Some(task) = self.task.take() {
+            task.abort();
+            // 等待任务结束释放监听端口，再按新配置绑定
+            let _ = task.await;
+        }
+        self.start(config, app)
+    }
+
+    /// 按配置启动服务，调用前服务必须处于停止状态
+    pub fn start(&mut self, config: &MetricsConfig, app: AppHandle) -> Result<(), String> {
+        self.config = None;
+        if !config.enabled {
+            self.config = Some(config.clone());
+            return Ok(());
+        }
+
+        let addr: SocketAddr = format!("{}:{}", config.bind_address.trim(), config.port)
+            .parse()
+            .map_err(|e| format!("Invalid metrics address: {}", e))?;
+        let listener = std::net::TcpListener::bind(addr)
+            .map_err(|e| format!("Failed to bind metrics server on {}: {}", addr, e))?;
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+        self.task = Some(tauri::async_runtime::spawn(async move {
+            match TcpListener::from_std(listener) {
+                Ok(listener) => serve(listener, move || render_from_app(&app)).await,
+                Err(e) => eprintln!("[Metrics] Failed to start server: {}", e),
+            }
+        }));
+        self.config = Some(config.clone());
+        Ok(())
+    }
+}
+
+pub type MetricsState = tokio::sync::Mutex<MetricsServer>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SourceConfig, SourceType};
+    use chrono::{TimeZone, Utc};
+
+    fn software(id: &str, name: &str, latest: &str, local: &str) -> Software {
+        let mut software = Software::new(
+            id.to_string(),
+            name.to_string(),
+            SourceConfig::new(SourceType::Npm, id),
+        );
+        software.latest_version = Some(latest.to_string());
+        software.local_version = Some(local.to_string());
+        software.last_checked_at = Utc.timestamp_opt(1718064000, 0).single();
+        software
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label("a\nb"), "a\\nb");
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let mut disabled = software("c", "Disabled", "2.0.0", "1.0.0");
+        disabled.enabled = false;
+        let softwares = vec![
+            software("a", "React", "18.3.1", "18.2.0"),
+            software("b", "Lodash", "4.17.21", "4.17.21"),
+            disabled,
+        ];
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, move || {
+            render_metrics(&softwares, NonSemverStrategy::default())
+        }));
+
+        let response = reqwest::get(format!("http://{}/metrics", addr))
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body = response.text().await.unwrap();
+        let lines: Vec<_> = body.lines().collect();
+
+        assert!(lines.contains(&"app_version_update_available{name=\"React\",source=\"npm\"} 1"));
+        assert!(lines.contains(&"app_version_update_available{name=\"Lodash\",source=\"npm\"} 0"));
+        assert!(lines.contains(
+            &"app_version_last_check_timestamp{name=\"React\",source=\"npm\"} 1718064000"
+        ));
+        assert!(!body.contains("Disabled"));
+
+        let response = reqwest::get(format!("http://{}/other", addr)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+}
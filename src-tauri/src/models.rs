@@ -1,5 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::version::{clean_version_prefix, comparator, VersionChange};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
@@ -7,9 +11,33 @@ pub enum SourceType {
     GithubRelease,
     GithubTags,
     Homebrew,
+    HomebrewCask,
     Npm,
     Pypi,
     Cargo,
+    DockerHub,
+    #[serde(rename = "rubygems")]
+    RubyGems,
+    #[serde(rename = "nuget")]
+    NuGet,
+    GoModule,
+    Bitbucket,
+    Maven,
+    Conda,
+    Packagist,
+    Hex,
+    Flathub,
+    Aur,
+    SourceForge,
+    Winget,
+    Cran,
+    Gitea,
+    GitTags,
+    Fdroid,
+    UbuntuPackage,
+    CustomJson,
+    Feed,
+    WebScrape,
 }
 
 impl SourceType {
@@ -18,9 +46,31 @@ impl SourceType {
             SourceType::GithubRelease => "github-release",
             SourceType::GithubTags => "github-tags",
             SourceType::Homebrew => "homebrew",
+            SourceType::HomebrewCask => "homebrew-cask",
             SourceType::Npm => "npm",
             SourceType::Pypi => "pypi",
             SourceType::Cargo => "cargo",
+            SourceType::DockerHub => "docker-hub",
+            SourceType::RubyGems => "rubygems",
+            SourceType::NuGet => "nuget",
+            SourceType::GoModule => "go-module",
+            SourceType::Bitbucket => "bitbucket",
+            SourceType::Maven => "maven",
+            SourceType::Conda => "conda",
+            SourceType::Packagist => "packagist",
+            SourceType::Hex => "hex",
+            SourceType::Flathub => "flathub",
+            SourceType::Aur => "aur",
+            SourceType::SourceForge => "sourceforge",
+            SourceType::Winget => "winget",
+            SourceType::Cran => "cran",
+            SourceType::Gitea => "gitea",
+            SourceType::GitTags => "git-tags",
+            SourceType::Fdroid => "fdroid",
+            SourceType::UbuntuPackage => "ubuntu-package",
+            SourceType::CustomJson => "custom-json",
+            SourceType::Feed => "feed",
+            SourceType::WebScrape => "web-scrape",
         }
     }
 
@@ -29,27 +79,254 @@ impl SourceType {
             "github-release" => Some(SourceType::GithubRelease),
             "github-tags" => Some(SourceType::GithubTags),
             "homebrew" => Some(SourceType::Homebrew),
+            "homebrew-cask" => Some(SourceType::HomebrewCask),
             "npm" => Some(SourceType::Npm),
             "pypi" => Some(SourceType::Pypi),
             "cargo" => Some(SourceType::Cargo),
+            "docker-hub" => Some(SourceType::DockerHub),
+            "rubygems" => Some(SourceType::RubyGems),
+            "nuget" => Some(SourceType::NuGet),
+            "go-module" => Some(SourceType::GoModule),
+            "bitbucket" => Some(SourceType::Bitbucket),
+            "maven" => Some(SourceType::Maven),
+            "conda" => Some(SourceType::Conda),
+            "packagist" => Some(SourceType::Packagist),
+            "hex" => Some(SourceType::Hex),
+            "flathub" => Some(SourceType::Flathub),
+            "aur" => Some(SourceType::Aur),
+            "sourceforge" => Some(SourceType::SourceForge),
+            "winget" => Some(SourceType::Winget),
+            "cran" => Some(SourceType::Cran),
+            "gitea" => Some(SourceType::Gitea),
+            "git-tags" => Some(SourceType::GitTags),
+            "fdroid" => Some(SourceType::Fdroid),
+            "ubuntu-package" => Some(SourceType::UbuntuPackage),
+            "custom-json" => Some(SourceType::CustomJson),
+            "feed" => Some(SourceType::Feed),
+            "web-scrape" => Some(SourceType::WebScrape),
             _ => None,
         }
     }
 }
 
+/// 判断是否有更新的依据
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompareMode {
+    /// 比较版本号
+    #[default]
+    Version,
+    /// 比较发布时间，适用于版本号是 commit hash 等无法比较的数据源
+    PublishedDate,
+}
+
+impl CompareMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompareMode::Version => "version",
+            CompareMode::PublishedDate => "published-date",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "version" => Some(CompareMode::Version),
+            "published-date" => Some(CompareMode::PublishedDate),
+            _ => None,
+        }
+    }
+}
+
+/// GitHub Release 从哪个字段读取版本号
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum VersionFrom {
+    /// tag 名，如 `v2.3.4`
+    #[default]
+    TagName,
+    /// release 标题，如 `Release 2.3.4`，适用于 tag 是 `build-20240115` 这类名称的项目
+    ReleaseName,
+}
+
+impl VersionFrom {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VersionFrom::TagName => "tag-name",
+            VersionFrom::ReleaseName => "release-name",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "tag-name" => Some(VersionFrom::TagName),
+            "release-name" => Some(VersionFrom::ReleaseName),
+            _ => None,
+        }
+    }
+}
+
+/// 从版本列表中选取时跟踪的发布渠道
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReleaseChannel {
+    /// 不限渠道，是否包含预发布版本由 `include_prerelease` 决定
+    #[default]
+    Any,
+    /// 只取正式版本，即使更新的是预发布版本
+    Stable,
+    /// 只取预发布版本，用于跟踪 beta 等测试渠道
+    Prerelease,
+}
+
+impl ReleaseChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseChannel::Any => "any",
+            ReleaseChannel::Stable => "stable",
+            ReleaseChannel::Prerelease => "prerelease",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "any" => Some(ReleaseChannel::Any),
+            "stable" => Some(ReleaseChannel::Stable),
+            "prerelease" => Some(ReleaseChannel::Prerelease),
+            _ => None,
+        }
+    }
+
+    /// 版本是否属于该渠道
+    pub fn allows(&self, prerelease: bool) -> bool {
+        match self {
+            ReleaseChannel::Any => true,
+            ReleaseChannel::Stable => !prerelease,
+            ReleaseChannel::Prerelease => prerelease,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SourceConfig {
     #[serde(rename = "type")]
     pub source_type: SourceType,
     pub identifier: String,
+    /// 是否包含预发布版本，默认只取正式版本（GitHub Release 改为从 release 列表中选取，
+    /// npm 不再只看 latest 标签）
+    #[serde(default)]
+    pub include_prerelease: bool,
+    /// 只保留匹配此正则的版本，用于返回版本列表的数据源（GitHub、npm、PyPI、Git Tags）
+    #[serde(default)]
+    pub version_filter_regex: Option<String>,
+    /// 排除匹配此正则的版本，如 `nightly`
+    #[serde(default)]
+    pub version_exclude_regex: Option<String>,
+    /// GitHub Release：只把带有匹配此通配符附件的 release 视为最新，如 `*-linux-x86_64.tar.gz`
+    #[serde(default)]
+    pub required_asset: Option<String>,
+    /// GitHub Tags：只考虑以此开头的 tag，如 monorepo 中的 `package-a/`，比较前去掉前缀
+    #[serde(default)]
+    pub tag_prefix: Option<String>,
+    /// GitHub Release / Tags：覆盖全局 `github_token`，用于需要单独授权的私有仓库
+    #[serde(default)]
+    pub auth_token: Option<String>,
+    #[serde(default)]
+    pub compare_by: CompareMode,
+    /// GitHub Release：从 tag 名还是 release 标题读取版本号
+    #[serde(default)]
+    pub version_from: VersionFrom,
+    /// GitHub Release：从上述字段中提取版本号的正则，有捕获组时取第一个捕获组
+    #[serde(default)]
+    pub version_extract_regex: Option<String>,
+    /// 从版本列表中选取的数据源（GitHub、npm、PyPI、Git Tags、Hex、NuGet、Docker Hub、Maven、
+    /// Gitea、Bitbucket、Ubuntu）只取该渠道的版本
+    #[serde(default)]
+    pub channel: ReleaseChannel,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl SourceConfig {
+    /// 测试用：其余选项均为默认值的数据源
+    #[cfg(test)]
+    pub fn new(source_type: SourceType, identifier: impl Into<String>) -> Self {
+        Self {
+            source_type,
+            identifier: identifier.into(),
+            include_prerelease: false,
+            version_filter_regex: None,
+            version_exclude_regex: None,
+            required_asset: None,
+            tag_prefix: None,
+            auth_token: None,
+            compare_by: CompareMode::default(),
+            version_from: VersionFrom::default(),
+            version_extract_regex: None,
+            channel: ReleaseChannel::default(),
+        }
+    }
+}
+
+/// 本地版本的获取方式
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case", rename_all_fields = "camelCase")]
+pub enum LocalVersionSource {
+    /// 执行命令并解析输出
+    #[default]
+    Command,
+    /// 执行命令并把输出解析为 JSON，按 `json_pointer`（如 `/version`）取值
+    CommandJson { json_pointer: String },
+    /// 读取文件，`json_pointer` 为空时用版本正则匹配文件内容
+    FileRead {
+        path: String,
+        #[serde(default)]
+        json_pointer: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LocalVersionConfig {
+    /// 获取方式，默认执行命令
+    #[serde(default)]
+    pub source: LocalVersionSource,
+    /// 要执行的命令，读取文件时不使用
+    #[serde(default)]
     pub command: String,
+    /// 旧版的单个参数，`args` 为空时按空白拆分使用
     pub version_arg: Option<String>,
+    /// 命令参数列表
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 命令的工作目录
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// 额外的环境变量
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
+    /// 自定义版本号提取正则，取第一个捕获组；为空时使用默认规则
+    #[serde(default)]
+    pub version_regex: Option<String>,
+    /// 命令超时时间（秒），为空时使用默认的 10 秒
+    #[serde(default)]
+    pub timeout_seconds: Option<u32>,
+    /// 通过 shell 执行命令（Unix 为 `sh -c`，Windows 为 `cmd /C`），支持管道等语法；
+    /// 命令由 shell 解析，默认直接执行
+    #[serde(default)]
+    pub use_shell: bool,
+}
+
+impl LocalVersionConfig {
+    /// 实际传给命令的参数：优先使用 `args`，否则拆分 `version_arg`，都为空时为 `--version`
+    pub fn command_args(&self) -> Vec<String> {
+        if !self.args.is_empty() {
+            return self.args.clone();
+        }
+
+        match self.version_arg.as_deref().map(str::trim) {
+            Some(arg) if !arg.is_empty() => arg.split_whitespace().map(String::from).collect(),
+            _ => vec!["--version".to_string()],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +346,38 @@ pub struct Software {
     pub last_notified_version: Option<String>,
     #[serde(default)]
     pub last_notified_at: Option<DateTime<Utc>>,
+    /// 单独的检查间隔（分钟），为空时使用全局的自动刷新间隔
+    #[serde(default)]
+    pub check_interval_minutes: Option<u32>,
+    /// 最新版本的发布说明，体积较大，通过 `get_release_notes` 单独获取
+    #[serde(skip)]
+    pub release_notes: Option<String>,
+    /// 最新版本中匹配 `required_asset` 的附件下载地址
+    #[serde(default)]
+    pub asset_url: Option<String>,
+    /// 分组标签，如 "work"、"dev tools"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 列表中的显示顺序，新增的软件排在最后，通过 `reorder_softwares` 调整
+    #[serde(default)]
+    pub sort_order: i64,
+    /// 暂缓提醒的截止时间，通过 `snooze_software` 设置
+    #[serde(default)]
+    pub snoozed_until: Option<DateTime<Utc>>,
+    /// 暂缓提醒时的最新版本，出现其他版本后暂缓失效
+    #[serde(default)]
+    pub snoozed_version: Option<String>,
+    /// 移入回收站的时间，回收站中的软件不参与检查
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// 最近一次检查失败的原因，检查成功后清除
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// 按发布时间比较时已确认的发布时间，之后的发布才算更新
+    #[serde(default)]
+    pub seen_published_at: Option<DateTime<Utc>>,
 }
 
 impl Software {
@@ -85,6 +394,104 @@ impl Software {
             enabled: true,
             last_notified_version: None,
             last_notified_at: None,
+            check_interval_minutes: None,
+            release_notes: None,
+            tags: Vec::new(),
+            sort_order: 0,
+            snoozed_until: None,
+            snoozed_version: None,
+            deleted_at: None,
+            asset_url: None,
+            last_error: None,
+            last_error_at: None,
+            seen_published_at: None,
+        }
+    }
+
+    /// 对 `latest_version` 的提醒是否处于暂缓期
+    pub fn is_snoozed(&self, latest_version: &str, now: DateTime<Utc>) -> bool {
+        self.snoozed_version.as_deref() == Some(latest_version)
+            && self.snoozed_until.is_some_and(|until| until > now)
+    }
+
+    /// 按发布时间比较：最新发布晚于已确认的发布，且本地版本不是最新版本
+    ///
+    /// 还没有确认过的发布时间时，以首次检查到的发布为基准，不视为更新
+    fn has_newer_release(
+        &self,
+        latest_version: &str,
+        local_version: &Option<String>,
+        published_at: Option<DateTime<Utc>>,
+    ) -> bool {
+        let installed = local_version.as_deref().is_some_and(|local| {
+            clean_version_prefix(local) == clean_version_prefix(latest_version)
+        });
+        !installed
+            && matches!(
+                (published_at, self.seen_published_at),
+                (Some(latest), Some(seen)) if latest > seen
+            )
+    }
+
+    /// 是否有可用更新，暂缓提醒期间视为没有更新
+    ///
+    /// `published_at` 是 `latest_version` 的发布时间，只在按发布时间比较时使用
+    pub fn has_update(
+        &self,
+        strategy: NonSemverStrategy,
+        latest_version: &str,
+        local_version: &Option<String>,
+        published_at: Option<DateTime<Utc>>,
+    ) -> bool {
+        let newer = match self.source.compare_by {
+            CompareMode::Version => comparator::has_update_for(
+                &self.source.source_type,
+                strategy,
+                latest_version,
+                local_version,
+            ),
+            CompareMode::PublishedDate => {
+                self.has_newer_release(latest_version, local_version, published_at)
+            }
+        };
+        newer && !self.is_snoozed(latest_version, Utc::now())
+    }
+
+    /// 判断更新的类型，按发布时间比较时无法区分，有更新时为 `Other`
+    pub fn change_kind(
+        &self,
+        strategy: NonSemverStrategy,
+        latest_version: &str,
+        local_version: &Option<String>,
+        published_at: Option<DateTime<Utc>>,
+    ) -> Option<VersionChange> {
+        match self.source.compare_by {
+            CompareMode::Version => comparator::classify_change_for(
+                &self.source.source_type,
+                strategy,
+                latest_version,
+                local_version,
+            ),
+            CompareMode::PublishedDate => self
+                .has_newer_release(latest_version, local_version, published_at)
+                .then_some(VersionChange::Other),
+        }
+    }
+
+    /// 记录检查结果后更新已确认的发布时间：按发布时间比较时，首次检查的发布作为基准，
+    /// 本地版本与最新版本一致时视为已更新到该发布
+    pub fn refresh_seen_published_at(&mut self) {
+        if self.source.compare_by != CompareMode::PublishedDate || self.published_at.is_none() {
+            return;
+        }
+        let installed = match (&self.latest_version, &self.local_version) {
+            (Some(latest), Some(local)) => {
+                clean_version_prefix(latest) == clean_version_prefix(local)
+            }
+            _ => false,
+        };
+        if self.seen_published_at.is_none() || installed {
+            self.seen_published_at = self.published_at;
         }
     }
 }
@@ -95,6 +502,10 @@ pub struct SoftwareFormData {
     pub name: String,
     pub source: SourceConfig,
     pub local_version_config: Option<LocalVersionConfig>,
+    #[serde(default)]
+    pub check_interval_minutes: Option<u32>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +516,162 @@ pub struct VersionCheckResult {
     pub local_version: Option<String>,
     pub published_at: Option<DateTime<Utc>>,
     pub has_update: bool,
+    /// 更新的类型，没有更新时为空
+    #[serde(default)]
+    pub change_kind: Option<VersionChange>,
+}
+
+/// 远程获取时得到的发布说明和附件地址，只随本次远程获取的结果写回
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReleaseDetails {
+    pub release_notes: Option<String>,
+    pub asset_url: Option<String>,
+}
+
+/// 远程获取失败的软件
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckFailure {
+    pub software_id: String,
+    pub name: String,
+    pub error: String,
+}
+
+/// 数据源预览结果，仅用于添加前校验，不写入数据库
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionPreview {
+    pub latest_version: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub asset_url: Option<String>,
+}
+
+/// 连续检查失败的软件的退避状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackoffStatus {
+    pub software_id: String,
+    pub consecutive_failures: u32,
+    /// 下一次重试的时间
+    pub next_retry_at: DateTime<Utc>,
+}
+
+/// 后台调度器的状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerStatus {
+    /// 调度循环是否在运行（关闭自动刷新时为 false）
+    pub running: bool,
+    /// 是否暂停了自动检查
+    pub paused: bool,
+    pub interval_minutes: u32,
+}
+
+/// 通知渠道
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationChannel {
+    Desktop,
+    Webhook,
+}
+
+impl NotificationChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::Desktop => "desktop",
+            NotificationChannel::Webhook => "webhook",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "desktop" => Some(NotificationChannel::Desktop),
+            "webhook" => Some(NotificationChannel::Webhook),
+            _ => None,
+        }
+    }
+}
+
+/// 已发送的通知记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationLogEntry {
+    pub id: i64,
+    pub software_id: String,
+    pub version: String,
+    pub sent_at: DateTime<Utc>,
+    pub channel: NotificationChannel,
+}
+
+/// 测试通知在单个渠道上的发送结果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationTestResult {
+    pub channel: NotificationChannel,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl NotificationTestResult {
+    pub fn new(channel: NotificationChannel, result: Result<(), String>) -> Self {
+        Self {
+            channel,
+            success: result.is_ok(),
+            error: result.err(),
+        }
+    }
+}
+
+/// 观察到的最新版本变化记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionHistoryEntry {
+    pub id: i64,
+    pub software_id: String,
+    pub version: String,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// 导入失败的条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportFailure {
+    pub id: String,
+    pub name: String,
+    pub error: String,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    pub imported: usize,
+    pub failed: Vec<ImportFailure>,
+}
+
+/// 批量添加失败的条目，`index` 为提交时的位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchAddFailure {
+    pub index: usize,
+    pub name: String,
+    pub error: String,
+}
+
+/// 批量添加结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchAddResult {
+    pub added: Vec<Software>,
+    pub failed: Vec<BatchAddFailure>,
+}
+
+/// GitHub API 限额信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GithubRateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,6 +680,20 @@ pub struct CacheConfig {
     pub ttl_minutes: u32,
     pub auto_refresh_enabled: bool,
     pub auto_refresh_interval: u32,
+    /// 获取失败的结果缓存多久（分钟），应短于成功结果的 TTL
+    #[serde(default = "default_error_ttl_minutes")]
+    pub error_ttl_minutes: u32,
+    /// 启动应用时立即检查所有已启用的软件，不等待检查间隔
+    #[serde(default = "default_check_on_startup")]
+    pub check_on_startup: bool,
+}
+
+fn default_error_ttl_minutes() -> u32 {
+    5
+}
+
+fn default_check_on_startup() -> bool {
+    true
 }
 
 impl Default for CacheConfig {
@@ -121,6 +702,84 @@ impl Default for CacheConfig {
             ttl_minutes: 30,
             auto_refresh_enabled: true,
             auto_refresh_interval: 60,
+            error_ttl_minutes: default_error_ttl_minutes(),
+            check_on_startup: default_check_on_startup(),
+        }
+    }
+}
+
+/// 网络配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkConfig {
+    /// 单个请求的超时时间（秒）
+    pub request_timeout_seconds: u32,
+    /// 批量检查时错开请求的最大随机延迟（秒），0 表示不错开
+    #[serde(default)]
+    pub check_jitter_seconds: u32,
+    /// 网络错误或 5xx 响应时 GET 请求的最大重试次数
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// 首次重试前的等待时间（毫秒），之后每次翻倍
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u32,
+    /// 自定义 User-Agent，为空时使用 `app-version-gui`
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// 联系方式（如邮箱），通过 `From` 请求头发送给数据源
+    #[serde(default)]
+    pub contact: Option<String>,
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_retry_base_delay_ms() -> u32 {
+    500
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout_seconds: 15,
+            check_jitter_seconds: 0,
+            max_retries: default_max_retries(),
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            user_agent: None,
+            contact: None,
+        }
+    }
+}
+
+/// Prometheus 指标服务配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsConfig {
+    /// 是否提供 `/metrics`，默认关闭
+    pub enabled: bool,
+    /// 监听端口
+    #[serde(default = "default_metrics_port")]
+    pub port: u16,
+    /// 监听地址，默认只允许本机访问
+    #[serde(default = "default_metrics_bind_address")]
+    pub bind_address: String,
+}
+
+fn default_metrics_port() -> u16 {
+    9464
+}
+
+fn default_metrics_bind_address() -> String {
+    "127.0.0.1".to_string()
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_metrics_port(),
+            bind_address: default_metrics_bind_address(),
         }
     }
 }
@@ -140,8 +799,74 @@ impl Default for ThemeMode {
     }
 }
 
+impl ThemeMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeMode::Light => "light",
+            ThemeMode::Dark => "dark",
+            ThemeMode::System => "system",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "light" => Some(ThemeMode::Light),
+            "dark" => Some(ThemeMode::Dark),
+            "system" => Some(ThemeMode::System),
+            _ => None,
+        }
+    }
+}
+
+/// 两个无法按语义化规则比较的版本不相等时的处理策略
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NonSemverStrategy {
+    /// 只要不相等就认为有更新
+    Optimistic,
+    /// 按字符串字典序比较，适用于日期等定长格式
+    LexicographicGreater,
+    /// 无法判断，不提示更新
+    #[default]
+    TreatDifferentAsUnknown,
+}
+
+impl NonSemverStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NonSemverStrategy::Optimistic => "optimistic",
+            NonSemverStrategy::LexicographicGreater => "lexicographic-greater",
+            NonSemverStrategy::TreatDifferentAsUnknown => "treat-different-as-unknown",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "optimistic" => Some(NonSemverStrategy::Optimistic),
+            "lexicographic-greater" => Some(NonSemverStrategy::LexicographicGreater),
+            "treat-different-as-unknown" => Some(NonSemverStrategy::TreatDifferentAsUnknown),
+            _ => None,
+        }
+    }
+}
+
+/// Webhook 通知配置（Slack/Discord 兼容的 incoming webhook）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub url: String,
+    pub enabled: bool,
+}
+
+impl WebhookConfig {
+    /// 是否启用且配置了地址
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.url.trim().is_empty()
+    }
+}
+
 /// 通知配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct NotificationConfig {
     /// 是否启用通知
@@ -158,8 +883,62 @@ pub struct NotificationConfig {
     pub silent_start_hour: Option<u8>,
     /// 静默时段结束小时 (0-23)
     pub silent_end_hour: Option<u8>,
+    /// 静默时段开始分钟 (0-59)，为空视为 0
+    #[serde(default)]
+    pub silent_start_minute: Option<u8>,
+    /// 静默时段结束分钟 (0-59)，为空视为 0
+    #[serde(default)]
+    pub silent_end_minute: Option<u8>,
     /// 测试模式：即使没有更新也发送通知
     pub test_mode: bool,
+    /// Webhook 通知渠道
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    /// 系统通知的发送方式
+    #[serde(default)]
+    pub style: NotificationStyle,
+    /// 每小时最多通知的次数，0 表示不限制
+    #[serde(default = "default_max_notifications_per_hour")]
+    pub max_per_hour: u32,
+    /// 每个软件每小时最多通知的次数，0 表示不限制
+    #[serde(default = "default_max_notifications_per_software_per_hour")]
+    pub max_per_software_per_hour: u32,
+}
+
+fn default_max_notifications_per_hour() -> u32 {
+    20
+}
+
+fn default_max_notifications_per_software_per_hour() -> u32 {
+    2
+}
+
+/// 一轮检查发现多个更新时系统通知的发送方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationStyle {
+    /// 每个软件一条通知
+    #[default]
+    Individual,
+    /// 汇总为一条通知
+    Digest,
+}
+
+impl NotificationStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationStyle::Individual => "individual",
+            NotificationStyle::Digest => "digest",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "individual" => Some(NotificationStyle::Individual),
+            "digest" => Some(NotificationStyle::Digest),
+            _ => None,
+        }
+    }
 }
 
 impl Default for NotificationConfig {
@@ -172,7 +951,13 @@ impl Default for NotificationConfig {
             notify_on_prerelease: false,
             silent_start_hour: Some(22),
             silent_end_hour: Some(8),
+            silent_start_minute: None,
+            silent_end_minute: None,
             test_mode: false,
+            webhook: WebhookConfig::default(),
+            style: NotificationStyle::default(),
+            max_per_hour: default_max_notifications_per_hour(),
+            max_per_software_per_hour: default_max_notifications_per_software_per_hour(),
         }
     }
 }
@@ -182,10 +967,28 @@ impl Default for NotificationConfig {
 pub struct AppSettings {
     pub cache: CacheConfig,
     pub github_token: Option<String>,
+    /// GitHub API 地址，为空时使用公共 GitHub（企业版形如 `https://host/api/v3`）
+    #[serde(default)]
+    pub github_api_base_url: Option<String>,
+    /// Bitbucket 凭据：access token，或 `用户名:应用密码`
+    #[serde(default)]
+    pub bitbucket_token: Option<String>,
+    /// Gitea/Forgejo 的 access token，访问私有仓库时使用
+    #[serde(default)]
+    pub gitea_token: Option<String>,
     pub theme: ThemeMode,
+    /// 非标准版本不相等时的比较策略
+    #[serde(default)]
+    pub non_semver_strategy: NonSemverStrategy,
     /// 通知配置
     #[serde(default)]
     pub notification: NotificationConfig,
+    /// 网络配置
+    #[serde(default)]
+    pub network: NetworkConfig,
+    /// 指标服务配置
+    #[serde(default)]
+    pub metrics: MetricsConfig,
 }
 
 impl Default for AppSettings {
@@ -193,8 +996,28 @@ impl Default for AppSettings {
         Self {
             cache: CacheConfig::default(),
             github_token: None,
+            github_api_base_url: None,
+            bitbucket_token: None,
+            gitea_token: None,
             theme: ThemeMode::default(),
+            non_semver_strategy: NonSemverStrategy::default(),
             notification: NotificationConfig::default(),
+            network: NetworkConfig::default(),
+            metrics: MetricsConfig::default(),
         }
     }
 }
+
+impl AppSettings {
+    /// 实际使用的 GitHub API 地址
+    pub fn github_api_base_url(&self) -> &str {
+        self.github_api_base_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .unwrap_or(crate::services::github::DEFAULT_API_BASE_URL)
+    }
+}
+
+/// 运行期间使用的设置，启动时从数据库读取，保存设置时整体替换
+pub type SettingsState = RwLock<AppSettings>;
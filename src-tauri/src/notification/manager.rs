@@ -1,6 +1,7 @@
-use chrono::{Timelike, Utc};
+use chrono::{DateTime, Duration, Local, TimeZone, Timelike, Utc};
+use std::sync::Mutex;
 
-use crate::models::{NotificationConfig, Software};
+use crate::models::{NotificationConfig, NotificationStyle, Software};
 use crate::version::{is_prerelease, parse_version, ParsedVersion};
 
 /// 通知判断结果
@@ -9,11 +10,53 @@ pub struct NotificationDecision {
     pub reason: String,
 }
 
+/// 通知频率限制的统计窗口（分钟）
+const RATE_LIMIT_WINDOW_MINUTES: i64 = 60;
+
+/// 最近发送过的通知（仅保存在内存中），防止比较出错时短时间内大量通知
+#[derive(Debug, Clone, Default)]
+pub struct NotificationRateLimiter {
+    sent: Vec<(String, DateTime<Utc>)>,
+}
+
+pub type NotificationLimiterState = Mutex<NotificationRateLimiter>;
+
+impl NotificationRateLimiter {
+    /// 最近一小时的通知次数已达到全局或单个软件的上限
+    pub fn is_limited(
+        &self,
+        config: &NotificationConfig,
+        software_id: &str,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let since = now - Duration::minutes(RATE_LIMIT_WINDOW_MINUTES);
+        let recent: Vec<&str> = self
+            .sent
+            .iter()
+            .filter(|(_, at)| *at > since)
+            .map(|(id, _)| id.as_str())
+            .collect();
+        let for_software = recent.iter().filter(|id| **id == software_id).count();
+
+        let exceeds = |count: usize, max: u32| max > 0 && count >= max as usize;
+        exceeds(recent.len(), config.max_per_hour)
+            || exceeds(for_software, config.max_per_software_per_hour)
+    }
+
+    /// 记录一次通知，同时丢弃统计窗口之外的记录
+    pub fn record(&mut self, software_id: &str, now: DateTime<Utc>) {
+        let since = now - Duration::minutes(RATE_LIMIT_WINDOW_MINUTES);
+        self.sent.retain(|(_, at)| *at > since);
+        self.sent.push((software_id.to_string(), now));
+    }
+}
+
 /// 检查是否应该发送通知
 pub fn should_notify(
     config: &NotificationConfig,
     software: &Software,
     new_version: &str,
+    limiter: &NotificationRateLimiter,
 ) -> NotificationDecision {
     // 测试模式：跳过所有检查（包括静默时段），直接发送通知
     if config.test_mode {
@@ -31,8 +74,16 @@ pub fn should_notify(
         };
     }
 
-    // 检查静默时段
-    if is_silent_period(config) {
+    // 用户对此版本暂缓了提醒
+    if software.is_snoozed(new_version, Utc::now()) {
+        return NotificationDecision {
+            should_notify: false,
+            reason: "此版本已暂缓提醒".to_string(),
+        };
+    }
+
+    // 检查静默时段（按系统本地时区）
+    if is_silent_period(config, &Local::now()) {
         return NotificationDecision {
             should_notify: false,
             reason: "当前处于静默时段".to_string(),
@@ -65,29 +116,43 @@ pub fn should_notify(
         }
     }
 
+    // 最近一小时通知过多
+    if limiter.is_limited(config, &software.id, Utc::now()) {
+        return NotificationDecision {
+            should_notify: false,
+            reason: "rate limited".to_string(),
+        };
+    }
+
     NotificationDecision {
         should_notify: true,
         reason: "版本更新".to_string(),
     }
 }
 
-/// 检查当前是否在静默时段
-fn is_silent_period(config: &NotificationConfig) -> bool {
-    let Some(start) = config.silent_start_hour else {
+/// 检查 `now` 是否在静默时段，静默时段按 `now` 所在时区的墙上时间解释
+fn is_silent_period<Tz: TimeZone>(config: &NotificationConfig, now: &DateTime<Tz>) -> bool {
+    is_silent_at(config, now.hour() * 60 + now.minute())
+}
+
+/// 检查一天中的第 `minute_of_day` 分钟是否在静默时段
+fn is_silent_at(config: &NotificationConfig, minute_of_day: u32) -> bool {
+    let Some(start_hour) = config.silent_start_hour else {
         return false;
     };
-    let Some(end) = config.silent_end_hour else {
+    let Some(end_hour) = config.silent_end_hour else {
         return false;
     };
 
-    let now_hour = Utc::now().hour() as u8;
+    let start = start_hour as u32 * 60 + config.silent_start_minute.unwrap_or(0) as u32;
+    let end = end_hour as u32 * 60 + config.silent_end_minute.unwrap_or(0) as u32;
 
     if start <= end {
         // 正常时段 (e.g., 8:00 - 22:00)
-        now_hour >= start && now_hour < end
+        minute_of_day >= start && minute_of_day < end
     } else {
-        // 跨日时段 (e.g., 22:00 - 8:00)
-        now_hour >= start || now_hour < end
+        // 跨日时段 (e.g., 22:30 - 7:45)
+        minute_of_day >= start || minute_of_day < end
     }
 }
 
@@ -138,32 +203,127 @@ fn check_version_type(
     None // 其他情况默认允许通知
 }
 
-/// 发送系统通知
-pub fn send_notification(
-    app: &tauri::AppHandle,
-    software_name: &str,
-    new_version: &str,
-    local_version: Option<&str>,
-) -> Result<(), String> {
-    use tauri_plugin_notification::NotificationExt;
+/// 一个待通知的版本更新
+pub struct UpdateNotice<'a> {
+    pub software_name: &'a str,
+    pub new_version: &'a str,
+    pub local_version: Option<&'a str>,
+    pub release_url: Option<String>,
+}
+
+/// 一条待发送的系统通知
+#[derive(Debug, PartialEq)]
+pub struct DesktopNotification {
+    pub body: String,
+    /// 点击通知时打开的页面
+    pub release_url: Option<String>,
+    /// 这条通知涵盖的更新在输入中的下标
+    pub covers: Vec<usize>,
+}
 
-    let body = match local_version {
+fn single_body(update: &UpdateNotice) -> String {
+    match update.local_version {
         Some(local) => format!(
             "{} 有新版本可用\n最新版本: {}\n当前版本: {}",
-            software_name, new_version, local
+            update.software_name, update.new_version, local
         ),
         None => format!(
             "{} 有新版本可用\n最新版本: {}",
-            software_name, new_version
+            update.software_name, update.new_version
         ),
-    };
+    }
+}
+
+/// 按通知方式生成要发送的系统通知：逐个通知时每个更新一条，汇总时多个更新合并为一条
+pub fn build_notifications(
+    style: NotificationStyle,
+    updates: &[UpdateNotice],
+) -> Vec<DesktopNotification> {
+    if style == NotificationStyle::Individual || updates.len() < 2 {
+        return updates
+            .iter()
+            .enumerate()
+            .map(|(index, update)| DesktopNotification {
+                body: single_body(update),
+                release_url: update.release_url.clone(),
+                covers: vec![index],
+            })
+            .collect();
+    }
+
+    let lines: Vec<String> = updates
+        .iter()
+        .map(|update| format!("{}: {}", update.software_name, update.new_version))
+        .collect();
+    vec![DesktopNotification {
+        body: format!("{} 个软件有新版本可用\n{}", updates.len(), lines.join("\n")),
+        release_url: None,
+        covers: (0..updates.len()).collect(),
+    }]
+}
+
+/// 发送系统通知
+///
+/// Linux 下直接通过 D-Bus 发送，带有 `release_url` 时点击通知会用 opener 打开该页面；
+/// 其他平台的通知插件不支持点击回调，只能在界面上"打开发布页面"
+pub fn send_notification(
+    app: &tauri::AppHandle,
+    notification: &DesktopNotification,
+) -> Result<(), String> {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        send_clickable_notification(app, notification)
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        use tauri_plugin_notification::NotificationExt;
+
+        app.notification()
+            .builder()
+            .title(NOTIFICATION_TITLE)
+            .body(&notification.body)
+            .show()
+            .map_err(|e| e.to_string())
+    }
+}
 
-    app.notification()
-        .builder()
-        .title("软件更新提醒")
-        .body(&body)
-        .show()
-        .map_err(|e| e.to_string())?;
+const NOTIFICATION_TITLE: &str = "软件更新提醒";
+
+/// 点击通知正文时触发的默认动作
+#[cfg(all(unix, not(target_os = "macos")))]
+const DEFAULT_ACTION: &str = "default";
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn send_clickable_notification(
+    app: &tauri::AppHandle,
+    notification: &DesktopNotification,
+) -> Result<(), String> {
+    let mut builder = notify_rust::Notification::new();
+    builder
+        .summary(NOTIFICATION_TITLE)
+        .body(&notification.body)
+        .auto_icon();
+    if notification.release_url.is_some() {
+        builder.action(DEFAULT_ACTION, "打开发布页面");
+    }
+    let handle = builder.show().map_err(|e| e.to_string())?;
+
+    if let Some(url) = notification.release_url.clone() {
+        let app = app.clone();
+        // wait_for_action 会阻塞到通知被点击或关闭
+        std::thread::spawn(move || {
+            handle.wait_for_action(|action| {
+                if action == DEFAULT_ACTION {
+                    use tauri_plugin_opener::OpenerExt;
+
+                    if let Err(e) = app.opener().open_url(&url, None::<&str>) {
+                        eprintln!("[Notification] Failed to open {}: {}", url, e);
+                    }
+                }
+            });
+        });
+    }
 
     Ok(())
 }
@@ -181,7 +341,13 @@ mod tests {
             notify_on_prerelease: false,
             silent_start_hour: None,
             silent_end_hour: None,
+            silent_start_minute: None,
+            silent_end_minute: None,
             test_mode: false,
+            webhook: Default::default(),
+            style: Default::default(),
+            max_per_hour: 3,
+            max_per_software_per_hour: 2,
         }
     }
 
@@ -189,10 +355,10 @@ mod tests {
         Software {
             id: "test".to_string(),
             name: "Test".to_string(),
-            source: crate::models::SourceConfig {
-                source_type: crate::models::SourceType::GithubRelease,
-                identifier: "test/test".to_string(),
-            },
+            source: crate::models::SourceConfig::new(
+                crate::models::SourceType::GithubRelease,
+                "test/test",
+            ),
             local_version_config: None,
             latest_version: Some("1.0.0".to_string()),
             local_version: Some("1.0.0".to_string()),
@@ -201,6 +367,17 @@ mod tests {
             enabled: true,
             last_notified_version: None,
             last_notified_at: None,
+            check_interval_minutes: None,
+            release_notes: None,
+            tags: Vec::new(),
+            sort_order: 0,
+            snoozed_until: None,
+            snoozed_version: None,
+            deleted_at: None,
+            asset_url: None,
+            last_error: None,
+            last_error_at: None,
+            seen_published_at: None,
         }
     }
 
@@ -210,7 +387,7 @@ mod tests {
         config.enabled = false;
         let software = test_software();
 
-        let decision = should_notify(&config, &software, "2.0.0");
+        let decision = should_notify(&config, &software, "2.0.0", &NotificationRateLimiter::default());
         assert!(!decision.should_notify);
     }
 
@@ -220,16 +397,31 @@ mod tests {
         let mut software = test_software();
         software.last_notified_version = Some("2.0.0".to_string());
 
-        let decision = should_notify(&config, &software, "2.0.0");
+        let decision = should_notify(&config, &software, "2.0.0", &NotificationRateLimiter::default());
         assert!(!decision.should_notify);
     }
 
+    #[test]
+    fn test_snoozed_version() {
+        let config = default_config();
+        let mut software = test_software();
+        software.snoozed_version = Some("2.0.0".to_string());
+        software.snoozed_until = Some(Utc::now() + chrono::Duration::days(1));
+
+        assert!(!should_notify(&config, &software, "2.0.0", &NotificationRateLimiter::default()).should_notify);
+        // 出现更新的版本后暂缓失效
+        assert!(should_notify(&config, &software, "3.0.0", &NotificationRateLimiter::default()).should_notify);
+
+        software.snoozed_until = Some(Utc::now() - chrono::Duration::minutes(1));
+        assert!(should_notify(&config, &software, "2.0.0", &NotificationRateLimiter::default()).should_notify);
+    }
+
     #[test]
     fn test_patch_disabled() {
         let config = default_config();
         let software = test_software();
 
-        let decision = should_notify(&config, &software, "1.0.1");
+        let decision = should_notify(&config, &software, "1.0.1", &NotificationRateLimiter::default());
         assert!(!decision.should_notify);
     }
 
@@ -238,7 +430,7 @@ mod tests {
         let config = default_config();
         let software = test_software();
 
-        let decision = should_notify(&config, &software, "2.0.0");
+        let decision = should_notify(&config, &software, "2.0.0", &NotificationRateLimiter::default());
         assert!(decision.should_notify);
     }
 
@@ -247,7 +439,154 @@ mod tests {
         let config = default_config();
         let software = test_software();
 
-        let decision = should_notify(&config, &software, "1.1.0");
+        let decision = should_notify(&config, &software, "1.1.0", &NotificationRateLimiter::default());
         assert!(decision.should_notify);
     }
+
+    #[test]
+    fn test_burst_is_rate_limited() {
+        let config = default_config();
+        let software = test_software();
+        let mut limiter = NotificationRateLimiter::default();
+        let now = Utc::now();
+
+        for version in ["2.0.0", "3.0.0"] {
+            let decision = should_notify(&config, &software, version, &limiter);
+            assert!(decision.should_notify);
+            limiter.record(&software.id, now);
+        }
+        let decision = should_notify(&config, &software, "4.0.0", &limiter);
+        assert!(!decision.should_notify);
+        assert_eq!(decision.reason, "rate limited");
+
+        // 其他软件在达到全局上限前不受影响
+        let mut other = test_software();
+        other.id = "other".to_string();
+        assert!(should_notify(&config, &other, "2.0.0", &limiter).should_notify);
+        limiter.record(&other.id, now);
+        assert!(!should_notify(&config, &other, "3.0.0", &limiter).should_notify);
+    }
+
+    #[test]
+    fn test_rate_limit_window() {
+        let mut config = default_config();
+        let mut limiter = NotificationRateLimiter::default();
+        let start = Utc::now();
+        limiter.record("test", start);
+        limiter.record("test", start + Duration::minutes(10));
+
+        assert!(limiter.is_limited(&config, "test", start + Duration::minutes(30)));
+        assert!(!limiter.is_limited(&config, "test", start + Duration::minutes(61)));
+
+        config.max_per_software_per_hour = 0;
+        config.max_per_hour = 0;
+        assert!(!limiter.is_limited(&config, "test", start + Duration::minutes(30)));
+    }
+
+    #[test]
+    fn test_silent_period_uses_wall_clock_of_timezone() {
+        use chrono::{FixedOffset, Utc};
+
+        let mut config = default_config();
+        config.silent_start_hour = Some(22);
+        config.silent_end_hour = Some(8);
+
+        // 同一时刻 UTC 14:30 在 UTC+8 是 22:30（静默），在 UTC 是 14:30（不静默）
+        let instant = Utc.with_ymd_and_hms(2024, 6, 1, 14, 30, 0).unwrap();
+        let shanghai = FixedOffset::east_opt(8 * 3600).unwrap();
+        assert!(is_silent_period(&config, &instant.with_timezone(&shanghai)));
+        assert!(!is_silent_period(&config, &instant));
+
+        // UTC+8 的 08:00 正好是静默结束
+        let end = shanghai.with_ymd_and_hms(2024, 6, 2, 8, 0, 0).unwrap();
+        assert!(!is_silent_period(&config, &end));
+        let before_end = shanghai.with_ymd_and_hms(2024, 6, 2, 7, 59, 0).unwrap();
+        assert!(is_silent_period(&config, &before_end));
+
+        // UTC-5 的 21:59 还未进入静默
+        let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+        let evening = new_york.with_ymd_and_hms(2024, 6, 1, 21, 59, 0).unwrap();
+        assert!(!is_silent_period(&config, &evening));
+    }
+
+    fn minute_of_day(hour: u32, minute: u32) -> u32 {
+        hour * 60 + minute
+    }
+
+    #[test]
+    fn test_silent_period_wraps_midnight_with_minutes() {
+        let mut config = default_config();
+        config.silent_start_hour = Some(22);
+        config.silent_start_minute = Some(30);
+        config.silent_end_hour = Some(7);
+        config.silent_end_minute = Some(45);
+
+        assert!(!is_silent_at(&config, minute_of_day(22, 29)));
+        assert!(is_silent_at(&config, minute_of_day(22, 30)));
+        assert!(is_silent_at(&config, minute_of_day(0, 0)));
+        assert!(is_silent_at(&config, minute_of_day(7, 44)));
+        assert!(!is_silent_at(&config, minute_of_day(7, 45)));
+        assert!(!is_silent_at(&config, minute_of_day(12, 0)));
+    }
+
+    #[test]
+    fn test_silent_period_same_day_with_minutes() {
+        let mut config = default_config();
+        config.silent_start_hour = Some(12);
+        config.silent_start_minute = Some(15);
+        config.silent_end_hour = Some(13);
+        config.silent_end_minute = None;
+
+        assert!(!is_silent_at(&config, minute_of_day(12, 14)));
+        assert!(is_silent_at(&config, minute_of_day(12, 15)));
+        assert!(is_silent_at(&config, minute_of_day(12, 59)));
+        assert!(!is_silent_at(&config, minute_of_day(13, 0)));
+    }
+
+    fn notice<'a>(name: &'a str, version: &'a str) -> UpdateNotice<'a> {
+        UpdateNotice {
+            software_name: name,
+            new_version: version,
+            local_version: Some("1.0.0"),
+            release_url: Some(format!("https://example.com/{}", name)),
+        }
+    }
+
+    #[test]
+    fn test_digest_combines_updates() {
+        let updates = vec![
+            notice("Node.js", "22.1.0"),
+            notice("Git", "2.45.0"),
+            notice("Rust", "1.78.0"),
+        ];
+
+        let digest = build_notifications(NotificationStyle::Digest, &updates);
+        assert_eq!(digest.len(), 1);
+        assert_eq!(
+            digest[0].body,
+            "3 个软件有新版本可用\nNode.js: 22.1.0\nGit: 2.45.0\nRust: 1.78.0"
+        );
+        assert_eq!(digest[0].covers, vec![0, 1, 2]);
+        assert_eq!(digest[0].release_url, None);
+
+        let individual = build_notifications(NotificationStyle::Individual, &updates);
+        assert_eq!(individual.len(), 3);
+        assert_eq!(individual[1].covers, vec![1]);
+        assert_eq!(
+            individual[1].release_url.as_deref(),
+            Some("https://example.com/Git")
+        );
+    }
+
+    #[test]
+    fn test_digest_single_update_uses_detailed_body() {
+        let updates = vec![notice("Git", "2.45.0")];
+        let notifications = build_notifications(NotificationStyle::Digest, &updates);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(
+            notifications[0].body,
+            "Git 有新版本可用\n最新版本: 2.45.0\n当前版本: 1.0.0"
+        );
+        assert!(build_notifications(NotificationStyle::Digest, &[]).is_empty());
+    }
 }
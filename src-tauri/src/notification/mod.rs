@@ -1,3 +1,8 @@
 pub mod manager;
+pub mod webhook;
 
-pub use manager::{send_notification, should_notify, NotificationDecision};
+pub use manager::{
+    build_notifications, send_notification, should_notify, DesktopNotification,
+    NotificationDecision, NotificationLimiterState, NotificationRateLimiter, UpdateNotice,
+};
+pub use webhook::send_webhook;
@@ -0,0 +1,105 @@
+use serde::Serialize;
+
+use crate::services::http::HttpClient;
+
+/// Webhook 请求体
+///
+/// 同时包含 Slack 使用的 `text` 和 Discord 使用的 `content`，两者都能直接接收
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload<'a> {
+    text: &'a str,
+    content: &'a str,
+    software: &'a str,
+    new_version: &'a str,
+    local_version: Option<&'a str>,
+}
+
+/// 生成通知文本
+fn format_message(software_name: &str, new_version: &str, local_version: Option<&str>) -> String {
+    match local_version {
+        Some(local) => format!(
+            "{} 有新版本可用：{}（当前版本 {}）",
+            software_name, new_version, local
+        ),
+        None => format!("{} 有新版本可用：{}", software_name, new_version),
+    }
+}
+
+/// 向 Slack/Discord 兼容的 incoming webhook 发送更新通知
+pub async fn send_webhook(
+    http: &HttpClient,
+    url: &str,
+    software_name: &str,
+    new_version: &str,
+    local_version: Option<&str>,
+) -> Result<(), String> {
+    let message = format_message(software_name, new_version, local_version);
+    let payload = WebhookPayload {
+        text: &message,
+        content: &message,
+        software: software_name,
+        new_version,
+        local_version,
+    };
+
+    let request = http.post(url).json(&payload);
+    let response = http.send("Webhook", request).await?;
+
+    if !response.status().is_success() {
+        return Err(format!("Webhook error: {}", response.status()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_send_webhook_payload() {
+        let server = MockServer::start(|_| MockResponse::text(204, "")).await;
+        let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+        let url = format!("{}/hooks/abc", server.url());
+
+        send_webhook(&http, &url, "Node.js", "22.1.0", Some("20.11.0"))
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/hooks/abc");
+        assert_eq!(
+            requests[0].header("content-type"),
+            Some("application/json")
+        );
+
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        let message = "Node.js 有新版本可用：22.1.0（当前版本 20.11.0）";
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "text": message,
+                "content": message,
+                "software": "Node.js",
+                "newVersion": "22.1.0",
+                "localVersion": "20.11.0",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_webhook_error_status() {
+        let server = MockServer::start(|_| MockResponse::text(500, "boom")).await;
+        let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+
+        let err = send_webhook(&http, server.url(), "Node.js", "22.1.0", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "Webhook error: 500 Internal Server Error");
+    }
+}
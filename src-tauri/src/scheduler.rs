@@ -1,27 +1,62 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::sync::watch;
+use tokio::sync::{watch, Notify};
 
 use crate::cache::CacheState;
+use crate::checker;
 use crate::database::DbState;
-use crate::models::{AppSettings, VersionCheckResult};
-use crate::version::comparator;
-use crate::services::{cargo, github, homebrew, local_version, npm, pypi};
-use crate::models::SourceType;
-use crate::notification::manager::{should_notify, send_notification};
-use chrono::Utc;
-use tokio::sync::Semaphore;
+use crate::models::{
+    BackoffStatus, CacheConfig, NotificationChannel, SchedulerStatus, SettingsState, Software,
+    VersionCheckResult,
+};
+use crate::services::{batch, http::HttpClient, urls};
+use crate::notification::{
+    build_notifications, send_notification, send_webhook, should_notify,
+    NotificationLimiterState, UpdateNotice,
+};
+use chrono::{DateTime, Utc};
 
 pub type SchedulerState = Arc<tokio::sync::Mutex<BackgroundScheduler>>;
 
+/// 连续失败时检查间隔放大倍数的上限
+const MAX_BACKOFF_MULTIPLIER: u32 = 32;
+
+/// 单个软件的调度状态（仅保存在内存中）
+#[derive(Debug, Clone)]
+struct CheckState {
+    /// 最近一次检查尝试的时间（包括失败的）
+    last_attempt: DateTime<Utc>,
+    /// 连续失败次数，成功后清零
+    consecutive_failures: u32,
+}
+
+type CheckStates = HashMap<String, CheckState>;
+
 pub struct BackgroundScheduler {
     cancel_tx: Option<watch::Sender<bool>>,
+    /// 软件或间隔变化时唤醒调度循环重新计算下次检查时间
+    reschedule: Arc<Notify>,
+    check_states: Arc<Mutex<CheckStates>>,
+    interval_minutes: u32,
+    /// 暂停时调度循环继续运行但跳过检查，重启调度器不会清除暂停状态
+    paused: Arc<AtomicBool>,
+    /// 调度循环按各软件的到期时间算出的下一次检查时间
+    next_check: Arc<Mutex<Option<DateTime<Utc>>>>,
 }
 
 impl BackgroundScheduler {
     pub fn new() -> Self {
-        Self { cancel_tx: None }
+        Self {
+            cancel_tx: None,
+            reschedule: Arc::new(Notify::new()),
+            check_states: Arc::new(Mutex::new(HashMap::new())),
+            interval_minutes: 0,
+            paused: Arc::new(AtomicBool::new(false)),
+            next_check: Arc::new(Mutex::new(None)),
+        }
     }
 
     pub fn start(&mut self, interval_minutes: u32, app_handle: AppHandle) {
@@ -34,11 +69,24 @@ impl BackgroundScheduler {
 
         let (cancel_tx, cancel_rx) = watch::channel(false);
         self.cancel_tx = Some(cancel_tx);
+        self.interval_minutes = interval_minutes;
 
-        let interval = Duration::from_secs(interval_minutes as u64 * 60);
+        let reschedule = self.reschedule.clone();
+        let check_states = self.check_states.clone();
+        let paused = self.paused.clone();
+        let next_check = self.next_check.clone();
 
         tokio::spawn(async move {
-            run_scheduler(interval, cancel_rx, app_handle).await;
+            run_scheduler(
+                interval_minutes,
+                cancel_rx,
+                reschedule,
+                check_states,
+                paused,
+                next_check,
+                app_handle,
+            )
+            .await;
         });
 
         println!("[Scheduler] Started with interval: {} minutes", interval_minutes);
@@ -55,186 +103,482 @@ impl BackgroundScheduler {
         self.stop();
         self.start(interval_minutes, app_handle);
     }
-}
 
-async fn run_scheduler(interval: Duration, mut cancel_rx: watch::Receiver<bool>, app_handle: AppHandle) {
-    let mut ticker = tokio::time::interval(interval);
-    // 跳过第一个立即触发的 tick
-    ticker.tick().await;
+    /// 软件列表或检查间隔变化后调用，让调度循环重新读取
+    pub fn reschedule(&self) {
+        self.reschedule.notify_one();
+    }
 
-    loop {
-        tokio::select! {
-            _ = ticker.tick() => {
-                println!("[Scheduler] Running scheduled version check...");
-                match perform_version_check(&app_handle).await {
-                    Ok(results) => {
-                        println!("[Scheduler] Check completed, {} results", results.len());
-                        // 通知前端更新
-                        if let Err(e) = app_handle.emit("versions-updated", &results) {
-                            eprintln!("[Scheduler] Failed to emit event: {}", e);
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("[Scheduler] Check failed: {}", e);
-                    }
-                }
-            }
-            _ = cancel_rx.changed() => {
-                if *cancel_rx.borrow() {
-                    println!("[Scheduler] Received cancel signal");
-                    break;
-                }
-            }
+    /// 暂停自动检查，保留当前的检查间隔
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+        println!("[Scheduler] Paused");
+    }
+
+    /// 恢复自动检查，立即补上暂停期间到期的软件
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+        self.reschedule();
+        println!("[Scheduler] Resumed");
+    }
+
+    pub fn status(&self) -> SchedulerStatus {
+        SchedulerStatus {
+            running: self.cancel_tx.is_some(),
+            paused: self.paused.load(Ordering::Relaxed),
+            interval_minutes: self.interval_minutes,
+        }
+    }
+
+    /// 下一次自动检查的时间，调度器未运行、已暂停或调度循环尚未开始时为 None
+    pub fn next_check_time(&self) -> Option<DateTime<Utc>> {
+        if self.cancel_tx.is_none() || self.paused.load(Ordering::Relaxed) {
+            return None;
         }
+        *self.next_check.lock().ok()?
+    }
+
+    /// 记录调度循环之外的一次检查，使启动检查中失败的软件同样按退避延后
+    pub fn record_check(
+        &self,
+        due_ids: &[String],
+        results: &[VersionCheckResult],
+        at: DateTime<Utc>,
+    ) {
+        if let Ok(mut states) = self.check_states.lock() {
+            record_results(&mut states, due_ids, results, at);
+        }
+    }
+
+    /// 清除软件的失败记录（例如修改了数据源之后）
+    pub fn reset_backoff(&self, software_id: &str) {
+        if let Ok(mut states) = self.check_states.lock() {
+            states.remove(software_id);
+        }
+    }
+
+    /// 当前处于退避状态的软件
+    pub fn backoff_statuses(&self, softwares: &[Software]) -> Vec<BackoffStatus> {
+        let Ok(states) = self.check_states.lock() else {
+            return Vec::new();
+        };
+
+        softwares
+            .iter()
+            .filter_map(|software| {
+                let state = states.get(&software.id)?;
+                if state.consecutive_failures == 0 {
+                    return None;
+                }
+
+                Some(BackoffStatus {
+                    software_id: software.id.clone(),
+                    consecutive_failures: state.consecutive_failures,
+                    next_retry_at: next_check_at(software, self.interval_minutes, &states),
+                })
+            })
+            .collect()
     }
 }
 
-async fn perform_version_check(app_handle: &AppHandle) -> Result<Vec<VersionCheckResult>, String> {
-    let db = app_handle.state::<DbState>();
-    let cache = app_handle.state::<CacheState>();
-    let settings = app_handle.state::<AppSettings>();
+/// 连续失败 `failures` 次后检查间隔的放大倍数：`min(2^failures, MAX_BACKOFF_MULTIPLIER)`
+fn backoff_multiplier(failures: u32) -> u32 {
+    if failures == 0 {
+        return 1;
+    }
+
+    2u32.checked_pow(failures)
+        .unwrap_or(u32::MAX)
+        .min(MAX_BACKOFF_MULTIPLIER)
+}
+
+/// 软件实际使用的检查间隔（分钟）
+fn effective_interval(software: &Software, global_interval_minutes: u32) -> u32 {
+    software
+        .check_interval_minutes
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(global_interval_minutes)
+}
 
-    let softwares = {
-        let db = db.lock().map_err(|e| e.to_string())?;
-        db.get_all_softwares().map_err(|e| e.to_string())?
+/// 软件下一次应当检查的时间
+///
+/// 以最近一次成功检查和最近一次尝试中较晚的为准，从未检查过的软件立即到期；
+/// 连续失败时按 `backoff_multiplier` 放大间隔
+fn next_check_at(
+    software: &Software,
+    global_interval_minutes: u32,
+    check_states: &CheckStates,
+) -> DateTime<Utc> {
+    let state = check_states.get(&software.id);
+    let last = match (software.last_checked_at, state.map(|s| s.last_attempt)) {
+        (Some(checked), Some(attempted)) => checked.max(attempted),
+        (Some(checked), None) => checked,
+        (None, Some(attempted)) => attempted,
+        (None, None) => return DateTime::<Utc>::MIN_UTC,
     };
 
-    let github_token = settings.github_token.clone();
+    let failures = state.map_or(0, |s| s.consecutive_failures);
+    let interval = effective_interval(software, global_interval_minutes) as i64
+        * backoff_multiplier(failures) as i64;
+    last + chrono::Duration::minutes(interval)
+}
+
+/// 当前已到期的软件
+fn due_softwares(
+    softwares: &[Software],
+    global_interval_minutes: u32,
+    check_states: &CheckStates,
+    now: DateTime<Utc>,
+) -> Vec<Software> {
+    softwares
+        .iter()
+        .filter(|s| s.enabled)
+        .filter(|s| next_check_at(s, global_interval_minutes, check_states) <= now)
+        .cloned()
+        .collect()
+}
+
+/// 距离下一个软件到期的等待时间，最长不超过全局间隔
+fn time_until_next_due(
+    softwares: &[Software],
+    global_interval_minutes: u32,
+    check_states: &CheckStates,
+    now: DateTime<Utc>,
+) -> Duration {
+    let max_wait = Duration::from_secs(global_interval_minutes as u64 * 60);
+
+    softwares
+        .iter()
+        .filter(|s| s.enabled)
+        .map(|s| next_check_at(s, global_interval_minutes, check_states))
+        .min()
+        .map(|next| (next - now).to_std().unwrap_or(Duration::ZERO))
+        .map_or(max_wait, |wait| wait.min(max_wait))
+}
 
-    let enabled_softwares: Vec<_> = softwares.into_iter().filter(|s| s.enabled).collect();
+/// 按调度循环的等待时间推算下一次检查时间
+fn next_check_after(now: DateTime<Utc>, wait: Duration) -> DateTime<Utc> {
+    now + chrono::Duration::from_std(wait).unwrap_or(chrono::Duration::zero())
+}
 
-    if enabled_softwares.is_empty() {
-        return Ok(Vec::new());
+/// 本轮需要检查的软件，暂停时跳过检查
+fn tick_due_softwares(
+    paused: bool,
+    softwares: &[Software],
+    global_interval_minutes: u32,
+    check_states: &CheckStates,
+    now: DateTime<Utc>,
+) -> Vec<Software> {
+    if paused {
+        return Vec::new();
     }
+    due_softwares(softwares, global_interval_minutes, check_states, now)
+}
 
-    // 先检查缓存
-    let mut cached_results = Vec::new();
-    let mut need_fetch = Vec::new();
+/// 记录一次检查尝试的结果
+fn record_attempt(check_states: &mut CheckStates, software_id: &str, at: DateTime<Utc>, ok: bool) {
+    let state = check_states
+        .entry(software_id.to_string())
+        .or_insert(CheckState {
+            last_attempt: at,
+            consecutive_failures: 0,
+        });
 
-    for software in enabled_softwares {
-        if let Some(cached) = cache.get(&software.id) {
-            let local_version = get_local_version(&software);
-            let has_update = comparator::has_update(&cached.latest_version, &local_version);
-            cached_results.push(VersionCheckResult {
-                software_id: software.id.clone(),
-                latest_version: cached.latest_version,
-                local_version,
-                published_at: cached.published_at,
-                has_update,
-            });
-        } else {
-            need_fetch.push(software);
-        }
+    state.last_attempt = at;
+    state.consecutive_failures = if ok {
+        0
+    } else {
+        state.consecutive_failures.saturating_add(1)
+    };
+}
+
+/// 记录一批检查尝试，没有返回结果的软件视为检查失败
+fn record_results(
+    check_states: &mut CheckStates,
+    due_ids: &[String],
+    results: &[VersionCheckResult],
+    at: DateTime<Utc>,
+) {
+    for id in due_ids {
+        let ok = results.iter().any(|r| &r.software_id == id);
+        record_attempt(check_states, id, at, ok);
     }
+}
 
-    if need_fetch.is_empty() {
-        return Ok(cached_results);
+/// 启动时需要检查的软件：开启启动检查时为所有已启用的软件
+///
+/// 启动检查属于后台检查，关闭自动刷新后同样不执行
+fn startup_softwares(softwares: Vec<Software>, cache: &CacheConfig) -> Vec<Software> {
+    let auto_refresh = cache.auto_refresh_enabled && cache.auto_refresh_interval > 0;
+    if !cache.check_on_startup || !auto_refresh {
+        return Vec::new();
     }
+    softwares.into_iter().filter(|s| s.enabled).collect()
+}
 
-    // 并发获取远程版本
-    let max_concurrent = 5;
-    let semaphore = Arc::new(Semaphore::new(max_concurrent));
+fn load_softwares(app_handle: &AppHandle) -> Vec<Software> {
+    let db = app_handle.state::<DbState>();
+    let softwares = db
+        .lock()
+        .map_err(|e| e.to_string())
+        .and_then(|db| db.get_all_softwares().map_err(|e| e.to_string()));
+    match softwares {
+        Ok(softwares) => softwares,
+        Err(e) => {
+            eprintln!("[Scheduler] Failed to load softwares: {}", e);
+            Vec::new()
+        }
+    }
+}
 
-    let tasks: Vec<_> = need_fetch
-        .into_iter()
-        .map(|software| {
-            let sem = semaphore.clone();
-            let token = github_token.clone();
+/// 应用启动时检查一次所有软件，在调度循环开始前执行，避免关闭期间的数据一直过期
+///
+/// 每次尝试都记入调度状态，调度循环开始后按同样的退避规则计算下次检查时间
+pub async fn run_startup_check(
+    app_handle: &AppHandle,
+    cache: &CacheConfig,
+    scheduler: &SchedulerState,
+) {
+    let due = startup_softwares(load_softwares(app_handle), cache);
+    if due.is_empty() {
+        return;
+    }
 
-            async move {
-                let _permit = sem.acquire().await.map_err(|e| e.to_string())?;
+    let now = Utc::now();
+    let due_ids: Vec<String> = due.iter().map(|s| s.id.clone()).collect();
+    println!("[Scheduler] Running startup check for {} softwares...", due.len());
+    let results = match perform_version_check(app_handle, due).await {
+        Ok(results) => {
+            if let Err(e) = app_handle.emit("versions-updated", &results) {
+                eprintln!("[Scheduler] Failed to emit event: {}", e);
+            }
+            results
+        }
+        Err(e) => {
+            eprintln!("[Scheduler] Startup check failed: {}", e);
+            Vec::new()
+        }
+    };
 
-                let fetch_result = fetch_remote_version(&software, token.as_deref()).await;
-                let local_version = get_local_version(&software);
+    scheduler.lock().await.record_check(&due_ids, &results, now);
+}
 
-                match fetch_result {
-                    Ok((latest_version, published_at)) => {
-                        let has_update = comparator::has_update(&latest_version, &local_version);
-                        Ok((
-                            software.id.clone(),
-                            VersionCheckResult {
-                                software_id: software.id,
-                                latest_version,
-                                local_version,
-                                published_at,
-                                has_update,
-                            },
-                        ))
+async fn run_scheduler(
+    global_interval_minutes: u32,
+    mut cancel_rx: watch::Receiver<bool>,
+    reschedule: Arc<Notify>,
+    check_states: Arc<Mutex<CheckStates>>,
+    paused: Arc<AtomicBool>,
+    next_check: Arc<Mutex<Option<DateTime<Utc>>>>,
+    app_handle: AppHandle,
+) {
+    loop {
+        let softwares = load_softwares(&app_handle);
+
+        let is_paused = paused.load(Ordering::Relaxed);
+        let now = Utc::now();
+        let due = match check_states.lock() {
+            Ok(states) => {
+                tick_due_softwares(is_paused, &softwares, global_interval_minutes, &states, now)
+            }
+            Err(_) => Vec::new(),
+        };
+
+        if !due.is_empty() {
+            let due_ids: Vec<String> = due.iter().map(|s| s.id.clone()).collect();
+
+            println!("[Scheduler] Running scheduled check for {} softwares...", due.len());
+            let results = match perform_version_check(&app_handle, due).await {
+                Ok(results) => {
+                    println!("[Scheduler] Check completed, {} results", results.len());
+                    // 通知前端更新
+                    if let Err(e) = app_handle.emit("versions-updated", &results) {
+                        eprintln!("[Scheduler] Failed to emit event: {}", e);
                     }
-                    Err(e) => Err(format!("Error checking {}: {}", software.name, e)),
+                    results
                 }
+                Err(e) => {
+                    eprintln!("[Scheduler] Check failed: {}", e);
+                    Vec::new()
+                }
+            };
+
+            if let Ok(mut states) = check_states.lock() {
+                record_results(&mut states, &due_ids, &results, now);
             }
-        })
-        .collect();
-
-    let results = futures::future::join_all(tasks).await;
-
-    let mut all_results = cached_results;
-    for result in results {
-        match result {
-            Ok((id, check_result)) => {
-                cache.set(
-                    &id,
-                    check_result.latest_version.clone(),
-                    check_result.published_at,
-                );
-                all_results.push(check_result);
+        }
+
+        // 暂停时按全局间隔空转，恢复时通过 reschedule 立即唤醒
+        let wait_from = Utc::now();
+        let wait = match check_states.lock() {
+            Ok(states) if !is_paused => {
+                time_until_next_due(&softwares, global_interval_minutes, &states, wait_from)
             }
-            Err(e) => eprintln!("{}", e),
+            _ => Duration::from_secs(global_interval_minutes as u64 * 60),
+        };
+
+        // 暂停时没有下一次检查时间
+        let next = (!is_paused).then(|| next_check_after(wait_from, wait));
+        if let Ok(mut next_check) = next_check.lock() {
+            *next_check = next;
+        }
+        if let Err(e) = app_handle.emit("next-check-time", next) {
+            eprintln!("[Scheduler] Failed to emit event: {}", e);
         }
-    }
 
-    // 批量更新数据库
-    {
-        let db = db.lock().map_err(|e| e.to_string())?;
-        for result in &all_results {
-            if let Ok(Some(mut software)) = db.get_software(&result.software_id) {
-                software.latest_version = Some(result.latest_version.clone());
-                software.local_version = result.local_version.clone();
-                software.published_at = result.published_at;
-                software.last_checked_at = Some(Utc::now());
-                let _ = db.update_software(&software);
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = reschedule.notified() => {
+                println!("[Scheduler] Rescheduling");
+            }
+            _ = cancel_rx.changed() => {
+                if *cancel_rx.borrow() {
+                    println!("[Scheduler] Received cancel signal");
+                    break;
+                }
             }
         }
     }
+}
+
+/// 检查已到期的软件
+///
+/// 到期的软件都需要刷新，因此跳过缓存直接请求远程版本，结果再写回缓存
+async fn perform_version_check(
+    app_handle: &AppHandle,
+    need_fetch: Vec<Software>,
+) -> Result<Vec<VersionCheckResult>, String> {
+    let db = app_handle.state::<DbState>();
+    let cache = app_handle.state::<CacheState>();
+    // 每次检查读取当前设置，保存设置后无需重启调度器
+    let settings = app_handle
+        .state::<SettingsState>()
+        .read()
+        .map_err(|e| e.to_string())?
+        .clone();
+    let settings = &settings;
+    let http = HttpClient::new(&settings.network)?;
+    let all_results = checker::check_softwares(
+        need_fetch,
+        true,
+        &db,
+        &cache,
+        &http,
+        settings,
+        batch::never_cancelled(),
+    )
+    .await?
+    .results;
 
     // 发送通知
     let notification_config = &settings.notification;
     if notification_config.enabled || notification_config.test_mode {
-        let db = db.lock().map_err(|e| e.to_string())?;
-        for result in &all_results {
-            // 测试模式下对所有软件发送通知，正常模式下仅对有更新的软件发送
-            if !notification_config.test_mode && !result.has_update {
-                continue;
+        // 先在锁内确定需要通知的软件，发送时不持有数据库锁
+        let limiter = app_handle.state::<NotificationLimiterState>();
+        let pending: Vec<(Software, &VersionCheckResult)> = {
+            let db = db.lock().map_err(|e| e.to_string())?;
+            // 在副本上预占本批次的额度，真正送达后才记入限流器
+            let mut planned = limiter.lock().map_err(|e| e.to_string())?.clone();
+            all_results
+                .iter()
+                // 测试模式下对所有软件发送通知，正常模式下仅对有更新的软件发送
+                .filter(|result| notification_config.test_mode || result.has_update)
+                .filter_map(|result| {
+                    let software = db.get_software(&result.software_id).ok().flatten()?;
+                    let decision = should_notify(
+                        notification_config,
+                        &software,
+                        &result.latest_version,
+                        &planned,
+                    );
+
+                    if decision.should_notify {
+                        planned.record(&software.id, Utc::now());
+                        println!(
+                            "[Scheduler] Sending notification for {}: {} (reason: {})",
+                            software.name, result.latest_version, decision.reason
+                        );
+                        Some((software, result))
+                    } else {
+                        println!(
+                            "[Scheduler] Skip notification for {}: {}",
+                            software.name, decision.reason
+                        );
+                        None
+                    }
+                })
+                .collect()
+        };
+
+        // 汇总模式下多个更新合并为一条系统通知，送达后涵盖的软件都记为已通知
+        let mut desktop_delivered = vec![false; pending.len()];
+        {
+            let updates: Vec<UpdateNotice> = pending
+                .iter()
+                .map(|(software, result)| UpdateNotice {
+                    software_name: &software.name,
+                    new_version: &result.latest_version,
+                    local_version: result.local_version.as_deref(),
+                    release_url: urls::release_url(
+                        &software.source,
+                        settings.github_api_base_url(),
+                    ),
+                })
+                .collect();
+            for notification in build_notifications(notification_config.style, &updates) {
+                match send_notification(app_handle, &notification) {
+                    Ok(()) => {
+                        for index in notification.covers {
+                            desktop_delivered[index] = true;
+                        }
+                    }
+                    Err(e) => eprintln!("[Scheduler] Failed to send notification: {}", e),
+                }
             }
+        }
 
-            if let Ok(Some(mut software)) = db.get_software(&result.software_id) {
-                let decision = should_notify(notification_config, &software, &result.latest_version);
+        for ((software, result), delivered) in pending.into_iter().zip(desktop_delivered) {
+            let mut delivered_channels = Vec::new();
+            if delivered {
+                delivered_channels.push(NotificationChannel::Desktop);
+            }
 
-                if decision.should_notify {
-                    println!(
-                        "[Scheduler] Sending notification for {}: {} (reason: {})",
-                        software.name, result.latest_version, decision.reason
-                    );
+            if notification_config.webhook.is_active() {
+                match send_webhook(
+                    http,
+                    notification_config.webhook.url.trim(),
+                    &software.name,
+                    &result.latest_version,
+                    result.local_version.as_deref(),
+                )
+                .await
+                {
+                    Ok(()) => delivered_channels.push(NotificationChannel::Webhook),
+                    Err(e) => eprintln!("[Scheduler] Failed to send webhook: {}", e),
+                }
+            }
 
-                    if let Err(e) = send_notification(
-                        app_handle,
-                        &software.name,
+            if !delivered_channels.is_empty() {
+                // 只写回通知记录，发送期间对软件的修改不会被覆盖
+                let sent_at = Utc::now();
+                limiter
+                    .lock()
+                    .map_err(|e| e.to_string())?
+                    .record(&software.id, sent_at);
+                let db = db.lock().map_err(|e| e.to_string())?;
+                if let Err(e) = db.set_notified(&software.id, &result.latest_version, sent_at) {
+                    eprintln!("[Scheduler] Failed to record notification: {}", e);
+                }
+
+                for channel in delivered_channels {
+                    if let Err(e) = db.insert_notification_log(
+                        &software.id,
                         &result.latest_version,
-                        result.local_version.as_deref(),
+                        channel,
+                        sent_at,
                     ) {
-                        eprintln!("[Scheduler] Failed to send notification: {}", e);
-                    } else {
-                        // 更新通知记录
-                        software.last_notified_version = Some(result.latest_version.clone());
-                        software.last_notified_at = Some(Utc::now());
-                        let _ = db.update_software(&software);
+                        eprintln!("[Scheduler] Failed to record notification: {}", e);
                     }
-                } else {
-                    println!(
-                        "[Scheduler] Skip notification for {}: {}",
-                        software.name, decision.reason
-                    );
                 }
             }
         }
@@ -243,38 +587,262 @@ async fn perform_version_check(app_handle: &AppHandle) -> Result<Vec<VersionChec
     Ok(all_results)
 }
 
-use crate::models::Software;
-use chrono::DateTime;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SourceConfig, SourceType};
+
+    fn software(id: &str, interval: Option<u32>, checked_minutes_ago: Option<i64>) -> Software {
+        let mut software = Software::new(
+            id.to_string(),
+            id.to_string(),
+            SourceConfig::new(SourceType::Npm, id),
+        );
+        software.check_interval_minutes = interval;
+        software.last_checked_at =
+            checked_minutes_ago.map(|minutes| Utc::now() - chrono::Duration::minutes(minutes));
+        software
+    }
 
-fn get_local_version(software: &Software) -> Option<String> {
-    software.local_version_config.as_ref().and_then(|config| {
-        local_version::get_version(&config.command, config.version_arg.as_deref()).ok()
-    })
-}
+    fn ids(softwares: &[Software]) -> Vec<&str> {
+        softwares.iter().map(|s| s.id.as_str()).collect()
+    }
 
-async fn fetch_remote_version(
-    software: &Software,
-    github_token: Option<&str>,
-) -> Result<(String, Option<DateTime<Utc>>), String> {
-    match software.source.source_type {
-        SourceType::GithubRelease => {
-            github::get_latest_release(&software.source.identifier, github_token).await
-        }
-        SourceType::GithubTags => {
-            github::get_latest_tag(&software.source.identifier, github_token).await
-        }
-        SourceType::Homebrew => {
-            let version = homebrew::get_version(&software.source.identifier).await?;
-            Ok((version, None))
-        }
-        SourceType::Npm => {
-            npm::get_latest_version(&software.source.identifier).await
-        }
-        SourceType::Pypi => {
-            pypi::get_latest_version(&software.source.identifier).await
-        }
-        SourceType::Cargo => {
-            cargo::get_latest_version(&software.source.identifier).await
-        }
+    #[test]
+    fn test_startup_check_ignores_interval() {
+        let mut disabled = software("disabled", None, Some(600));
+        disabled.enabled = false;
+        let softwares = vec![
+            software("fresh", Some(24 * 60), Some(5)),
+            software("never", None, None),
+            disabled,
+        ];
+
+        // 刚检查过的软件在启动时同样会被检查
+        let cache = CacheConfig::default();
+        assert_eq!(
+            ids(&startup_softwares(softwares.clone(), &cache)),
+            vec!["fresh", "never"]
+        );
+        let disabled = CacheConfig {
+            check_on_startup: false,
+            ..CacheConfig::default()
+        };
+        assert!(startup_softwares(softwares.clone(), &disabled).is_empty());
+        // 关闭自动刷新后启动时也不检查
+        let no_auto_refresh = CacheConfig {
+            auto_refresh_enabled: false,
+            ..CacheConfig::default()
+        };
+        assert!(startup_softwares(softwares, &no_auto_refresh).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_startup_check_fetches_recently_checked() {
+        use crate::cache::CacheState;
+        use crate::database::Database;
+        use crate::models::{AppSettings, NetworkConfig};
+        use crate::test_support::{MockResponse, MockServer};
+
+        let server =
+            MockServer::start(|_| MockResponse::json(200, r#"{"dist-tags":{"latest":"2.0.0"}}"#))
+                .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url());
+        let db = Mutex::new(Database::new(":memory:").unwrap());
+        let cache = CacheState::new(60);
+
+        // 五分钟前检查过、缓存仍然有效的软件
+        let fresh = software("fresh", Some(24 * 60), Some(5));
+        db.lock().unwrap().insert_software(&fresh).unwrap();
+        cache.set("fresh", "1.0.0".to_string(), None);
+
+        let softwares = db.lock().unwrap().get_all_softwares().unwrap();
+        let due = startup_softwares(softwares, &CacheConfig::default());
+        let due_ids: Vec<String> = due.iter().map(|s| s.id.clone()).collect();
+        let results = checker::check_softwares(
+            due,
+            true,
+            &db,
+            &cache,
+            &http,
+            &AppSettings::default(),
+            batch::never_cancelled(),
+        )
+        .await
+        .unwrap()
+        .results;
+
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(results[0].latest_version, "2.0.0");
+        let stored = db.lock().unwrap().get_software("fresh").unwrap().unwrap();
+        assert_eq!(stored.latest_version.as_deref(), Some("2.0.0"));
+
+        // 启动检查的结果记入调度状态
+        let mut states = CheckStates::new();
+        record_results(&mut states, &due_ids, &results, Utc::now());
+        assert_eq!(states["fresh"].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_due_uses_per_software_interval() {
+        let softwares = vec![
+            software("nightly", Some(15), Some(20)),
+            software("stable", Some(24 * 60), Some(20)),
+            software("default", None, Some(20)),
+            software("never", None, None),
+        ];
+
+        let due = due_softwares(&softwares, 60, &HashMap::new(), Utc::now());
+        assert_eq!(ids(&due), vec!["nightly", "never"]);
+    }
+
+    #[test]
+    fn test_due_skips_disabled_and_recent_attempts() {
+        let mut disabled = software("disabled", None, None);
+        disabled.enabled = false;
+        let softwares = vec![disabled, software("failed", Some(15), Some(30))];
+
+        let mut check_states = HashMap::new();
+        record_attempt(
+            &mut check_states,
+            "failed",
+            Utc::now() - chrono::Duration::minutes(5),
+            false,
+        );
+
+        let due = due_softwares(&softwares, 60, &check_states, Utc::now());
+        assert!(due.is_empty());
+    }
+
+    #[test]
+    fn test_ticks_skipped_while_paused() {
+        let softwares = vec![software("never", None, None), software("stale", Some(15), Some(30))];
+        let check_states = HashMap::new();
+        let now = Utc::now();
+
+        assert!(tick_due_softwares(true, &softwares, 60, &check_states, now).is_empty());
+        assert_eq!(
+            ids(&tick_due_softwares(false, &softwares, 60, &check_states, now)),
+            vec!["never", "stale"]
+        );
+    }
+
+    #[test]
+    fn test_pause_and_resume_keep_interval() {
+        let mut scheduler = BackgroundScheduler::new();
+        scheduler.interval_minutes = 30;
+
+        scheduler.pause();
+        let status = scheduler.status();
+        assert!(status.paused);
+        assert!(!status.running);
+        assert_eq!(status.interval_minutes, 30);
+
+        scheduler.resume();
+        let status = scheduler.status();
+        assert!(!status.paused);
+        assert_eq!(status.interval_minutes, 30);
+    }
+
+    #[test]
+    fn test_next_check_follows_earliest_due_software() {
+        let now = Utc::now();
+        let softwares = vec![
+            software("nightly", Some(15), Some(10)),
+            software("default", None, Some(10)),
+        ];
+
+        // 按单独设置的 15 分钟间隔到期，而不是全局间隔
+        let wait = time_until_next_due(&softwares, 90, &HashMap::new(), now);
+        assert!(next_check_after(now, wait) <= now + chrono::Duration::minutes(5));
+
+        let tick = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            next_check_after(tick, Duration::from_secs(90 * 60)).to_rfc3339(),
+            "2024-05-01T13:30:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_backoff_multiplier() {
+        assert_eq!(backoff_multiplier(0), 1);
+        assert_eq!(backoff_multiplier(1), 2);
+        assert_eq!(backoff_multiplier(3), 8);
+        assert_eq!(backoff_multiplier(5), MAX_BACKOFF_MULTIPLIER);
+        assert_eq!(backoff_multiplier(40), MAX_BACKOFF_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_backoff_delays_failing_software() {
+        let now = Utc::now();
+        let attempted = now - chrono::Duration::minutes(20);
+        let softwares = vec![software("flaky", Some(15), None)];
+
+        let mut check_states = HashMap::new();
+        record_attempt(&mut check_states, "flaky", attempted, false);
+        // 失败 1 次：间隔放大到 30 分钟
+        assert!(due_softwares(&softwares, 60, &check_states, now).is_empty());
+        assert_eq!(
+            next_check_at(&softwares[0], 60, &check_states),
+            attempted + chrono::Duration::minutes(30)
+        );
+
+        record_attempt(&mut check_states, "flaky", attempted, false);
+        assert_eq!(
+            next_check_at(&softwares[0], 60, &check_states),
+            attempted + chrono::Duration::minutes(60)
+        );
+
+        // 成功后恢复正常间隔
+        record_attempt(&mut check_states, "flaky", attempted, true);
+        assert_eq!(check_states["flaky"].consecutive_failures, 0);
+        assert_eq!(due_softwares(&softwares, 60, &check_states, now).len(), 1);
+    }
+
+    #[test]
+    fn test_missing_results_recorded_as_failures() {
+        let now = Utc::now();
+        let softwares = vec![
+            software("ok", None, Some(120)),
+            software("failed", None, Some(120)),
+        ];
+        let due_ids = vec!["ok".to_string(), "failed".to_string()];
+        let results = vec![VersionCheckResult {
+            software_id: "ok".to_string(),
+            latest_version: "1.0.0".to_string(),
+            local_version: None,
+            published_at: None,
+            has_update: false,
+            change_kind: None,
+        }];
+
+        let mut check_states = HashMap::new();
+        record_results(&mut check_states, &due_ids, &results, now);
+
+        assert_eq!(check_states["ok"].consecutive_failures, 0);
+        assert_eq!(check_states["failed"].consecutive_failures, 1);
+        // 启动检查失败的软件不会在调度循环开始时立即重试
+        assert!(due_softwares(&softwares, 60, &check_states, now).is_empty());
+    }
+
+    #[test]
+    fn test_time_until_next_due() {
+        let now = Utc::now();
+        let softwares = vec![
+            software("nightly", Some(15), Some(10)),
+            software("default", None, Some(10)),
+        ];
+
+        let wait = time_until_next_due(&softwares, 60, &HashMap::new(), now);
+        assert!(wait <= Duration::from_secs(5 * 60));
+        assert!(wait > Duration::from_secs(4 * 60));
+
+        // 没有软件时按全局间隔等待
+        let wait = time_until_next_due(&[], 60, &HashMap::new(), now);
+        assert_eq!(wait, Duration::from_secs(60 * 60));
     }
 }
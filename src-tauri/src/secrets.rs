@@ -0,0 +1,88 @@
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use std::sync::Mutex;
+
+/// 系统钥匙串中的服务名
+const KEYRING_SERVICE: &str = "app-version-gui";
+
+/// 凭据存储后端，`key` 与 settings 表中的键名一致
+pub trait SecretStore: Send + Sync {
+    fn get(&self, key: &str) -> Result<Option<String>, String>;
+    fn set(&self, key: &str, value: &str) -> Result<(), String>;
+    /// 删除不存在的凭据不视为错误
+    fn delete(&self, key: &str) -> Result<(), String>;
+}
+
+/// 系统钥匙串（macOS Keychain、Windows 凭据管理器、Linux Secret Service）
+#[cfg_attr(test, allow(dead_code))]
+pub struct KeyringStore;
+
+#[cfg_attr(test, allow(dead_code))]
+impl KeyringStore {
+    fn entry(key: &str) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYRING_SERVICE, key).map_err(|e| e.to_string())
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        match Self::entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        Self::entry(key)?
+            .set_password(value)
+            .map_err(|e| e.to_string())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        match Self::entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// 内存中的凭据存储，测试时代替系统钥匙串
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+#[cfg(test)]
+impl SecretStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.entries.lock().unwrap().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// 默认的凭据存储，测试中不访问真实的系统钥匙串
+pub fn default_store() -> Box<dyn SecretStore> {
+    #[cfg(test)]
+    {
+        Box::new(MemoryStore::default())
+    }
+    #[cfg(not(test))]
+    {
+        Box::new(KeyringStore)
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AurPackage {
+    /// 形如 `1:2.3.4-1`，包含 epoch 和 pkgrel，按 Debian 风格版本比较
+    version: String,
+    /// 最后修改时间（Unix 时间戳，秒）
+    last_modified: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct AurResponse {
+    #[serde(default)]
+    results: Vec<AurPackage>,
+    /// 请求出错时返回的错误信息
+    error: Option<String>,
+}
+
+/// 获取 AUR 包的最新版本
+pub async fn get_latest_version(
+    http: &HttpClient,
+    pkg: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let pkg = pkg.trim();
+    let request = http
+        .get("https://aur.archlinux.org/rpc/v5/info")
+        .query(&[("arg[]", pkg)]);
+    let response = http.send("AUR", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("AUR", &response));
+    }
+
+    let body: AurResponse = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse AUR response: {}", e)))?;
+
+    if let Some(error) = body.error {
+        return Err(ServiceError::Api(format!("AUR API error: {}", error)));
+    }
+
+    let package = body
+        .results
+        .into_iter()
+        .next()
+        .ok_or_else(|| ServiceError::NotFound(format!("Package '{}' not found in AUR", pkg)))?;
+
+    let published_at = package
+        .last_modified
+        .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single());
+
+    Ok((package.version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+    use crate::version::{compare_versions, parse_version, ParsedVersion, VersionComparison};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://aur.archlinux.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{
+                    "resultcount": 1,
+                    "results": [{
+                        "Name": "yay",
+                        "PackageBase": "yay",
+                        "Version": "12.3.5-1",
+                        "LastModified": 1718064000,
+                        "OutOfDate": null
+                    }],
+                    "type": "multiinfo",
+                    "version": 5
+                }"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) = get_latest_version(&test_client(&server), "yay")
+            .await
+            .unwrap();
+
+        assert_eq!(version, "12.3.5-1");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-06-11T00:00:00+00:00".to_string())
+        );
+        assert_eq!(server.requests()[0].path, "/rpc/v5/info?arg%5B%5D=yay");
+
+        // pkgrel 按 Debian 风格的修订号比较
+        assert!(matches!(
+            parse_version(&version),
+            ParsedVersion::Debian { .. }
+        ));
+        assert_eq!(
+            compare_versions(&version, &Some("12.3.4-2".to_string())),
+            VersionComparison::Greater
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_package() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"resultcount": 0, "results": [], "type": "multiinfo"}"#,
+            )
+        })
+        .await;
+
+        let err = get_latest_version(&test_client(&server), "missing")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
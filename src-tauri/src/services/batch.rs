@@ -0,0 +1,262 @@
+use futures::future::join_all;
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::{watch, Semaphore};
+
+/// 批量检查时的最大并发数，避免触发 API 速率限制
+///
+/// GitHub: 60次/小时（未认证）、5000次/小时（认证）
+pub const MAX_CONCURRENT: usize = 5;
+
+/// 手动批量检查的取消信号
+pub struct CancelSignal {
+    tx: watch::Sender<bool>,
+}
+
+impl CancelSignal {
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(false);
+        Self { tx }
+    }
+
+    /// 取消正在进行的批量检查
+    pub fn cancel(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// 开始新一轮检查：清除上次的取消状态并返回本轮使用的接收端
+    pub fn reset(&self) -> watch::Receiver<bool> {
+        self.tx.send_replace(false);
+        self.tx.subscribe()
+    }
+}
+
+/// 不会被取消的接收端，用于调度器和命令行等不支持取消的批量检查
+pub fn never_cancelled() -> watch::Receiver<bool> {
+    watch::channel(false).1
+}
+
+impl Default for CancelSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 等待取消信号，发送端被丢弃时视为永不取消
+async fn cancelled(mut cancel: watch::Receiver<bool>) {
+    if cancel.wait_for(|cancelled| *cancelled).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// 为 `count` 个任务生成启动延迟
+///
+/// 将 `[0, max_jitter)` 等分为 `count` 段，每个任务在自己的段内随机取一个延迟，
+/// 这样启动时间既分散又不会全部挤在一起
+fn stagger_delays(count: usize, max_jitter: Duration) -> Vec<Duration> {
+    if count == 0 || max_jitter.is_zero() {
+        return vec![Duration::ZERO; count];
+    }
+
+    let slot = max_jitter / count as u32;
+    let mut rng = rand::thread_rng();
+
+    (0..count)
+        .map(|i| slot * i as u32 + slot.mul_f64(rng.gen::<f64>()))
+        .collect()
+}
+
+/// 并发执行任务，同时运行的任务不超过 `max_concurrent` 个
+///
+/// `max_jitter` 不为 0 时各任务按 `stagger_delays` 错开启动，结果顺序与输入一致
+pub async fn run_staggered<T, F, Fut>(
+    items: Vec<T>,
+    max_concurrent: usize,
+    max_jitter: Duration,
+    task: F,
+) -> Vec<Fut::Output>
+where
+    F: Fn(T) -> Fut,
+    Fut: Future,
+{
+    let semaphore = Semaphore::new(max_concurrent.max(1));
+    let delays = stagger_delays(items.len(), max_jitter);
+    let semaphore = &semaphore;
+    let task = &task;
+
+    let tasks = items
+        .into_iter()
+        .zip(delays)
+        .map(|(item, delay)| run_item(item, delay, semaphore, task));
+
+    join_all(tasks).await
+}
+
+/// 等待启动延迟和并发许可后执行单个任务
+async fn run_item<T, F, Fut>(
+    item: T,
+    delay: Duration,
+    semaphore: &Semaphore,
+    task: &F,
+) -> Fut::Output
+where
+    F: Fn(T) -> Fut,
+    Fut: Future,
+{
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+    let _permit = semaphore.acquire().await;
+    task(item).await
+}
+
+/// 与 `run_staggered` 相同，但收到取消信号后放弃尚未完成的任务
+///
+/// 被取消的任务结果为 None，已完成的任务结果保留。
+/// 取消信号同时作用于启动延迟和等待并发许可的阶段，不必等到任务真正开始
+pub async fn run_staggered_cancellable<T, F, Fut>(
+    items: Vec<T>,
+    max_concurrent: usize,
+    max_jitter: Duration,
+    cancel: watch::Receiver<bool>,
+    task: F,
+) -> Vec<Option<Fut::Output>>
+where
+    F: Fn(T) -> Fut,
+    Fut: Future,
+{
+    let semaphore = Semaphore::new(max_concurrent.max(1));
+    let delays = stagger_delays(items.len(), max_jitter);
+    let semaphore = &semaphore;
+    let cancel = &cancel;
+    let task = &task;
+
+    let tasks = items
+        .into_iter()
+        .zip(delays)
+        .map(|(item, delay)| async move {
+            tokio::select! {
+                biased;
+                _ = cancelled(cancel.clone()) => None,
+                output = run_item(item, delay, semaphore, task) => Some(output),
+            }
+        });
+
+    join_all(tasks).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_stagger_delays_spread_across_slots() {
+        let max_jitter = Duration::from_millis(1000);
+        let delays = stagger_delays(4, max_jitter);
+
+        assert_eq!(delays.len(), 4);
+        for (i, delay) in delays.iter().enumerate() {
+            assert!(*delay >= Duration::from_millis(250 * i as u64));
+            assert!(*delay < Duration::from_millis(250 * (i as u64 + 1)));
+        }
+
+        assert_eq!(stagger_delays(3, Duration::ZERO), vec![Duration::ZERO; 3]);
+        assert!(stagger_delays(0, max_jitter).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_staggered_spreads_start_times() {
+        let started = Instant::now();
+
+        let offsets = run_staggered(
+            (0..5).collect(),
+            MAX_CONCURRENT,
+            Duration::from_millis(500),
+            |i: u32| async move { (i, started.elapsed()) },
+        )
+        .await;
+
+        // 结果顺序与输入一致
+        let ids: Vec<u32> = offsets.iter().map(|(i, _)| *i).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+
+        let first = offsets.iter().map(|(_, t)| *t).min().unwrap();
+        let last = offsets.iter().map(|(_, t)| *t).max().unwrap();
+        assert!(last - first >= Duration::from_millis(200));
+        assert!(started.elapsed() < Duration::from_millis(1500));
+    }
+
+    #[tokio::test]
+    async fn test_run_staggered_limits_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        run_staggered((0..10).collect(), 3, Duration::ZERO, |_: u32| async {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            running.fetch_sub(1, Ordering::SeqCst);
+        })
+        .await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_mid_flight() {
+        let signal = CancelSignal::new();
+        let cancel = signal.reset();
+
+        let canceller = async {
+            tokio::time::sleep(Duration::from_millis(70)).await;
+            signal.cancel();
+        };
+        let run = run_staggered_cancellable(
+            (0..10).collect(),
+            2,
+            Duration::ZERO,
+            cancel,
+            |i: u32| async move {
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                i
+            },
+        );
+        let (results, _) = tokio::join!(run, canceller);
+
+        assert_eq!(results.len(), 10);
+        let completed: Vec<u32> = results.iter().flatten().copied().collect();
+        assert!(!completed.is_empty());
+        assert!(completed.len() < 10);
+        // 下一轮检查不受上次取消的影响
+        assert!(!*signal.reset().borrow());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_during_jitter() {
+        let signal = CancelSignal::new();
+        let cancel = signal.reset();
+        let started = Instant::now();
+
+        let canceller = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            signal.cancel();
+        };
+        let run = run_staggered_cancellable(
+            (0..4).collect(),
+            MAX_CONCURRENT,
+            Duration::from_secs(10),
+            cancel,
+            |i: u32| async move { i },
+        );
+        let (results, _) = tokio::join!(run, canceller);
+
+        // 仍在启动延迟中的任务应立即放弃，而不是等延迟结束
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(results.len(), 4);
+        assert!(results[1..].iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,252 @@
+use chrono::{DateTime, Utc};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::is_prerelease;
+
+#[derive(Deserialize)]
+struct BitbucketTarget {
+    date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BitbucketTag {
+    name: String,
+    target: Option<BitbucketTarget>,
+}
+
+/// 分页响应，这里只使用第一页
+#[derive(Deserialize)]
+struct BitbucketTagPage {
+    #[serde(default)]
+    values: Vec<BitbucketTag>,
+}
+
+/// 按凭据格式添加认证头：`用户名:应用密码` 使用 Basic 认证，否则作为 access token
+fn with_auth(request: RequestBuilder, token: Option<&str>) -> RequestBuilder {
+    match token.map(str::trim).filter(|token| !token.is_empty()) {
+        Some(token) => match token.split_once(':') {
+            Some((username, password)) => request.basic_auth(username, Some(password)),
+            None => request.bearer_auth(token),
+        },
+        None => request,
+    }
+}
+
+/// 获取 Bitbucket Cloud 仓库最新的 tag（按提交时间倒序的第一个），设置了过滤条件时取匹配的第一个
+pub async fn get_latest_tag(
+    http: &HttpClient,
+    repo: &str,
+    token: Option<&str>,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let repo = repo.trim().trim_matches('/');
+    if repo.split('/').count() != 2 {
+        return Err(ServiceError::Api(format!(
+            "Invalid Bitbucket repository '{}', expected workspace/repo",
+            repo
+        )));
+    }
+
+    let mut url = format!(
+        "https://api.bitbucket.org/2.0/repositories/{}/refs/tags?sort=-target.date",
+        repo
+    );
+    // 按过滤条件选取时需要向后查找，取更大的一页
+    if !filter.is_empty() {
+        url.push_str("&pagelen=100");
+    }
+    let request = with_auth(http.get(&url), token);
+    let response = http.send("Bitbucket", request).await?;
+
+    // 私有仓库在未认证时同样返回 404
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(ServiceError::NotFound(format!(
+            "Repository '{}' not found on Bitbucket (private repositories require an app password)",
+            repo
+        )));
+    }
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Bitbucket", &response));
+    }
+
+    let page: BitbucketTagPage = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Bitbucket response: {}", e)))?;
+
+    let latest = page
+        .values
+        .into_iter()
+        .find(|tag| filter.matches(&tag.name) && filter.matches_channel(is_prerelease(&tag.name)))
+        .ok_or_else(|| ServiceError::NotFound(format!("No tags found for {}", repo)))?;
+
+    let published_at = latest
+        .target
+        .and_then(|target| target.date)
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    Ok((latest.name, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://api.bitbucket.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_tag() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{
+                    "pagelen": 10,
+                    "next": "https://api.bitbucket.org/2.0/repositories/team/tool/refs/tags?page=2",
+                    "values": [
+                        {"name": "v2.3.0", "target": {"date": "2024-05-01T12:00:00+00:00"}},
+                        {"name": "v2.2.0", "target": {"date": "2024-03-01T12:00:00+00:00"}}
+                    ]
+                }"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) = get_latest_tag(
+            &test_client(&server),
+            "team/tool",
+            None,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "v2.3.0");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-05-01T12:00:00+00:00".to_string())
+        );
+        let request = &server.requests()[0];
+        assert_eq!(
+            request.path,
+            "/2.0/repositories/team/tool/refs/tags?sort=-target.date"
+        );
+        assert_eq!(request.header("authorization"), None);
+    }
+
+    #[tokio::test]
+    async fn test_channel_skips_other_tags() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"values": [{"name": "v2.4.0-beta.1"}, {"name": "v2.3.0"}]}"#,
+            )
+        })
+        .await;
+
+        for (channel, expected) in [
+            (ReleaseChannel::Stable, "v2.3.0"),
+            (ReleaseChannel::Prerelease, "v2.4.0-beta.1"),
+        ] {
+            let (version, _) = get_latest_tag(
+                &test_client(&server),
+                "team/tool",
+                None,
+                &VersionFilter::for_channel(channel),
+            )
+            .await
+            .unwrap();
+            assert_eq!(version, expected);
+        }
+        assert!(server.requests()[0].path.ends_with("&pagelen=100"));
+    }
+
+    #[tokio::test]
+    async fn test_app_password_uses_basic_auth() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, r#"{"values": [{"name": "1.0.0", "target": {}}]}"#)
+        })
+        .await;
+
+        let (version, published_at) = get_latest_tag(
+            &test_client(&server),
+            "team/tool",
+            Some("user:secret"),
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "1.0.0");
+        assert_eq!(published_at, None);
+        // base64("user:secret")
+        assert_eq!(
+            server.requests()[0].header("authorization"),
+            Some("Basic dXNlcjpzZWNyZXQ=")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_private_or_missing_repo() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                404,
+                r#"{"type": "error", "error": {"message": "Not found"}}"#,
+            )
+        })
+        .await;
+
+        let err = get_latest_tag(
+            &test_client(&server),
+            "team/private",
+            None,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap_err();
+        match err {
+            ServiceError::NotFound(message) => assert!(message.contains("app password")),
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_tag_list() {
+        let server = MockServer::start(|_| MockResponse::json(200, r#"{"values": []}"#)).await;
+
+        let err = get_latest_tag(
+            &test_client(&server),
+            "team/tool",
+            None,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_repo() {
+        let server = MockServer::start(|_| MockResponse::json(200, "{}")).await;
+
+        assert!(get_latest_tag(
+            &test_client(&server),
+            "tool",
+            None,
+            &VersionFilter::default()
+        )
+        .await
+        .is_err());
+        assert!(server.requests().is_empty());
+    }
+}
@@ -1,7 +1,12 @@
 use chrono::{DateTime, Utc};
-use reqwest::Client;
 use serde::Deserialize;
 
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+/// 未自定义 User-Agent 时发给 crates.io 的值
+const CRATES_IO_USER_AGENT: &str = "app-version-gui (https://github.com/try-to-fly)";
+
 #[derive(Deserialize)]
 struct CrateCrate {
     max_version: String,
@@ -16,26 +21,28 @@ struct CrateResponse {
 
 /// 获取 crates.io 上的 crate 最新版本
 pub async fn get_latest_version(
+    http: &HttpClient,
     crate_name: &str,
-) -> Result<(String, Option<DateTime<Utc>>), String> {
-    let client = Client::new();
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
     let url = format!("https://crates.io/api/v1/crates/{}", crate_name);
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "app-version-gui (https://github.com/try-to-fly)")
-        .send()
-        .await
-        .map_err(|e| format!("crates.io request failed: {}", e))?;
+    let mut request = http.get(&url);
+    // crates.io 要求 User-Agent 中带有联系方式，用户没有自定义时附上项目地址
+    if !http.has_custom_user_agent() {
+        request = request.header("User-Agent", CRATES_IO_USER_AGENT);
+    }
+    let response = http.send("crates.io", request).await?;
 
     if !response.status().is_success() {
-        return Err(format!("crates.io API error: {}", response.status()));
+        return Err(ServiceError::from_response("crates.io", &response));
     }
 
     let crate_response: CrateResponse = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse crates.io response: {}", e))?;
+        .map_err(|e| {
+            ServiceError::Parse(format!("Failed to parse crates.io response: {}", e))
+        })?;
 
     let latest_version = crate_response.crate_info.max_version;
     let updated_at = crate_response
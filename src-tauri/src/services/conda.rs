@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+/// 未指定 channel 时使用的默认 channel
+const DEFAULT_CHANNEL: &str = "conda-forge";
+
+#[derive(Deserialize)]
+struct CondaPackage {
+    latest_version: Option<String>,
+    modified_at: Option<String>,
+}
+
+/// 拆分 `channel/package`，只写包名时使用 conda-forge
+pub(crate) fn parse_spec(spec: &str) -> Result<(&str, &str), ServiceError> {
+    let spec = spec.trim().trim_matches('/');
+    let (channel, package) = match spec.split_once('/') {
+        Some((channel, package)) => (channel.trim(), package.trim()),
+        None => (DEFAULT_CHANNEL, spec),
+    };
+
+    if channel.is_empty() || package.is_empty() || package.contains('/') {
+        return Err(ServiceError::Api(format!(
+            "Invalid conda package '{}', expected channel/package",
+            spec
+        )));
+    }
+    Ok((channel, package))
+}
+
+/// Anaconda 的时间格式为 `2024-02-06 10:19:39.823000+00:00`
+fn parse_modified_at(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%:z"))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// 获取 Anaconda 上 conda 包的最新版本，`spec` 为 `channel/package`
+pub async fn get_latest_version(
+    http: &HttpClient,
+    spec: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let (channel, package) = parse_spec(spec)?;
+    let url = format!("https://api.anaconda.org/package/{}/{}", channel, package);
+
+    let request = http.get(&url);
+    let response = http.send("Anaconda", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Anaconda", &response));
+    }
+
+    let info: CondaPackage = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Anaconda response: {}", e)))?;
+
+    let version = info
+        .latest_version
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!("No version found for {}/{}", channel, package))
+        })?;
+    let published_at = info.modified_at.as_deref().and_then(parse_modified_at);
+
+    Ok((version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://api.anaconda.org", server.url())
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        assert_eq!(
+            parse_spec("bioconda/samtools").unwrap(),
+            ("bioconda", "samtools")
+        );
+        assert_eq!(parse_spec("numpy").unwrap(), ("conda-forge", "numpy"));
+        assert!(parse_spec("a/b/c").is_err());
+        assert!(parse_spec("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{
+                    "name": "numpy",
+                    "owner": {"login": "conda-forge"},
+                    "latest_version": "1.26.4",
+                    "versions": ["1.26.3", "1.26.4"],
+                    "modified_at": "2024-02-06 10:19:39.823000+00:00"
+                }"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) = get_latest_version(&test_client(&server), "numpy")
+            .await
+            .unwrap();
+
+        assert_eq!(version, "1.26.4");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-02-06T10:19:39.823+00:00".to_string())
+        );
+        assert_eq!(server.requests()[0].path, "/package/conda-forge/numpy");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_package() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(404, r#"{"error": "\"missing\" could not be found"}"#)
+        })
+        .await;
+
+        let err = get_latest_version(&test_client(&server), "conda-forge/missing")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
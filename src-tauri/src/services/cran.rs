@@ -0,0 +1,131 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+#[derive(Deserialize)]
+struct CranPackage {
+    #[serde(rename = "Version")]
+    version: String,
+    /// 发布时间（RFC 3339）
+    date: Option<String>,
+    /// 包文件时间，形如 `2024-04-23 08:12:16`，缺少 `date` 时使用
+    crandb_file_date: Option<String>,
+}
+
+fn parse_date(package: &CranPackage) -> Option<DateTime<Utc>> {
+    package
+        .date
+        .as_deref()
+        .and_then(|date| DateTime::parse_from_rfc3339(date.trim()).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|| {
+            package
+                .crandb_file_date
+                .as_deref()
+                .and_then(|date| {
+                    NaiveDateTime::parse_from_str(date.trim(), "%Y-%m-%d %H:%M:%S").ok()
+                })
+                .map(|dt| dt.and_utc())
+        })
+}
+
+/// 获取 CRAN 上 R 包的最新版本
+pub async fn get_latest_version(
+    http: &HttpClient,
+    package: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let package = package.trim();
+    let url = format!("https://crandb.r-pkg.org/{}", package);
+
+    let request = http.get(&url);
+    let response = http.send("CRAN", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("CRAN", &response));
+    }
+
+    let body: CranPackage = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse CRAN response: {}", e)))?;
+
+    let published_at = parse_date(&body);
+    Ok((body.version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://crandb.r-pkg.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{
+                    "Package": "ggplot2",
+                    "Version": "3.5.1",
+                    "Date/Publication": "2024-04-23 08:00:08 UTC",
+                    "crandb_file_date": "2024-04-23 08:12:16",
+                    "date": "2024-04-23T07:00:08+00:00"
+                }"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) = get_latest_version(&test_client(&server), "ggplot2")
+            .await
+            .unwrap();
+
+        assert_eq!(version, "3.5.1");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-04-23T07:00:08+00:00".to_string())
+        );
+        assert_eq!(server.requests()[0].path, "/ggplot2");
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_file_date() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"Version": "1.1.4", "crandb_file_date": "2023-11-17 16:52:25"}"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) = get_latest_version(&test_client(&server), "dplyr")
+            .await
+            .unwrap();
+
+        assert_eq!(version, "1.1.4");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2023-11-17T16:52:25+00:00".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_package() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(404, r#"{"error": "not_found", "reason": "missing"}"#)
+        })
+        .await;
+
+        let err = get_latest_version(&test_client(&server), "missing")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
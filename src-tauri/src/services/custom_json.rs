@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+/// 解析后的自定义 JSON 数据源配置
+///
+/// 标识符格式为 `URL|版本选择器`，可再追加 `|日期选择器`，
+/// 如 `https://example.com/v.json|$.data.version|$.data.released_at`
+#[derive(Debug, PartialEq)]
+struct CustomJsonSpec<'a> {
+    url: &'a str,
+    version_path: &'a str,
+    date_path: Option<&'a str>,
+}
+
+fn parse_spec(spec: &str) -> Result<CustomJsonSpec<'_>, ServiceError> {
+    let mut parts = spec.split('|').map(str::trim);
+    let url = parts.next().filter(|s| !s.is_empty());
+    let version_path = parts.next().filter(|s| !s.is_empty());
+    let date_path = parts.next().filter(|s| !s.is_empty());
+
+    match (url, version_path) {
+        (Some(url), Some(version_path)) if parts.next().is_none() => Ok(CustomJsonSpec {
+            url,
+            version_path,
+            date_path,
+        }),
+        _ => Err(ServiceError::Parse(format!(
+            "Invalid custom JSON source '{}', expected 'URL|$.path[|$.date_path]'",
+            spec
+        ))),
+    }
+}
+
+/// 路径中的一段：对象字段或数组下标
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// 解析 `$.data.items[0].version` 形式的简化 JSONPath
+fn parse_path(path: &str) -> Result<Vec<PathSegment>, ServiceError> {
+    let invalid = || ServiceError::Parse(format!("Invalid JSON path '{}'", path));
+
+    let rest = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+    let mut chars = rest.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next == '.' || next == '[' {
+                        break;
+                    }
+                    key.push(next);
+                    chars.next();
+                }
+                if key.is_empty() {
+                    return Err(invalid());
+                }
+                segments.push(PathSegment::Key(key));
+            }
+            '[' => {
+                let mut index = String::new();
+                for next in chars.by_ref() {
+                    if next == ']' {
+                        break;
+                    }
+                    index.push(next);
+                }
+                let index = index.trim().parse().map_err(|_| invalid())?;
+                segments.push(PathSegment::Index(index));
+            }
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(segments)
+}
+
+/// 按路径取值
+fn select<'v>(value: &'v Value, path: &[PathSegment]) -> Option<&'v Value> {
+    path.iter()
+        .try_fold(value, |current, segment| match segment {
+            PathSegment::Key(key) => current.get(key.as_str()),
+            PathSegment::Index(index) => current.get(*index),
+        })
+}
+
+/// 按 `$.a.b[0]` 形式的路径取值，也接受 RFC 6901 的 `/a/b/0` 形式
+pub(crate) fn select_path<'v>(
+    value: &'v Value,
+    path: &str,
+) -> Result<Option<&'v Value>, ServiceError> {
+    if path.starts_with('/') {
+        return Ok(value.pointer(path));
+    }
+    Ok(select(value, &parse_path(path)?))
+}
+
+/// 将选中的值转为字符串，兼容版本号被写成数字的情况
+pub(crate) fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn extract_version(
+    body: &Value,
+    spec: &CustomJsonSpec<'_>,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let version = select_path(body, spec.version_path)?
+        .and_then(value_to_string)
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!("No version found at '{}'", spec.version_path))
+        })?;
+
+    let published_at = match spec.date_path {
+        Some(date_path) => select_path(body, date_path)?
+            .and_then(Value::as_str)
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc)),
+        None => None,
+    };
+
+    Ok((version, published_at))
+}
+
+/// 从自定义 JSON 接口获取最新版本
+pub async fn get_latest_version(
+    http: &HttpClient,
+    spec: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let spec = parse_spec(spec)?;
+
+    let request = http.get(spec.url);
+    let response = http.send("Custom JSON", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Custom JSON", &response));
+    }
+
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse custom JSON response: {}", e)))?;
+
+    extract_version(&body, &spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+    use serde_json::json;
+
+    fn extract(body: &Value, version_path: &str, date_path: Option<&str>) -> Option<String> {
+        let spec = CustomJsonSpec {
+            url: "https://example.com",
+            version_path,
+            date_path,
+        };
+        extract_version(body, &spec)
+            .ok()
+            .map(|(version, _)| version)
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = parse_spec("https://example.com/v.json | $.data.version").unwrap();
+        assert_eq!(spec.url, "https://example.com/v.json");
+        assert_eq!(spec.version_path, "$.data.version");
+        assert_eq!(spec.date_path, None);
+
+        let spec = parse_spec("https://example.com/v.json|$.version|$.date").unwrap();
+        assert_eq!(spec.date_path, Some("$.date"));
+
+        assert!(parse_spec("https://example.com/v.json").is_err());
+        assert!(parse_spec("https://example.com/v.json|").is_err());
+        assert!(parse_spec("a|b|c|d").is_err());
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("$.releases[2].tag").unwrap(),
+            vec![
+                PathSegment::Key("releases".to_string()),
+                PathSegment::Index(2),
+                PathSegment::Key("tag".to_string()),
+            ]
+        );
+        assert_eq!(parse_path("$").unwrap(), vec![]);
+        assert!(parse_path("$..version").is_err());
+        assert!(parse_path("$.items[first]").is_err());
+    }
+
+    #[test]
+    fn test_nested_path() {
+        let body = json!({"data": {"app": {"version": "3.2.1"}}});
+        assert_eq!(
+            extract(&body, "$.data.app.version", None),
+            Some("3.2.1".to_string())
+        );
+        assert_eq!(extract(&body, "$.data.missing", None), None);
+    }
+
+    #[test]
+    fn test_array_index() {
+        let body = json!({
+            "releases": [
+                {"version": "2.0.0", "channels": ["stable", "beta"]},
+                {"version": "1.9.0"}
+            ]
+        });
+        assert_eq!(
+            extract(&body, "$.releases[0].version", None),
+            Some("2.0.0".to_string())
+        );
+        assert_eq!(
+            extract(&body, "$.releases[1].version", None),
+            Some("1.9.0".to_string())
+        );
+        assert_eq!(
+            extract(&body, "$.releases[0].channels[1]", None),
+            Some("beta".to_string())
+        );
+        assert_eq!(extract(&body, "$.releases[5].version", None), None);
+
+        let root_array = json!([{"v": 7}]);
+        assert_eq!(extract(&root_array, "$[0].v", None), Some("7".to_string()));
+    }
+
+    #[test]
+    fn test_rfc6901_pointer() {
+        let body = json!({"data": {"items": [{"version": "1.0.4"}]}});
+        assert_eq!(
+            extract(&body, "/data/items/0/version", None),
+            Some("1.0.4".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version_with_date() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"data": {"version": "5.0.0", "released": "2024-02-10T09:30:00Z"}}"#,
+            )
+        })
+        .await;
+        let spec = format!("{}/v.json|$.data.version|$.data.released", server.url());
+
+        let (version, published_at) =
+            get_latest_version(&HttpClient::new(&NetworkConfig::default()).unwrap(), &spec)
+                .await
+                .unwrap();
+
+        assert_eq!(version, "5.0.0");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-02-10T09:30:00+00:00".to_string())
+        );
+        assert_eq!(server.requests()[0].path, "/v.json");
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use semver::Version;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::parse_version;
+
+#[derive(Deserialize)]
+struct DockerTag {
+    name: String,
+    last_updated: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DockerTagsResponse {
+    results: Vec<DockerTag>,
+}
+
+/// 将镜像名转换为 Docker Hub 的 namespace/repo 形式
+///
+/// 官方镜像（如 `nginx`）位于 `library` 命名空间下
+pub(crate) fn normalize_image(image: &str) -> String {
+    let image = image.trim().trim_start_matches("docker.io/");
+    // 去掉可能携带的 tag 或 digest
+    let image = image.split(['@', ':']).next().unwrap_or(image);
+
+    if image.contains('/') {
+        image.to_string()
+    } else {
+        format!("library/{}", image)
+    }
+}
+
+fn version_tag_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"^v?\d+\.\d+(?:\.\d+)?(?:-(?:alpha|beta|rc)\.?\d*)?$").expect("valid regex")
+    })
+}
+
+/// 判断 tag 是否为版本号形式
+///
+/// 排除 `latest` 等浮动 tag、`sha256-...` 之类的 digest tag 以及 `1.25-alpine` 等变体，
+/// 保留 `2.0.0-rc1` 这类预发布 tag
+fn parse_version_tag(name: &str) -> Option<Version> {
+    if !version_tag_regex().is_match(name) {
+        return None;
+    }
+
+    parse_version(name).into_semver()
+}
+
+/// 从匹配 `filter` 的 tag 中选出版本号最大的 tag，默认只取正式版本，限定了发布渠道时按渠道选取
+fn select_latest_tag(tags: Vec<DockerTag>, filter: &VersionFilter) -> Option<DockerTag> {
+    tags.into_iter()
+        .filter(|tag| filter.matches(&tag.name))
+        .filter_map(|tag| parse_version_tag(&tag.name).map(|v| (v, tag)))
+        .filter(|(v, _)| {
+            let prerelease = !v.pre.is_empty();
+            if filter.has_channel() {
+                filter.matches_channel(prerelease)
+            } else {
+                !prerelease
+            }
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag)
+}
+
+/// 获取 Docker Hub 镜像的最新版本 tag
+pub async fn get_latest_version(
+    http: &HttpClient,
+    image: &str,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let url = format!(
+        "https://hub.docker.com/v2/repositories/{}/tags?page_size=100&ordering=last_updated",
+        normalize_image(image)
+    );
+
+    let request = http.get(&url);
+    let response = http.send("Docker Hub", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Docker Hub", &response));
+    }
+
+    let tags_response: DockerTagsResponse = response
+        .json()
+        .await
+        .map_err(|e| {
+            ServiceError::Parse(format!("Failed to parse Docker Hub response: {}", e))
+        })?;
+
+    let latest = select_latest_tag(tags_response.results, filter).ok_or_else(|| {
+        let message = if filter.is_empty() {
+            "No version tags found"
+        } else {
+            "No version tags match the version filter"
+        };
+        ServiceError::NotFound(message.to_string())
+    })?;
+
+    let last_updated = latest
+        .last_updated
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    Ok((latest.name, last_updated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ReleaseChannel, SourceConfig, SourceType};
+
+    fn tag(name: &str) -> DockerTag {
+        DockerTag {
+            name: name.to_string(),
+            last_updated: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_official_image() {
+        assert_eq!(normalize_image("nginx"), "library/nginx");
+        assert_eq!(normalize_image("docker.io/nginx:latest"), "library/nginx");
+        assert_eq!(normalize_image("bitnami/redis"), "bitnami/redis");
+    }
+
+    #[test]
+    fn test_select_latest_skips_non_version_tags() {
+        let tags = vec![
+            tag("latest"),
+            tag("mainline"),
+            tag("1.27-alpine"),
+            tag("sha256-0a1b2c3d4e5f.sig"),
+            tag("1.25.3"),
+            tag("1.27.0"),
+            tag("1.9.15"),
+        ];
+
+        let latest = select_latest_tag(tags, &VersionFilter::default()).unwrap();
+        assert_eq!(latest.name, "1.27.0");
+    }
+
+    #[test]
+    fn test_select_latest_without_version_tags() {
+        let tags = vec![tag("latest"), tag("stable")];
+        assert!(select_latest_tag(tags, &VersionFilter::default()).is_none());
+    }
+
+    #[test]
+    fn test_select_latest_by_channel() {
+        let tags = || vec![tag("1.27.0"), tag("1.28.0-rc1"), tag("1.28.0-alpine")];
+
+        let latest = select_latest_tag(tags(), &VersionFilter::default()).unwrap();
+        assert_eq!(latest.name, "1.27.0");
+        let stable = VersionFilter::for_channel(ReleaseChannel::Stable);
+        assert_eq!(select_latest_tag(tags(), &stable).unwrap().name, "1.27.0");
+        let prerelease = VersionFilter::for_channel(ReleaseChannel::Prerelease);
+        assert_eq!(
+            select_latest_tag(tags(), &prerelease).unwrap().name,
+            "1.28.0-rc1"
+        );
+    }
+
+    #[test]
+    fn test_select_latest_applies_regex_filter() {
+        let tags = || vec![tag("latest"), tag("1.25.3"), tag("1.27.0")];
+
+        let exclude = VersionFilter::from_source(&SourceConfig {
+            version_exclude_regex: Some(r"^1\.27\.".to_string()),
+            ..SourceConfig::new(SourceType::DockerHub, "nginx")
+        })
+        .unwrap();
+        assert_eq!(select_latest_tag(tags(), &exclude).unwrap().name, "1.25.3");
+
+        let include = VersionFilter::from_source(&SourceConfig {
+            version_filter_regex: Some(r"^1\.25\.".to_string()),
+            ..SourceConfig::new(SourceType::DockerHub, "nginx")
+        })
+        .unwrap();
+        assert_eq!(select_latest_tag(tags(), &include).unwrap().name, "1.25.3");
+
+        let exclude_all = VersionFilter::from_source(&SourceConfig {
+            version_exclude_regex: Some(r"^1\.".to_string()),
+            ..SourceConfig::new(SourceType::DockerHub, "nginx")
+        })
+        .unwrap();
+        assert!(select_latest_tag(tags(), &exclude_all).is_none());
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Local, Utc};
+use reqwest::{Response, StatusCode};
+use serde::Serialize;
+use std::fmt;
+
+/// 数据源请求错误
+///
+/// 序列化后前端可以通过 `kind` 区分不存在、限流、网络故障等情况
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "camelCase")]
+pub enum ServiceError {
+    /// 包/仓库不存在（404）或没有可用版本
+    NotFound(String),
+    /// 请求被限流，`retry_after` 为建议的重试等待秒数
+    ///
+    /// `reset_at` 为限额重置时间（GitHub 通过 `X-RateLimit-Reset` 返回）
+    #[serde(rename_all = "camelCase")]
+    RateLimited {
+        retry_after: Option<u64>,
+        reset_at: Option<DateTime<Utc>>,
+    },
+    /// 未授权（401/403），通常是 token 无效或权限不足
+    Unauthorized,
+    /// 网络错误（连接失败、超时等）
+    Network(String),
+    /// 响应解析失败
+    Parse(String),
+    /// 其他 API 错误
+    Api(String),
+}
+
+impl ServiceError {
+    /// 根据 HTTP 状态码构造错误
+    pub fn from_status(source: &str, status: StatusCode, retry_after: Option<u64>) -> Self {
+        match status {
+            StatusCode::NOT_FOUND => {
+                ServiceError::NotFound(format!("{} API error: {}", source, status))
+            }
+            StatusCode::TOO_MANY_REQUESTS => ServiceError::RateLimited {
+                retry_after,
+                reset_at: None,
+            },
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ServiceError::Unauthorized,
+            _ => ServiceError::Api(format!("{} API error: {}", source, status)),
+        }
+    }
+
+    /// 根据失败的响应构造错误，读取 `Retry-After` 头
+    pub fn from_response(source: &str, response: &Response) -> Self {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+
+        Self::from_status(source, response.status(), retry_after)
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::NotFound(message)
+            | ServiceError::Network(message)
+            | ServiceError::Parse(message)
+            | ServiceError::Api(message) => write!(f, "{}", message),
+            ServiceError::RateLimited {
+                reset_at: Some(reset_at),
+                ..
+            } => write!(
+                f,
+                "Rate limited, resets at {}",
+                reset_at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+            ),
+            ServiceError::RateLimited {
+                retry_after: Some(seconds),
+                reset_at: None,
+            } => write!(f, "Rate limited, retry after {}s", seconds),
+            ServiceError::RateLimited {
+                retry_after: None,
+                reset_at: None,
+            } => write!(f, "Rate limited"),
+            ServiceError::Unauthorized => {
+                write!(f, "Unauthorized, please check the configured token")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+impl From<ServiceError> for String {
+    fn from(error: ServiceError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_found() {
+        assert_eq!(
+            ServiceError::from_status("npm", StatusCode::NOT_FOUND, None),
+            ServiceError::NotFound("npm API error: 404 Not Found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        assert_eq!(
+            ServiceError::from_status("GitHub", StatusCode::TOO_MANY_REQUESTS, Some(60)),
+            ServiceError::RateLimited {
+                retry_after: Some(60),
+                reset_at: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unauthorized() {
+        assert_eq!(
+            ServiceError::from_status("GitHub", StatusCode::UNAUTHORIZED, None),
+            ServiceError::Unauthorized
+        );
+        assert_eq!(
+            ServiceError::from_status("GitHub", StatusCode::FORBIDDEN, None),
+            ServiceError::Unauthorized
+        );
+    }
+
+    #[test]
+    fn test_other_status() {
+        assert_eq!(
+            ServiceError::from_status("PyPI", StatusCode::INTERNAL_SERVER_ERROR, None),
+            ServiceError::Api("PyPI API error: 500 Internal Server Error".to_string())
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let json = serde_json::to_value(ServiceError::RateLimited {
+            retry_after: Some(30),
+            reset_at: None,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "rateLimited",
+                "detail": { "retryAfter": 30, "resetAt": null }
+            })
+        );
+
+        let json = serde_json::to_value(ServiceError::Unauthorized).unwrap();
+        assert_eq!(json, serde_json::json!({ "kind": "unauthorized" }));
+    }
+}
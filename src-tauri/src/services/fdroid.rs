@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FdroidPackage {
+    suggested_version_name: Option<String>,
+    suggested_version_code: Option<u64>,
+    #[serde(default)]
+    packages: Vec<FdroidBuild>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FdroidBuild {
+    version_name: String,
+    version_code: u64,
+}
+
+/// 推荐版本名：优先使用 `suggestedVersionName`，否则在构建列表中查找推荐的 versionCode，
+/// 都没有时取 versionCode 最大的构建
+fn suggested_version(package: &FdroidPackage) -> Option<String> {
+    if let Some(name) = package
+        .suggested_version_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        return Some(name.to_string());
+    }
+    package
+        .suggested_version_code
+        .and_then(|code| package.packages.iter().find(|b| b.version_code == code))
+        .or_else(|| package.packages.iter().max_by_key(|b| b.version_code))
+        .map(|build| build.version_name.clone())
+}
+
+/// 获取 F-Droid 上 Android 应用的推荐版本
+///
+/// API 不返回发布时间，时间始终为 None
+pub async fn get_latest_version(
+    http: &HttpClient,
+    package_id: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let package_id = package_id.trim();
+    let url = format!("https://f-droid.org/api/v1/packages/{}", package_id);
+
+    let request = http.get(&url);
+    let response = http.send("F-Droid", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("F-Droid", &response));
+    }
+
+    let body: FdroidPackage = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse F-Droid response: {}", e)))?;
+
+    let version = suggested_version(&body)
+        .ok_or_else(|| ServiceError::NotFound(format!("No versions found for {}", package_id)))?;
+    Ok((version, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://f-droid.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{
+                    "packageName": "org.fdroid.fdroid",
+                    "suggestedVersionName": "1.19.1",
+                    "suggestedVersionCode": 1019050,
+                    "packages": [
+                        {"versionName": "1.20.0-alpha1", "versionCode": 1020001},
+                        {"versionName": "1.19.1", "versionCode": 1019050}
+                    ]
+                }"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) =
+            get_latest_version(&test_client(&server), "org.fdroid.fdroid")
+                .await
+                .unwrap();
+
+        assert_eq!(version, "1.19.1");
+        assert_eq!(published_at, None);
+        assert_eq!(
+            server.requests()[0].path,
+            "/api/v1/packages/org.fdroid.fdroid"
+        );
+    }
+
+    #[test]
+    fn test_suggested_version_from_builds() {
+        let package: FdroidPackage = serde_json::from_str(
+            r#"{
+                "suggestedVersionCode": 1019050,
+                "packages": [
+                    {"versionName": "1.20.0-alpha1", "versionCode": 1020001},
+                    {"versionName": "1.19.1", "versionCode": 1019050}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(suggested_version(&package).as_deref(), Some("1.19.1"));
+
+        let package: FdroidPackage = serde_json::from_str(
+            r#"{"packages": [
+                {"versionName": "2.0", "versionCode": 20},
+                {"versionName": "2.1", "versionCode": 21}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(suggested_version(&package).as_deref(), Some("2.1"));
+
+        let package: FdroidPackage = serde_json::from_str(r#"{"packages": []}"#).unwrap();
+        assert_eq!(suggested_version(&package), None);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_package() {
+        let server = MockServer::start(|_| MockResponse::json(404, "{}")).await;
+
+        let err = get_latest_version(&test_client(&server), "missing.app")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
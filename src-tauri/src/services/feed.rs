@@ -0,0 +1,235 @@
+use chrono::{DateTime, Utc};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use regex::Regex;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+/// 订阅源中第一条条目的信息
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct FeedEntry {
+    pub(crate) title: String,
+    pub(crate) date: Option<String>,
+}
+
+/// 当前正在读取的条目字段
+#[derive(Clone, Copy)]
+enum EntryField {
+    Title,
+    Date,
+}
+
+/// 解析 Atom 或 RSS 文档中的第一个 `<entry>`/`<item>`
+pub(crate) fn parse_first_entry(xml: &str) -> Result<Option<FeedEntry>, ServiceError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut entry: Option<FeedEntry> = None;
+    let mut field: Option<EntryField> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| ServiceError::Parse(format!("Failed to parse feed: {}", e)))?;
+
+        match event {
+            Event::Start(tag) => match tag.local_name().as_ref() {
+                b"entry" | b"item" if entry.is_none() => entry = Some(FeedEntry::default()),
+                b"title" if entry.is_some() => field = Some(EntryField::Title),
+                // Atom 优先使用 updated，RSS 使用 pubDate
+                b"updated" | b"published" | b"pubDate" if entry.is_some() => {
+                    field = Some(EntryField::Date)
+                }
+                _ => field = None,
+            },
+            Event::Text(text) => {
+                let text = text
+                    .unescape()
+                    .map_err(|e| ServiceError::Parse(format!("Failed to parse feed: {}", e)))?;
+                fill_field(entry.as_mut(), field, &text);
+            }
+            Event::CData(data) => {
+                let text = String::from_utf8_lossy(&data.into_inner()).into_owned();
+                fill_field(entry.as_mut(), field, &text);
+            }
+            Event::End(tag) => match tag.local_name().as_ref() {
+                b"entry" | b"item" if entry.is_some() => return Ok(entry),
+                _ => field = None,
+            },
+            Event::Eof => return Ok(entry),
+            _ => {}
+        }
+    }
+}
+
+fn fill_field(entry: Option<&mut FeedEntry>, field: Option<EntryField>, text: &str) {
+    let (Some(entry), Some(field)) = (entry, field) else {
+        return;
+    };
+    match field {
+        EntryField::Title => entry.title.push_str(text),
+        // 同时存在 updated 和 published 时保留先出现的
+        EntryField::Date if entry.date.is_none() => entry.date = Some(text.trim().to_string()),
+        EntryField::Date => {}
+    }
+}
+
+/// 解析 Atom 的 RFC 3339 或 RSS 的 RFC 2822 时间
+pub(crate) fn parse_feed_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .or_else(|_| DateTime::parse_from_rfc2822(date))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// 从条目标题中提取版本号，有捕获组时取第一个捕获组
+fn extract_version(title: &str, pattern: Option<&str>) -> Result<String, ServiceError> {
+    let title = title.trim();
+    let Some(pattern) = pattern else {
+        return Ok(title.to_string());
+    };
+
+    let regex = Regex::new(pattern)
+        .map_err(|e| ServiceError::Parse(format!("Invalid version pattern: {}", e)))?;
+    let captures = regex.captures(title).ok_or_else(|| {
+        ServiceError::NotFound(format!("No version matching '{}' in '{}'", pattern, title))
+    })?;
+
+    let matched = captures.get(1).or_else(|| captures.get(0));
+    Ok(matched.map(|m| m.as_str().to_string()).unwrap_or_default())
+}
+
+/// 从 RSS/Atom 订阅源获取最新版本
+///
+/// 标识符为订阅地址，可追加 `|正则` 从标题中提取版本号，
+/// 如 `https://github.com/owner/repo/releases.atom|v?(\d+\.\d+\.\d+)`
+pub async fn get_latest_version(
+    http: &HttpClient,
+    spec: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let (url, pattern) = match spec.split_once('|') {
+        Some((url, pattern)) => (url.trim(), Some(pattern.trim()).filter(|p| !p.is_empty())),
+        None => (spec.trim(), None),
+    };
+
+    let request = http.get(url);
+    let response = http.send("Feed", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Feed", &response));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to read feed: {}", e)))?;
+
+    let entry = parse_first_entry(&body)?
+        .filter(|entry| !entry.title.trim().is_empty())
+        .ok_or_else(|| ServiceError::NotFound("No entries found in feed".to_string()))?;
+
+    let version = extract_version(&entry.title, pattern)?;
+    let published_at = entry.date.as_deref().and_then(parse_feed_date);
+
+    Ok((version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Release notes from tool</title>
+  <updated>2024-04-02T10:00:00Z</updated>
+  <entry>
+    <id>tag:github.com,2008:Repository/1/v1.8.0</id>
+    <updated>2024-04-01T08:30:00Z</updated>
+    <link rel="alternate" type="text/html" href="https://github.com/o/tool/releases/tag/v1.8.0"/>
+    <title>Release v1.8.0 &amp; friends</title>
+  </entry>
+  <entry>
+    <updated>2024-03-01T08:30:00Z</updated>
+    <title>Release v1.7.0</title>
+  </entry>
+</feed>"#;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0">
+  <channel>
+    <title>Tool announcements</title>
+    <item>
+      <title><![CDATA[Tool 3.2 released]]></title>
+      <pubDate>Tue, 14 May 2024 16:00:00 +0200</pubDate>
+    </item>
+    <item>
+      <title>Tool 3.1 released</title>
+      <pubDate>Mon, 01 Apr 2024 09:00:00 GMT</pubDate>
+    </item>
+  </channel>
+</rss>"#;
+
+    #[test]
+    fn test_parse_atom_first_entry() {
+        let entry = parse_first_entry(ATOM).unwrap().unwrap();
+        assert_eq!(entry.title, "Release v1.8.0 & friends");
+        assert_eq!(
+            parse_feed_date(entry.date.as_deref().unwrap()).map(|dt| dt.to_rfc3339()),
+            Some("2024-04-01T08:30:00+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_rss_first_item() {
+        let entry = parse_first_entry(RSS).unwrap().unwrap();
+        assert_eq!(entry.title, "Tool 3.2 released");
+        assert_eq!(
+            parse_feed_date(entry.date.as_deref().unwrap()).map(|dt| dt.to_rfc3339()),
+            Some("2024-05-14T14:00:00+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_feed() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>x</title></feed>"#;
+        assert_eq!(parse_first_entry(xml).unwrap(), None);
+    }
+
+    #[test]
+    fn test_extract_version() {
+        assert_eq!(extract_version(" v1.0.0 ", None).unwrap(), "v1.0.0");
+        assert_eq!(
+            extract_version("Release v1.8.0", Some(r"v(\d+\.\d+\.\d+)")).unwrap(),
+            "1.8.0"
+        );
+        assert_eq!(
+            extract_version("Tool 3.2 released", Some(r"\d+\.\d+")).unwrap(),
+            "3.2"
+        );
+        assert!(matches!(
+            extract_version("Weekly digest", Some(r"\d+\.\d+")),
+            Err(ServiceError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version_from_atom() {
+        let server = MockServer::start(|_| {
+            MockResponse::text(200, ATOM).with_header("Content-Type", "application/atom+xml")
+        })
+        .await;
+        let spec = format!("{}/releases.atom|v(\\d+\\.\\d+\\.\\d+)", server.url());
+
+        let (version, published_at) =
+            get_latest_version(&HttpClient::new(&NetworkConfig::default()).unwrap(), &spec)
+                .await
+                .unwrap();
+
+        assert_eq!(version, "1.8.0");
+        assert!(published_at.is_some());
+        assert_eq!(server.requests()[0].path, "/releases.atom");
+    }
+}
@@ -0,0 +1,116 @@
+use regex::Regex;
+
+use super::error::ServiceError;
+use crate::models::{ReleaseChannel, SourceConfig};
+
+/// 按数据源配置的正则和发布渠道筛选版本号，在选出最大版本之前使用
+#[derive(Debug, Default)]
+pub struct VersionFilter {
+    include: Option<Regex>,
+    exclude: Option<Regex>,
+    channel: ReleaseChannel,
+}
+
+/// 编译正则，空字符串视为未设置
+fn compile(pattern: Option<&str>, name: &str) -> Result<Option<Regex>, ServiceError> {
+    pattern
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| {
+            Regex::new(pattern)
+                .map_err(|e| ServiceError::Parse(format!("Invalid {} pattern: {}", name, e)))
+        })
+        .transpose()
+}
+
+impl VersionFilter {
+    pub fn from_source(source: &SourceConfig) -> Result<Self, ServiceError> {
+        Ok(Self {
+            include: compile(source.version_filter_regex.as_deref(), "version filter")?,
+            exclude: compile(source.version_exclude_regex.as_deref(), "version exclude")?,
+            channel: source.channel,
+        })
+    }
+
+    /// 只限定发布渠道，用于测试各数据源的渠道选择
+    #[cfg(test)]
+    pub fn for_channel(channel: ReleaseChannel) -> Self {
+        Self {
+            channel,
+            ..Self::default()
+        }
+    }
+
+    /// 没有设置任何正则，也没有限定发布渠道
+    pub fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none() && self.channel == ReleaseChannel::Any
+    }
+
+    /// 是否限定了发布渠道，限定时预发布版本的取舍只由渠道决定
+    pub fn has_channel(&self) -> bool {
+        self.channel != ReleaseChannel::Any
+    }
+
+    /// 版本是否属于限定的发布渠道，`prerelease` 由各数据源按自己的版本规则判断
+    pub fn matches_channel(&self, prerelease: bool) -> bool {
+        self.channel.allows(prerelease)
+    }
+
+    pub fn matches(&self, version: &str) -> bool {
+        let included = self.include.as_ref().is_none_or(|regex| regex.is_match(version));
+        let excluded = self.exclude.as_ref().is_some_and(|regex| regex.is_match(version));
+        included && !excluded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SourceType;
+
+    fn source(filter: Option<&str>, exclude: Option<&str>) -> SourceConfig {
+        SourceConfig {
+            version_filter_regex: filter.map(str::to_string),
+            version_exclude_regex: exclude.map(str::to_string),
+            ..SourceConfig::new(SourceType::GithubTags, "owner/repo")
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter =
+            VersionFilter::from_source(&source(Some(r"^v\d+\."), Some("nightly"))).unwrap();
+        assert!(filter.matches("v1.2.0"));
+        assert!(!filter.matches("latest"));
+        assert!(!filter.matches("v1.3.0-nightly.20240501"));
+
+        let empty = VersionFilter::from_source(&source(Some(" "), None)).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.matches("anything"));
+    }
+
+    #[test]
+    fn test_channel() {
+        let mut stable = source(None, None);
+        stable.channel = ReleaseChannel::Stable;
+        let stable = VersionFilter::from_source(&stable).unwrap();
+        assert!(!stable.is_empty());
+        assert!(stable.matches_channel(false));
+        assert!(!stable.matches_channel(true));
+
+        let prerelease = VersionFilter::for_channel(ReleaseChannel::Prerelease);
+        assert!(prerelease.has_channel());
+        assert!(prerelease.matches_channel(true));
+        assert!(!prerelease.matches_channel(false));
+
+        let any = VersionFilter::from_source(&source(None, None)).unwrap();
+        assert!(any.is_empty() && !any.has_channel());
+        assert!(any.matches_channel(true) && any.matches_channel(false));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let err = VersionFilter::from_source(&source(None, Some("("))).unwrap_err();
+        assert!(matches!(err, ServiceError::Parse(_)));
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+#[derive(Deserialize)]
+struct AppstreamRelease {
+    version: Option<String>,
+    /// Unix 时间戳（秒），接口可能返回字符串或数字
+    timestamp: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct Appstream {
+    #[serde(default)]
+    releases: Vec<AppstreamRelease>,
+}
+
+fn release_time(release: &AppstreamRelease) -> Option<DateTime<Utc>> {
+    let seconds = match release.timestamp.as_ref()? {
+        Value::String(s) => s.trim().parse().ok()?,
+        Value::Number(n) => n.as_i64()?,
+        _ => return None,
+    };
+    Utc.timestamp_opt(seconds, 0).single()
+}
+
+/// 取发布时间最新的版本；都没有时间时按 appstream 的约定取第一条
+fn select_newest(releases: Vec<AppstreamRelease>) -> Option<(String, Option<DateTime<Utc>>)> {
+    let mut newest: Option<(String, Option<DateTime<Utc>>)> = None;
+
+    for release in releases {
+        let published_at = release_time(&release);
+        let Some(version) = release.version.filter(|v| !v.is_empty()) else {
+            continue;
+        };
+
+        let is_newer = match &newest {
+            Some((_, current)) => published_at > *current,
+            None => true,
+        };
+        if is_newer {
+            newest = Some((version, published_at));
+        }
+    }
+
+    newest
+}
+
+/// 获取 Flathub 上应用的最新版本，`app_id` 如 `org.mozilla.firefox`
+pub async fn get_latest_version(
+    http: &HttpClient,
+    app_id: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let app_id = app_id.trim();
+    let url = format!("https://flathub.org/api/v2/appstream/{}", app_id);
+
+    let request = http.get(&url);
+    let response = http.send("Flathub", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Flathub", &response));
+    }
+
+    let appstream: Appstream = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Flathub response: {}", e)))?;
+
+    select_newest(appstream.releases)
+        .ok_or_else(|| ServiceError::NotFound(format!("No releases found for {}", app_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://flathub.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{
+                    "type": "desktop-application",
+                    "id": "org.mozilla.firefox",
+                    "name": "Firefox",
+                    "releases": [
+                        {"version": "126.0.1", "timestamp": "1716854400", "type": "stable"},
+                        {"version": "127.0", "timestamp": 1718064000, "type": "stable"},
+                        {"version": "126.0", "timestamp": "1715644800", "type": "stable"}
+                    ]
+                }"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) =
+            get_latest_version(&test_client(&server), "org.mozilla.firefox")
+                .await
+                .unwrap();
+
+        assert_eq!(version, "127.0");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-06-11T00:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            server.requests()[0].path,
+            "/api/v2/appstream/org.mozilla.firefox"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_app_without_releases() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, r#"{"id": "org.example.App", "releases": []}"#)
+        })
+        .await;
+
+        let err = get_latest_version(&test_client(&server), "org.example.App")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
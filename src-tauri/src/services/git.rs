@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use std::io::ErrorKind;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use crate::version::{is_prerelease, parse_version};
+
+/// 从 `git ls-remote --tags` 的输出中取出 tag 名，去掉注解 tag 的 `^{}` 后缀并去重
+fn parse_ls_remote(output: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for line in output.lines() {
+        let Some((_, reference)) = line.split_once('\t') else {
+            continue;
+        };
+        let Some(tag) = reference.trim().strip_prefix("refs/tags/") else {
+            continue;
+        };
+        let tag = tag.strip_suffix("^{}").unwrap_or(tag);
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// 取能解析为语义化版本的最大 tag，不包含预发布版本时，有正式版本就忽略预发布版本
+fn select_latest_tag<'a>(
+    tags: &'a [String],
+    include_prerelease: bool,
+    filter: &VersionFilter,
+) -> Option<&'a String> {
+    let versioned: Vec<_> = tags
+        .iter()
+        .filter(|tag| filter.matches(tag) && filter.matches_channel(is_prerelease(tag)))
+        .filter_map(|tag| Some((tag, parse_version(tag).into_semver()?)))
+        .collect();
+    let skip_prerelease =
+        !include_prerelease && versioned.iter().any(|(_, version)| version.pre.is_empty());
+
+    versioned
+        .into_iter()
+        .filter(|(_, version)| !skip_prerelease || version.pre.is_empty())
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(tag, _)| tag)
+}
+
+/// 执行 `git ls-remote --tags`，返回远程仓库的 tag 列表
+async fn list_remote_tags(url: &str, timeout: Duration) -> Result<Vec<String>, ServiceError> {
+    let mut command = Command::new("git");
+    command
+        .args(["ls-remote", "--tags", url])
+        // 需要认证的仓库直接失败，而不是等待终端输入用户名密码
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+
+    let output = match tokio::time::timeout(timeout, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) if e.kind() == ErrorKind::NotFound => {
+            return Err(ServiceError::Api(
+                "git is not installed or not in PATH, it is required for git tag sources"
+                    .to_string(),
+            ))
+        }
+        Ok(Err(e)) => return Err(ServiceError::Api(format!("Failed to run git: {}", e))),
+        Err(_) => {
+            return Err(ServiceError::Network(format!(
+                "git ls-remote timed out after {}s",
+                timeout.as_secs()
+            )))
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("").trim();
+        return Err(ServiceError::Api(format!(
+            "git ls-remote failed: {}",
+            reason
+        )));
+    }
+
+    Ok(parse_ls_remote(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 通过 `git ls-remote` 获取任意 git 仓库的最新 tag，适用于没有 API 的托管服务
+pub async fn get_latest_tag(
+    url: &str,
+    include_prerelease: bool,
+    filter: &VersionFilter,
+    timeout: Duration,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let tags = list_remote_tags(url.trim(), timeout).await?;
+
+    let latest = select_latest_tag(&tags, include_prerelease, filter).ok_or_else(|| {
+        let message = if tags.is_empty() {
+            "No tags found".to_string()
+        } else {
+            "No tags with a semantic version".to_string()
+        };
+        ServiceError::NotFound(message)
+    })?;
+
+    // ls-remote 不返回 tag 时间
+    Ok((latest.clone(), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ReleaseChannel;
+    use std::path::Path;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false"])
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .expect("git is required for this test");
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_parse_ls_remote() {
+        let output = "\
+a1\trefs/tags/v1.0.0
+b2\trefs/tags/v1.1.0
+c3\trefs/tags/v1.1.0^{}
+d4\trefs/heads/main
+";
+        assert_eq!(parse_ls_remote(output), vec!["v1.0.0", "v1.1.0"]);
+        assert!(parse_ls_remote("").is_empty());
+    }
+
+    #[test]
+    fn test_select_latest_tag() {
+        let tags: Vec<String> = ["v1.2.0", "v1.10.0", "v2.0.0-rc.1", "nightly"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let filter = VersionFilter::default();
+        assert_eq!(select_latest_tag(&tags, false, &filter).unwrap(), "v1.10.0");
+        assert_eq!(
+            select_latest_tag(&tags, true, &filter).unwrap(),
+            "v2.0.0-rc.1"
+        );
+        assert!(select_latest_tag(&["nightly".to_string()], false, &filter).is_none());
+
+        let stable = VersionFilter::for_channel(ReleaseChannel::Stable);
+        assert_eq!(select_latest_tag(&tags, true, &stable).unwrap(), "v1.10.0");
+        let prerelease = VersionFilter::for_channel(ReleaseChannel::Prerelease);
+        assert_eq!(
+            select_latest_tag(&tags, true, &prerelease).unwrap(),
+            "v2.0.0-rc.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_tag_from_bare_repo() {
+        let root = std::env::temp_dir().join(format!("git-tags-{}", uuid::Uuid::new_v4()));
+        let work = root.join("work");
+        std::fs::create_dir_all(&work).unwrap();
+        git(&work, &["init", "-q"]);
+        git(&work, &["commit", "-q", "--allow-empty", "-m", "init"]);
+        git(&work, &["tag", "v1.2.0"]);
+        git(&work, &["tag", "-a", "v1.10.0", "-m", "release"]);
+        git(&work, &["tag", "v2.0.0-beta.1"]);
+        git(&root, &["clone", "-q", "--bare", "work", "remote.git"]);
+
+        let url = root.join("remote.git").to_string_lossy().to_string();
+        let timeout = Duration::from_secs(10);
+        let filter = VersionFilter::default();
+        let (version, published_at) = get_latest_tag(&url, false, &filter, timeout).await.unwrap();
+        assert_eq!(version, "v1.10.0");
+        assert_eq!(published_at, None);
+        let (version, _) = get_latest_tag(&url, true, &filter, timeout).await.unwrap();
+        assert_eq!(version, "v2.0.0-beta.1");
+
+        let missing = root.join("missing.git").to_string_lossy().to_string();
+        assert!(matches!(
+            get_latest_tag(&missing, false, &filter, timeout).await,
+            Err(ServiceError::Api(_))
+        ));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::is_prerelease;
+
+#[derive(Deserialize)]
+struct GiteaRelease {
+    tag_name: String,
+    #[serde(default)]
+    prerelease: bool,
+    published_at: Option<String>,
+}
+
+/// 拆分 `https://gitea.example.com/owner/repo` 形式的标识，返回 (实例地址, owner/repo)
+///
+/// 实例部署在子路径下时（如 `https://example.com/git/owner/repo`）地址包含子路径
+pub(crate) fn parse_identifier(identifier: &str) -> Result<(&str, &str), ServiceError> {
+    let identifier = identifier.trim().trim_end_matches('/');
+    let invalid = || {
+        ServiceError::Api(format!(
+            "Invalid Gitea repository '{}', expected https://host/owner/repo",
+            identifier
+        ))
+    };
+
+    let host_start = identifier.find("://").ok_or_else(invalid)? + 3;
+    let repo_start = identifier.rmatch_indices('/').nth(1).ok_or_else(invalid)?.0;
+    if repo_start <= host_start {
+        return Err(invalid());
+    }
+
+    let (base_url, owner_repo) = identifier.split_at(repo_start);
+    let owner_repo = &owner_repo[1..];
+    if owner_repo.split('/').any(str::is_empty) {
+        return Err(invalid());
+    }
+    Ok((base_url, owner_repo))
+}
+
+/// 获取 Gitea/Forgejo 仓库最新的 release，限定了发布渠道时取该渠道内最新的一个
+pub async fn get_latest_release(
+    http: &HttpClient,
+    base_url: &str,
+    owner_repo: &str,
+    token: Option<&str>,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    // 按过滤条件选取时需要向后查找，否则只取最新的一个
+    let limit = if filter.is_empty() { 1 } else { 50 };
+    let url = format!(
+        "{}/api/v1/repos/{}/releases?limit={}",
+        base_url.trim_end_matches('/'),
+        owner_repo,
+        limit
+    );
+    let mut request = http.get(&url);
+    if let Some(token) = token.map(str::trim).filter(|token| !token.is_empty()) {
+        request = request.header("Authorization", format!("token {}", token));
+    }
+    let response = http.send("Gitea", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Gitea", &response));
+    }
+
+    let releases: Vec<GiteaRelease> = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Gitea response: {}", e)))?;
+
+    let latest = releases
+        .into_iter()
+        .find(|release| {
+            filter.matches(&release.tag_name)
+                && filter.matches_channel(release.prerelease || is_prerelease(&release.tag_name))
+        })
+        .ok_or_else(|| ServiceError::NotFound(format!("No releases found for {}", owner_repo)))?;
+
+    let published_at = latest
+        .published_at
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    Ok((latest.tag_name, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[test]
+    fn test_parse_identifier() {
+        assert_eq!(
+            parse_identifier("https://codeberg.org/forgejo/forgejo").unwrap(),
+            ("https://codeberg.org", "forgejo/forgejo")
+        );
+        assert_eq!(
+            parse_identifier(" https://example.com/git/team/tool/ ").unwrap(),
+            ("https://example.com/git", "team/tool")
+        );
+        assert!(parse_identifier("team/tool").is_err());
+        assert!(parse_identifier("https://codeberg.org/forgejo").is_err());
+        assert!(parse_identifier("https://codeberg.org//tool").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_release() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"[{
+                    "id": 42,
+                    "tag_name": "v1.22.1",
+                    "name": "1.22.1",
+                    "draft": false,
+                    "prerelease": false,
+                    "published_at": "2024-07-04T10:00:00Z"
+                }]"#,
+            )
+        })
+        .await;
+        let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+
+        let (version, published_at) = get_latest_release(
+            &http,
+            server.url(),
+            "gitea/tea",
+            Some("secret"),
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "v1.22.1");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-07-04T10:00:00+00:00".to_string())
+        );
+        let request = &server.requests()[0];
+        assert_eq!(request.path, "/api/v1/repos/gitea/tea/releases?limit=1");
+        assert_eq!(request.header("authorization"), Some("token secret"));
+    }
+
+    #[tokio::test]
+    async fn test_channel_searches_more_releases() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"[
+                    {"tag_name": "v1.23.0-rc1", "prerelease": true, "published_at": null},
+                    {"tag_name": "v1.22.1", "prerelease": false, "published_at": null}
+                ]"#,
+            )
+        })
+        .await;
+        let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+
+        for (channel, expected) in [
+            (ReleaseChannel::Stable, "v1.22.1"),
+            (ReleaseChannel::Prerelease, "v1.23.0-rc1"),
+        ] {
+            let (version, _) = get_latest_release(
+                &http,
+                server.url(),
+                "gitea/tea",
+                None,
+                &VersionFilter::for_channel(channel),
+            )
+            .await
+            .unwrap();
+            assert_eq!(version, expected);
+        }
+        assert_eq!(
+            server.requests()[0].path,
+            "/api/v1/repos/gitea/tea/releases?limit=50"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_releases() {
+        let server = MockServer::start(|_| MockResponse::json(200, "[]")).await;
+        let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+
+        let err = get_latest_release(
+            &http,
+            server.url(),
+            "gitea/tea",
+            None,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
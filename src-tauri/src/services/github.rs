@@ -1,11 +1,100 @@
-use chrono::{DateTime, Utc};
-use reqwest::Client;
+use chrono::{DateTime, TimeZone, Utc};
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use reqwest::{RequestBuilder, Response};
 use serde::Deserialize;
 
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use super::RemoteVersion;
+use crate::models::{GithubRateLimit, SourceConfig, VersionFrom};
+use crate::version::{is_prerelease, parse_version};
+
+/// 每页获取的 tag 数量（GitHub 允许的最大值）
+const TAGS_PER_PAGE: usize = 100;
+/// 最多翻页数，避免 tag 很多的仓库消耗过多 API 限额
+const MAX_TAG_PAGES: usize = 3;
+
 #[derive(Deserialize)]
 struct GithubRelease {
     tag_name: String,
-    published_at: String,
+    /// release 标题，未填写时为 null
+    #[serde(default)]
+    name: Option<String>,
+    /// 草稿没有发布时间
+    published_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    /// 发布说明（Markdown）
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl From<GithubRelease> for RemoteVersion {
+    fn from(release: GithubRelease) -> Self {
+        RemoteVersion {
+            published_at: release.published_at.as_deref().and_then(parse_github_date),
+            version: release.tag_name,
+            release_notes: release.body.filter(|body| !body.trim().is_empty()),
+            asset_url: None,
+        }
+    }
+}
+
+/// 从 release 中读取版本号：选择 tag 名或 release 标题，可再用正则提取
+#[derive(Debug, Default)]
+pub struct ReleaseVersion {
+    from: VersionFrom,
+    pattern: Option<Regex>,
+}
+
+impl ReleaseVersion {
+    pub fn from_source(source: &SourceConfig) -> Result<Self, ServiceError> {
+        let pattern = match source.version_extract_regex.as_deref().map(str::trim) {
+            Some(pattern) if !pattern.is_empty() => Some(Regex::new(pattern).map_err(|e| {
+                ServiceError::Parse(format!("Invalid version extract pattern: {}", e))
+            })?),
+            _ => None,
+        };
+        Ok(Self {
+            from: source.version_from,
+            pattern,
+        })
+    }
+
+    /// 字段为空或正则不匹配时返回 None，正则有捕获组时取第一个捕获组，否则取整个匹配
+    fn extract(&self, release: &GithubRelease) -> Option<String> {
+        let field = match self.from {
+            VersionFrom::TagName => release.tag_name.as_str(),
+            VersionFrom::ReleaseName => release.name.as_deref()?,
+        }
+        .trim();
+        let version = match &self.pattern {
+            Some(pattern) => {
+                let captures = pattern.captures(field)?;
+                captures.get(1).or_else(|| captures.get(0))?.as_str().trim()
+            }
+            None => field,
+        };
+        (!version.is_empty()).then(|| version.to_string())
+    }
+}
+
+fn parse_github_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
 }
 
 #[derive(Deserialize)]
@@ -34,79 +123,310 @@ struct GithubCommit {
     commit: GithubCommitDetail,
 }
 
+#[derive(Deserialize)]
+struct GithubContent {
+    name: String,
+    #[serde(rename = "type")]
+    content_type: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRateResource {
+    limit: u32,
+    remaining: u32,
+    reset: i64,
+}
+
+#[derive(Deserialize)]
+struct GithubRateResources {
+    core: GithubRateResource,
+}
+
+#[derive(Deserialize)]
+struct GithubRateLimitResponse {
+    resources: GithubRateResources,
+}
+
+/// 公共 GitHub 的 API 地址
+pub const DEFAULT_API_BASE_URL: &str = "https://api.github.com";
+
+/// 拼接 API 地址，兼容 GitHub Enterprise 的 `https://host/api/v3` 形式
+fn api_url(base_url: &str, path: &str) -> String {
+    format!("{}{}", base_url.trim_end_matches('/'), path)
+}
+
+/// 构造带认证信息的 GitHub API 请求
+fn github_request(http: &HttpClient, url: &str, token: Option<&str>) -> RequestBuilder {
+    let request = http
+        .get(url)
+        .header("Accept", "application/vnd.github.v3+json");
+
+    match token {
+        Some(token) => request.header("Authorization", format!("Bearer {}", token)),
+        None => request,
+    }
+}
+
+/// 从响应头中读取限流重置时间（仅当剩余次数为 0 时返回）
+fn rate_limit_reset(headers: &HeaderMap) -> Option<DateTime<Utc>> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let remaining: u64 = header("x-ratelimit-remaining")?.trim().parse().ok()?;
+    if remaining > 0 {
+        return None;
+    }
+
+    let reset: i64 = header("x-ratelimit-reset")?.trim().parse().ok()?;
+    Utc.timestamp_opt(reset, 0).single()
+}
+
+/// 将失败的 GitHub 响应转换为错误，识别限流的 403/429
+fn github_error(response: &Response) -> ServiceError {
+    match rate_limit_reset(response.headers()) {
+        Some(reset_at) => ServiceError::RateLimited {
+            retry_after: Some((reset_at - Utc::now()).num_seconds().max(0) as u64),
+            reset_at: Some(reset_at),
+        },
+        None => ServiceError::from_response("GitHub", response),
+    }
+}
+
 pub async fn get_latest_release(
+    http: &HttpClient,
+    base_url: &str,
     repo: &str,
     token: Option<&str>,
-) -> Result<(String, Option<DateTime<Utc>>), String> {
-    let client = Client::new();
-    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
+    version: &ReleaseVersion,
+) -> Result<RemoteVersion, ServiceError> {
+    let url = api_url(base_url, &format!("/repos/{}/releases/latest", repo));
 
-    let mut request = client
-        .get(&url)
-        .header("User-Agent", "app-version-gui")
-        .header("Accept", "application/vnd.github.v3+json");
+    let request = github_request(http, &url, token);
+    let response = http.send("GitHub", request).await?;
 
-    if let Some(token) = token {
-        request = request.header("Authorization", format!("Bearer {}", token));
+    if !response.status().is_success() {
+        return Err(github_error(&response));
     }
 
-    let response = request.send().await.map_err(|e| e.to_string())?;
+    let release: GithubRelease = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+
+    let latest = version.extract(&release).ok_or_else(|| {
+        ServiceError::NotFound(format!(
+            "No version found in the latest release of {}",
+            repo
+        ))
+    })?;
+    Ok(RemoteVersion {
+        version: latest,
+        ..release.into()
+    })
+}
+
+/// 把附件名通配符（`*`、`?`）转换为完整匹配的正则
+pub fn asset_pattern(glob: &str) -> Result<Regex, ServiceError> {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).map_err(|e| ServiceError::Parse(format!("Invalid asset pattern: {}", e)))
+}
+
+/// 跳过草稿和不匹配筛选的版本，按需排除预发布版本，选出发布时间最新的 release
+///
+/// 设置了 `required_asset` 时跳过还没有上传匹配附件的 release，并返回该附件的下载地址；
+/// 读取不到版本号的 release 同样跳过
+fn select_newest_release(
+    releases: Vec<GithubRelease>,
+    include_prerelease: bool,
+    filter: &VersionFilter,
+    version: &ReleaseVersion,
+    required_asset: Option<&Regex>,
+) -> Option<RemoteVersion> {
+    releases
+        .into_iter()
+        .filter(|release| !release.draft && (include_prerelease || !release.prerelease))
+        .filter_map(|release| {
+            let version = version.extract(&release)?;
+            let prerelease = release.prerelease || is_prerelease(&version);
+            if !filter.matches(&version) || !filter.matches_channel(prerelease) {
+                return None;
+            }
+            let asset_url = match required_asset {
+                Some(pattern) => Some(
+                    release
+                        .assets
+                        .iter()
+                        .find(|asset| pattern.is_match(&asset.name))?
+                        .browser_download_url
+                        .clone(),
+                ),
+                None => None,
+            };
+            Some(RemoteVersion {
+                version,
+                asset_url,
+                ..RemoteVersion::from(release)
+            })
+        })
+        .max_by_key(|release| release.published_at)
+}
+
+/// 从 release 列表中获取最新版本
+///
+/// `/releases/latest` 只返回最新的正式版本，只发布预发布版本的项目会得到 404，
+/// 这里改为查询最近的 release 列表自行筛选
+#[allow(clippy::too_many_arguments)]
+pub async fn get_latest_release_from_list(
+    http: &HttpClient,
+    base_url: &str,
+    repo: &str,
+    token: Option<&str>,
+    include_prerelease: bool,
+    filter: &VersionFilter,
+    version: &ReleaseVersion,
+    required_asset: Option<&Regex>,
+) -> Result<RemoteVersion, ServiceError> {
+    let url = api_url(base_url, &format!("/repos/{}/releases?per_page=30", repo));
+
+    let request = github_request(http, &url, token);
+    let response = http.send("GitHub", request).await?;
 
     if !response.status().is_success() {
-        return Err(format!("GitHub API error: {}", response.status()));
+        return Err(github_error(&response));
     }
 
-    let release: GithubRelease = response.json().await.map_err(|e| e.to_string())?;
+    let releases: Vec<GithubRelease> = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
 
-    let published_at = DateTime::parse_from_rfc3339(&release.published_at)
-        .ok()
-        .map(|dt| dt.with_timezone(&Utc));
+    select_newest_release(
+        releases,
+        include_prerelease,
+        filter,
+        version,
+        required_asset,
+    )
+    .ok_or_else(|| {
+        let message = match required_asset {
+            Some(_) => format!("No published release of {} has the required asset", repo),
+            None => format!("No published releases found for {}", repo),
+        };
+        ServiceError::NotFound(message)
+    })
+}
 
-    Ok((release.tag_name, published_at))
+/// 按版本号选出最新的 tag
+///
+/// GitHub 返回的 tag 顺序不是版本顺序，这里取能解析为语义化版本的最大值，
+/// 不包含预发布版本时，有正式版本就忽略预发布版本；都无法解析时退回第一个 tag。
+/// 只在以 `prefix` 开头的 tag 中选取，去掉前缀后再筛选和比较版本
+fn select_latest_tag<'a>(
+    tags: &'a [GithubTag],
+    include_prerelease: bool,
+    filter: &VersionFilter,
+    prefix: &str,
+) -> Option<&'a GithubTag> {
+    let tags: Vec<(&GithubTag, &str)> = tags
+        .iter()
+        .filter_map(|tag| Some((tag, tag.name.strip_prefix(prefix)?)))
+        .filter(|(_, version)| filter.matches(version))
+        .filter(|(_, version)| filter.matches_channel(is_prerelease(version)))
+        .collect();
+    let versioned: Vec<_> = tags
+        .iter()
+        .filter_map(|&(tag, version)| Some((tag, parse_version(version).into_semver()?)))
+        .collect();
+    let skip_prerelease =
+        !include_prerelease && versioned.iter().any(|(_, version)| version.pre.is_empty());
+
+    versioned
+        .into_iter()
+        .filter(|(_, version)| !skip_prerelease || version.pre.is_empty())
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(tag, _)| tag)
+        .or_else(|| tags.first().map(|(tag, _)| *tag))
 }
 
-pub async fn get_latest_tag(
+/// 分页获取仓库的 tag 列表
+async fn list_tags(
+    http: &HttpClient,
+    base_url: &str,
     repo: &str,
     token: Option<&str>,
-) -> Result<(String, Option<DateTime<Utc>>), String> {
-    let client = Client::new();
-    let url = format!("https://api.github.com/repos/{}/tags", repo);
+) -> Result<Vec<GithubTag>, ServiceError> {
+    let mut tags = Vec::new();
 
-    let mut request = client
-        .get(&url)
-        .header("User-Agent", "app-version-gui")
-        .header("Accept", "application/vnd.github.v3+json");
+    for page in 1..=MAX_TAG_PAGES {
+        let url = api_url(
+            base_url,
+            &format!(
+                "/repos/{}/tags?per_page={}&page={}",
+                repo, TAGS_PER_PAGE, page
+            ),
+        );
 
-    if let Some(token) = token {
-        request = request.header("Authorization", format!("Bearer {}", token));
-    }
+        let request = github_request(http, &url, token);
+        let response = http.send("GitHub", request).await?;
 
-    let response = request.send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(github_error(&response));
+        }
 
-    if !response.status().is_success() {
-        return Err(format!("GitHub API error: {}", response.status()));
+        let page_tags: Vec<GithubTag> = response
+            .json()
+            .await
+            .map_err(|e| ServiceError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+        let is_last_page = page_tags.len() < TAGS_PER_PAGE;
+        tags.extend(page_tags);
+
+        if is_last_page {
+            break;
+        }
     }
 
-    let tags: Vec<GithubTag> = response.json().await.map_err(|e| e.to_string())?;
+    Ok(tags)
+}
+
+pub async fn get_latest_tag(
+    http: &HttpClient,
+    base_url: &str,
+    repo: &str,
+    token: Option<&str>,
+    include_prerelease: bool,
+    filter: &VersionFilter,
+    tag_prefix: Option<&str>,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let tags = list_tags(http, base_url, repo, token).await?;
+    let prefix = tag_prefix.unwrap_or("");
 
-    let latest = tags.first().ok_or("No tags found")?;
+    let latest = select_latest_tag(&tags, include_prerelease, filter, prefix).ok_or_else(|| {
+        let message = if tags.is_empty() {
+            "No tags found".to_string()
+        } else if !tags.iter().any(|tag| tag.name.starts_with(prefix)) {
+            format!("No tags start with {}", prefix)
+        } else {
+            "No tags match the version filter".to_string()
+        };
+        ServiceError::NotFound(message)
+    })?;
 
     // 获取 commit 信息来得到 tag 创建时间
-    let commit_url = format!(
-        "https://api.github.com/repos/{}/commits/{}",
-        repo, latest.commit.sha
+    let commit_url = api_url(
+        base_url,
+        &format!("/repos/{}/commits/{}", repo, latest.commit.sha),
     );
 
-    let mut commit_request = client
-        .get(&commit_url)
-        .header("User-Agent", "app-version-gui")
-        .header("Accept", "application/vnd.github.v3+json");
-
-    if let Some(token) = token {
-        commit_request = commit_request.header("Authorization", format!("Bearer {}", token));
-    }
+    let commit_request = github_request(http, &commit_url, token);
 
-    let created_at = match commit_request.send().await {
+    let created_at = match http.send("GitHub", commit_request).await {
         Ok(response) if response.status().is_success() => {
             match response.json::<GithubCommit>().await {
                 Ok(commit) => DateTime::parse_from_rfc3339(&commit.commit.author.date)
@@ -118,5 +438,947 @@ pub async fn get_latest_tag(
         _ => None,
     };
 
-    Ok((latest.name.clone(), created_at))
+    let version = latest.name.strip_prefix(prefix).unwrap_or(&latest.name);
+    Ok((version.to_string(), created_at))
+}
+
+/// 查询仓库中某个文件最后一次提交的时间，失败时返回 None
+pub async fn get_file_last_commit_date(
+    http: &HttpClient,
+    base_url: &str,
+    repo: &str,
+    path: &str,
+    token: Option<&str>,
+) -> Option<DateTime<Utc>> {
+    let url = api_url(base_url, &format!("/repos/{}/commits", repo));
+    let request = github_request(http, &url, token).query(&[("path", path), ("per_page", "1")]);
+
+    let response = http.send("GitHub", request).await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+
+    let commits: Vec<GithubCommit> = response.json().await.ok()?;
+    commits
+        .first()
+        .and_then(|commit| parse_github_date(&commit.commit.author.date))
+}
+
+/// 列出仓库中某个目录下的子目录名
+pub async fn list_directories(
+    http: &HttpClient,
+    base_url: &str,
+    repo: &str,
+    path: &str,
+    token: Option<&str>,
+) -> Result<Vec<String>, ServiceError> {
+    let url = api_url(base_url, &format!("/repos/{}/contents/{}", repo, path));
+    let request = github_request(http, &url, token);
+    let response = http.send("GitHub", request).await?;
+
+    if !response.status().is_success() {
+        return Err(github_error(&response));
+    }
+
+    let contents: Vec<GithubContent> = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+
+    Ok(contents
+        .into_iter()
+        .filter(|content| content.content_type == "dir")
+        .map(|content| content.name)
+        .collect())
+}
+
+/// 查询当前 token（或匿名 IP）的 API 限额
+pub async fn get_rate_limit(
+    http: &HttpClient,
+    base_url: &str,
+    token: Option<&str>,
+) -> Result<GithubRateLimit, ServiceError> {
+    let url = api_url(base_url, "/rate_limit");
+    let request = github_request(http, &url, token);
+    let response = http.send("GitHub", request).await?;
+
+    if !response.status().is_success() {
+        return Err(github_error(&response));
+    }
+
+    let rate_limit: GithubRateLimitResponse = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+    let core = rate_limit.resources.core;
+
+    Ok(GithubRateLimit {
+        limit: core.limit,
+        remaining: core.remaining,
+        reset_at: Utc.timestamp_opt(core.reset, 0).single(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client() -> HttpClient {
+        HttpClient::new(&NetworkConfig::default()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_release() {
+        let reset = Utc::now().timestamp() + 600;
+        let server = MockServer::start(move |_| {
+            MockResponse::json(403, r#"{"message": "API rate limit exceeded"}"#)
+                .with_header("X-RateLimit-Remaining", "0")
+                .with_header("X-RateLimit-Reset", &reset.to_string())
+        })
+        .await;
+
+        let err = get_latest_release(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            &ReleaseVersion::default(),
+        )
+        .await
+        .unwrap_err();
+
+        match err {
+            ServiceError::RateLimited {
+                retry_after,
+                reset_at,
+            } => {
+                assert_eq!(reset_at.map(|dt| dt.timestamp()), Some(reset));
+                assert!(retry_after.unwrap() <= 600);
+            }
+            other => panic!("Expected RateLimited, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_without_rate_limit() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(403, r#"{"message": "Forbidden"}"#)
+                .with_header("X-RateLimit-Remaining", "42")
+        })
+        .await;
+
+        let err = get_latest_release(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            &ReleaseVersion::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err, ServiceError::Unauthorized);
+    }
+
+    const RELEASES: &str = r#"[
+        {"tag_name": "v3.0.0", "published_at": null, "draft": true, "prerelease": false},
+        {"tag_name": "v2.1.0-rc.1", "published_at": "2024-06-01T00:00:00Z", "draft": false, "prerelease": true},
+        {"tag_name": "v2.0.1", "published_at": "2024-05-01T00:00:00Z", "draft": false, "prerelease": false},
+        {"tag_name": "v1.9.9", "published_at": "2024-05-15T00:00:00Z", "draft": false, "prerelease": false}
+    ]"#;
+
+    #[tokio::test]
+    async fn test_release_list_skips_drafts_and_prereleases() {
+        let server = MockServer::start(|_| MockResponse::json(200, RELEASES)).await;
+
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            &ReleaseVersion::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        // 按发布时间而不是列表顺序选取
+        assert_eq!(release.version, "v1.9.9");
+        assert_eq!(
+            release.published_at.map(|dt| dt.to_rfc3339()).as_deref(),
+            Some("2024-05-15T00:00:00+00:00")
+        );
+        assert_eq!(
+            server.requests()[0].path,
+            "/repos/owner/repo/releases?per_page=30"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_release_list_includes_prereleases() {
+        let server = MockServer::start(|_| MockResponse::json(200, RELEASES)).await;
+
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            true,
+            &VersionFilter::default(),
+            &ReleaseVersion::default(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version, "v2.1.0-rc.1");
+    }
+
+    #[tokio::test]
+    async fn test_release_list_only_drafts() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"[{"tag_name": "v1.0.0", "published_at": null, "draft": true, "prerelease": false}]"#,
+            )
+        })
+        .await;
+
+        let err = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            true,
+            &VersionFilter::default(),
+            &ReleaseVersion::default(),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+
+    const RELEASES_WITH_ASSETS: &str = r#"[
+        {"tag_name": "v1.3.0", "published_at": "2024-06-02T00:00:00Z", "assets": [
+            {"name": "tool-darwin-arm64.tar.gz", "browser_download_url": "https://example.com/v1.3.0/darwin"}
+        ]},
+        {"tag_name": "v1.2.0", "published_at": "2024-05-02T00:00:00Z", "assets": [
+            {"name": "tool-darwin-arm64.tar.gz", "browser_download_url": "https://example.com/v1.2.0/darwin"},
+            {"name": "tool-linux-x86_64.tar.gz", "browser_download_url": "https://example.com/v1.2.0/linux"}
+        ]}
+    ]"#;
+
+    #[test]
+    fn test_asset_pattern() {
+        let pattern = asset_pattern("*-linux-x86_64.tar.gz").unwrap();
+        assert!(pattern.is_match("tool-linux-x86_64.tar.gz"));
+        assert!(!pattern.is_match("tool-linux-x86_64.tar.gz.sha256"));
+        assert!(!pattern.is_match("tool-linux-x86_64-tar-gz"));
+        assert!(asset_pattern("tool-v?.zip")
+            .unwrap()
+            .is_match("tool-v2.zip"));
+    }
+
+    #[tokio::test]
+    async fn test_release_list_requires_asset() {
+        let server = MockServer::start(|_| MockResponse::json(200, RELEASES_WITH_ASSETS)).await;
+        let linux = asset_pattern("*-linux-x86_64.tar.gz").unwrap();
+
+        // 最新的 release 还没有上传 Linux 附件
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            &ReleaseVersion::default(),
+            Some(&linux),
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version, "v1.2.0");
+        assert_eq!(
+            release.asset_url.as_deref(),
+            Some("https://example.com/v1.2.0/linux")
+        );
+
+        let darwin = asset_pattern("*-darwin-arm64.tar.gz").unwrap();
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            &ReleaseVersion::default(),
+            Some(&darwin),
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version, "v1.3.0");
+        assert_eq!(
+            release.asset_url.as_deref(),
+            Some("https://example.com/v1.3.0/darwin")
+        );
+
+        let windows = asset_pattern("*.msi").unwrap();
+        let err = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            &ReleaseVersion::default(),
+            Some(&windows),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_release_list_without_asset_requirement() {
+        let server = MockServer::start(|_| MockResponse::json(200, RELEASES_WITH_ASSETS)).await;
+
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            &ReleaseVersion::default(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version, "v1.3.0");
+        assert_eq!(release.asset_url, None);
+    }
+
+    #[tokio::test]
+    async fn test_latest_release_captures_body() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"tag_name": "v1.4.0", "published_at": "2024-05-01T00:00:00Z", "body": "Fixes:\n- Crash on start"}"#,
+            )
+        })
+        .await;
+
+        let release = get_latest_release(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            &ReleaseVersion::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release.version, "v1.4.0");
+        assert_eq!(
+            release.release_notes.as_deref(),
+            Some("Fixes:\n- Crash on start")
+        );
+        assert_eq!(
+            server.requests()[0].path,
+            "/repos/owner/repo/releases/latest"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_rate_limit() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"resources": {"core": {"limit": 60, "remaining": 12, "reset": 1700000000, "used": 48}}}"#,
+            )
+        })
+        .await;
+
+        let rate_limit = get_rate_limit(&test_client(), server.url(), Some("token"))
+            .await
+            .unwrap();
+
+        assert_eq!(rate_limit.limit, 60);
+        assert_eq!(rate_limit.remaining, 12);
+        assert_eq!(
+            rate_limit.reset_at.map(|dt| dt.timestamp()),
+            Some(1700000000)
+        );
+        assert_eq!(
+            server.requests()[0].header("authorization"),
+            Some("Bearer token")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enterprise_base_url() {
+        let server = MockServer::start(|request| {
+            if request.path.contains("/tags") {
+                MockResponse::json(200, r#"[{"name": "v2.1.0", "commit": {"sha": "abc123"}}]"#)
+            } else {
+                MockResponse::json(
+                    200,
+                    r#"{"commit": {"author": {"date": "2024-03-01T08:00:00Z"}}}"#,
+                )
+            }
+        })
+        .await;
+        let base_url = format!("{}/api/v3/", server.url());
+
+        let (version, created_at) = get_latest_tag(
+            &test_client(),
+            &base_url,
+            "corp/tool",
+            Some("ghe-token"),
+            false,
+            &VersionFilter::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "v2.1.0");
+        assert!(created_at.is_some());
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].path,
+            "/api/v3/repos/corp/tool/tags?per_page=100&page=1"
+        );
+        assert_eq!(requests[1].path, "/api/v3/repos/corp/tool/commits/abc123");
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("Bearer ghe-token")
+        );
+    }
+
+    fn tag(name: &str, sha: &str) -> GithubTag {
+        GithubTag {
+            name: name.to_string(),
+            commit: GithubTagCommit {
+                sha: sha.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_select_latest_tag_prefers_stable() {
+        let none = VersionFilter::default();
+        let tags = vec![
+            tag("v3.0.0-beta.1", "a"),
+            tag("v2.9.1", "b"),
+            tag("nightly", "c"),
+        ];
+        assert_eq!(
+            select_latest_tag(&tags, false, &none, "").unwrap().name,
+            "v2.9.1"
+        );
+        assert_eq!(
+            select_latest_tag(&tags, true, &none, "").unwrap().name,
+            "v3.0.0-beta.1"
+        );
+
+        let only_pre = vec![tag("v1.0.0-rc.1", "a"), tag("v1.0.0-rc.2", "b")];
+        assert_eq!(
+            select_latest_tag(&only_pre, false, &none, "").unwrap().name,
+            "v1.0.0-rc.2"
+        );
+
+        let unparsable = vec![tag("nightly", "a"), tag("stable", "b")];
+        assert_eq!(
+            select_latest_tag(&unparsable, false, &none, "")
+                .unwrap()
+                .name,
+            "nightly"
+        );
+        assert!(select_latest_tag(&[], false, &none, "").is_none());
+    }
+
+    fn filter(include: Option<&str>, exclude: Option<&str>) -> VersionFilter {
+        VersionFilter::from_source(&crate::models::SourceConfig {
+            include_prerelease: true,
+            version_filter_regex: include.map(str::to_string),
+            version_exclude_regex: exclude.map(str::to_string),
+            ..crate::models::SourceConfig::new(crate::models::SourceType::GithubTags, "owner/repo")
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_select_latest_tag_skips_nightly() {
+        let tags = vec![
+            tag("nightly", "a"),
+            tag("v2.0.0-nightly.20240601", "b"),
+            tag("v1.4.0", "c"),
+            tag("latest", "d"),
+        ];
+
+        let exclude = filter(None, Some("nightly"));
+        assert_eq!(
+            select_latest_tag(&tags, true, &exclude, "").unwrap().name,
+            "v1.4.0"
+        );
+
+        let include = filter(Some(r"^v\d+\.\d+\.\d+$"), None);
+        assert_eq!(
+            select_latest_tag(&tags, true, &include, "").unwrap().name,
+            "v1.4.0"
+        );
+
+        // 全部被筛掉时不退回第一个 tag
+        let unparsable = vec![tag("nightly", "a"), tag("latest", "b")];
+        assert!(select_latest_tag(&unparsable, false, &include, "").is_none());
+    }
+
+    #[test]
+    fn test_select_latest_tag_with_prefix() {
+        let tags = vec![
+            tag("package-b/v2.1.0-rc.1", "a"),
+            tag("package-a/v1.2.3", "b"),
+            tag("package-b/v2.0.0", "c"),
+            tag("package-a/v1.10.0", "d"),
+            tag("v9.0.0", "e"),
+        ];
+        let none = VersionFilter::default();
+
+        assert_eq!(
+            select_latest_tag(&tags, false, &none, "package-a/")
+                .unwrap()
+                .name,
+            "package-a/v1.10.0"
+        );
+        assert_eq!(
+            select_latest_tag(&tags, false, &none, "package-b/")
+                .unwrap()
+                .name,
+            "package-b/v2.0.0"
+        );
+        assert_eq!(
+            select_latest_tag(&tags, true, &none, "package-b/")
+                .unwrap()
+                .name,
+            "package-b/v2.1.0-rc.1"
+        );
+        // 筛选正则作用于去掉前缀后的版本号
+        let exclude = filter(Some(r"^v1\.2\."), None);
+        assert_eq!(
+            select_latest_tag(&tags, false, &exclude, "package-a/")
+                .unwrap()
+                .name,
+            "package-a/v1.2.3"
+        );
+        assert!(select_latest_tag(&tags, false, &none, "package-c/").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_latest_tag_strips_prefix() {
+        let server = MockServer::start(|request| {
+            if request.path.contains("/tags") {
+                MockResponse::json(
+                    200,
+                    r#"[
+                        {"name": "package-b/v2.0.0", "commit": {"sha": "b"}},
+                        {"name": "package-a/v1.2.3", "commit": {"sha": "a"}}
+                    ]"#,
+                )
+            } else {
+                MockResponse::json(
+                    200,
+                    r#"{"commit": {"author": {"date": "2024-03-01T08:00:00Z"}}}"#,
+                )
+            }
+        })
+        .await;
+
+        let (version, _) = get_latest_tag(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            Some("package-a/"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(version, "v1.2.3");
+        assert_eq!(server.requests()[1].path, "/repos/owner/repo/commits/a");
+
+        let err = get_latest_tag(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            Some("package-c/"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ServiceError::NotFound("No tags start with package-c/".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_tag_filter_matches_nothing() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, r#"[{"name": "nightly", "commit": {"sha": "a"}}]"#)
+        })
+        .await;
+
+        let exclude = filter(None, Some("nightly"));
+        let err = get_latest_tag(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &exclude,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ServiceError::NotFound("No tags match the version filter".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_release_list_applies_filter() {
+        let server = MockServer::start(|_| MockResponse::json(200, RELEASES)).await;
+
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            true,
+            &filter(None, Some("-rc")),
+            &ReleaseVersion::default(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version, "v1.9.9");
+    }
+
+    #[tokio::test]
+    async fn test_release_list_channel() {
+        let server = MockServer::start(|_| MockResponse::json(200, RELEASES)).await;
+
+        for (channel, expected) in [
+            (ReleaseChannel::Stable, "v1.9.9"),
+            (ReleaseChannel::Prerelease, "v2.1.0-rc.1"),
+        ] {
+            let release = get_latest_release_from_list(
+                &test_client(),
+                server.url(),
+                "owner/repo",
+                None,
+                true,
+                &VersionFilter::for_channel(channel),
+                &ReleaseVersion::default(),
+                None,
+            )
+            .await
+            .unwrap();
+            assert_eq!(release.version, expected);
+        }
+    }
+
+    #[test]
+    fn test_select_latest_tag_channel() {
+        let tags = vec![
+            tag("v3.0.0-beta.1", "a"),
+            tag("v2.9.1", "b"),
+            tag("nightly", "c"),
+        ];
+        let stable = VersionFilter::for_channel(ReleaseChannel::Stable);
+        let prerelease = VersionFilter::for_channel(ReleaseChannel::Prerelease);
+        assert_eq!(
+            select_latest_tag(&tags, true, &stable, "").unwrap().name,
+            "v2.9.1"
+        );
+        assert_eq!(
+            select_latest_tag(&tags, true, &prerelease, "")
+                .unwrap()
+                .name,
+            "v3.0.0-beta.1"
+        );
+        assert!(select_latest_tag(&[tag("v1.0.0", "a")], true, &prerelease, "").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_latest_tag_sorted_by_version() {
+        let server = MockServer::start(|request| {
+            if request.path.contains("/tags") {
+                // 旧分支上的补丁版本排在前面
+                MockResponse::json(
+                    200,
+                    r#"[
+                        {"name": "v1.9.12", "commit": {"sha": "old"}},
+                        {"name": "v2.10.0", "commit": {"sha": "new"}},
+                        {"name": "v2.9.3", "commit": {"sha": "mid"}}
+                    ]"#,
+                )
+            } else {
+                MockResponse::json(
+                    200,
+                    r#"{"commit": {"author": {"date": "2024-03-01T08:00:00Z"}}}"#,
+                )
+            }
+        })
+        .await;
+
+        let (version, _) = get_latest_tag(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "v2.10.0");
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].path, "/repos/owner/repo/commits/new");
+    }
+
+    #[tokio::test]
+    async fn test_latest_tag_paginates() {
+        let server = MockServer::start(|request| {
+            if request.path.ends_with("&page=1") {
+                let tags: Vec<String> = (0..TAGS_PER_PAGE)
+                    .map(|i| format!(r#"{{"name": "v1.0.{}", "commit": {{"sha": "p1"}}}}"#, i))
+                    .collect();
+                MockResponse::json(200, format!("[{}]", tags.join(",")))
+            } else if request.path.ends_with("&page=2") {
+                MockResponse::json(200, r#"[{"name": "v1.1.0", "commit": {"sha": "p2"}}]"#)
+            } else {
+                MockResponse::json(404, "{}")
+            }
+        })
+        .await;
+
+        let (version, created_at) = get_latest_tag(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "v1.1.0");
+        assert_eq!(created_at, None);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[1].path,
+            "/repos/owner/repo/tags?per_page=100&page=2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_tag_stops_at_page_limit() {
+        let server = MockServer::start(|request| {
+            if request.path.contains("/tags") {
+                let page = request.path.rsplit('=').next().unwrap().to_string();
+                let tags: Vec<String> = (0..TAGS_PER_PAGE)
+                    .map(|i| {
+                        format!(
+                            r#"{{"name": "v{}.0.{}", "commit": {{"sha": "p{}"}}}}"#,
+                            page, i, page
+                        )
+                    })
+                    .collect();
+                MockResponse::json(200, format!("[{}]", tags.join(",")))
+            } else {
+                MockResponse::json(404, "{}")
+            }
+        })
+        .await;
+
+        let (version, _) = get_latest_tag(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        // 每页都是满的，只取前 MAX_TAG_PAGES 页
+        assert_eq!(
+            version,
+            format!("v{}.0.{}", MAX_TAG_PAGES, TAGS_PER_PAGE - 1)
+        );
+        let requests = server.requests();
+        assert_eq!(requests.len(), MAX_TAG_PAGES + 1);
+        assert_eq!(
+            requests[MAX_TAG_PAGES].path,
+            format!("/repos/owner/repo/commits/p{}", MAX_TAG_PAGES)
+        );
+    }
+
+    fn release_version(from: VersionFrom, pattern: Option<&str>) -> ReleaseVersion {
+        ReleaseVersion::from_source(&SourceConfig {
+            version_from: from,
+            version_extract_regex: pattern.map(str::to_string),
+            ..SourceConfig::new(crate::models::SourceType::GithubRelease, "owner/repo")
+        })
+        .unwrap()
+    }
+
+    fn release(tag_name: &str, name: Option<&str>) -> GithubRelease {
+        GithubRelease {
+            tag_name: tag_name.to_string(),
+            name: name.map(str::to_string),
+            published_at: None,
+            draft: false,
+            prerelease: false,
+            body: None,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_release_version_extract() {
+        let ugly_tag = release("build-20240115", Some("Release 2.3.4"));
+        let ugly_name = release("v1.8.0", Some("Nightly build #512"));
+
+        let tag = release_version(VersionFrom::TagName, None);
+        assert_eq!(tag.extract(&ugly_name).as_deref(), Some("v1.8.0"));
+        let tag = release_version(VersionFrom::TagName, Some(r"build-(\d+)"));
+        assert_eq!(tag.extract(&ugly_tag).as_deref(), Some("20240115"));
+        assert_eq!(tag.extract(&ugly_name), None);
+
+        let name = release_version(VersionFrom::ReleaseName, Some(r"Release (\S+)"));
+        assert_eq!(name.extract(&ugly_tag).as_deref(), Some("2.3.4"));
+        assert_eq!(name.extract(&ugly_name), None);
+        // 没有捕获组时取整个匹配
+        let name = release_version(VersionFrom::ReleaseName, Some(r"\d+\.\d+\.\d+"));
+        assert_eq!(name.extract(&ugly_tag).as_deref(), Some("2.3.4"));
+        // 没有标题的 release 读取不到版本号
+        let name = release_version(VersionFrom::ReleaseName, None);
+        assert_eq!(name.extract(&release("v1.0.0", None)), None);
+        assert_eq!(name.extract(&release("v1.0.0", Some(" "))), None);
+    }
+
+    #[test]
+    fn test_invalid_version_extract_pattern() {
+        let mut source = SourceConfig {
+            version_from: VersionFrom::ReleaseName,
+            version_extract_regex: Some("(".to_string()),
+            ..SourceConfig::new(crate::models::SourceType::GithubRelease, "owner/repo")
+        };
+        let err = ReleaseVersion::from_source(&source).unwrap_err();
+        assert!(matches!(err, ServiceError::Parse(_)));
+
+        source.version_extract_regex = Some("  ".to_string());
+        assert!(ReleaseVersion::from_source(&source).is_ok());
+    }
+
+    const NAMED_RELEASES: &str = r#"[
+        {"tag_name": "build-20240601", "name": "Release 2.4.0-beta", "published_at": "2024-06-01T00:00:00Z", "prerelease": true},
+        {"tag_name": "build-20240515", "name": "Release 2.3.4", "published_at": "2024-05-15T00:00:00Z"},
+        {"tag_name": "build-20240520", "name": null, "published_at": "2024-05-20T00:00:00Z"},
+        {"tag_name": "build-20240501", "name": "Release 2.3.3", "published_at": "2024-05-01T00:00:00Z"}
+    ]"#;
+
+    #[tokio::test]
+    async fn test_release_list_version_from_release_name() {
+        let server = MockServer::start(|_| MockResponse::json(200, NAMED_RELEASES)).await;
+        let version = release_version(VersionFrom::ReleaseName, Some(r"Release (\S+)"));
+
+        // 没有标题的 release 被跳过
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            &version,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version, "2.3.4");
+
+        // 筛选作用于提取出的版本号
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            true,
+            &filter(None, Some("-beta")),
+            &version,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version, "2.3.4");
+
+        let tag = release_version(VersionFrom::TagName, Some(r"build-(\d+)"));
+        let release = get_latest_release_from_list(
+            &test_client(),
+            server.url(),
+            "owner/repo",
+            None,
+            false,
+            &VersionFilter::default(),
+            &tag,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version, "20240520");
+    }
+
+    #[tokio::test]
+    async fn test_latest_release_version_from_release_name() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"tag_name": "build-20240115", "name": "Release 2.3.4", "published_at": "2024-01-15T00:00:00Z"}"#,
+            )
+        })
+        .await;
+
+        let version = release_version(VersionFrom::ReleaseName, Some(r"Release (\S+)"));
+        let release =
+            get_latest_release(&test_client(), server.url(), "owner/repo", None, &version)
+                .await
+                .unwrap();
+        assert_eq!(release.version, "2.3.4");
+
+        let version = release_version(VersionFrom::ReleaseName, Some(r"^v(\d+)"));
+        let err = get_latest_release(&test_client(), server.url(), "owner/repo", None, &version)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
 }
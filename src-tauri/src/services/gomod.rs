@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ModuleInfo {
+    version: String,
+    time: Option<String>,
+}
+
+/// 按 Go module proxy 协议转义模块路径：大写字母转换为 `!` 加小写字母
+fn escape_module_path(module_path: &str) -> String {
+    let mut escaped = String::with_capacity(module_path.len());
+    for c in module_path.trim().chars() {
+        if c.is_ascii_uppercase() {
+            escaped.push('!');
+            escaped.push(c.to_ascii_lowercase());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// 获取 Go 模块的最新版本
+pub async fn get_latest_version(
+    http: &HttpClient,
+    module_path: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let url = format!(
+        "https://proxy.golang.org/{}/@latest",
+        escape_module_path(module_path)
+    );
+
+    let request = http.get(&url);
+    let response = http.send("Go proxy", request).await?;
+
+    // proxy 对不存在的模块返回 404 或 410
+    if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::GONE) {
+        return Err(ServiceError::NotFound(format!(
+            "Module '{}' not found on Go proxy",
+            module_path
+        )));
+    }
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Go proxy", &response));
+    }
+
+    let info: ModuleInfo = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Go proxy response: {}", e)))?;
+
+    let published_at = info
+        .time
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    Ok((info.version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[test]
+    fn test_escape_mixed_case_module() {
+        assert_eq!(
+            escape_module_path("github.com/BurntSushi/toml"),
+            "github.com/!burnt!sushi/toml"
+        );
+        assert_eq!(
+            escape_module_path("github.com/Azure/azure-sdk-for-go"),
+            "github.com/!azure/azure-sdk-for-go"
+        );
+        assert_eq!(
+            escape_module_path("golang.org/x/tools"),
+            "golang.org/x/tools"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"Version": "v1.4.0", "Time": "2024-05-01T12:00:00Z"}"#,
+            )
+        })
+        .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://proxy.golang.org", server.url());
+
+        let (version, published_at) = get_latest_version(&http, "github.com/BurntSushi/toml")
+            .await
+            .unwrap();
+
+        assert_eq!(version, "v1.4.0");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-05-01T12:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            server.requests()[0].path,
+            "/github.com/!burnt!sushi/toml/@latest"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_module() {
+        let server = MockServer::start(|_| MockResponse::text(410, "not found")).await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://proxy.golang.org", server.url());
+
+        let err = get_latest_version(&http, "example.com/missing")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
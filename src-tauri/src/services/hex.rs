@@ -0,0 +1,186 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::{is_prerelease, parse_version};
+
+#[derive(Deserialize)]
+struct HexRelease {
+    version: String,
+    inserted_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HexPackage {
+    latest_version: Option<String>,
+    latest_stable_version: Option<String>,
+    #[serde(default)]
+    releases: Vec<HexRelease>,
+}
+
+/// 从发布列表中选出匹配 `filter` 的最大版本，未限定发布渠道时按 `include_prerelease` 取舍预发布版本
+fn select_filtered(
+    releases: &[HexRelease],
+    include_prerelease: bool,
+    filter: &VersionFilter,
+) -> Option<String> {
+    releases
+        .iter()
+        .filter(|release| filter.matches(&release.version))
+        .filter(|release| {
+            let prerelease = is_prerelease(&release.version);
+            if filter.has_channel() {
+                filter.matches_channel(prerelease)
+            } else {
+                include_prerelease || !prerelease
+            }
+        })
+        .filter_map(|release| {
+            parse_version(&release.version)
+                .into_semver()
+                .map(|v| (v, &release.version))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, version)| version.clone())
+}
+
+/// 获取 Hex.pm 上包的最新版本，默认取最新的稳定版本
+pub async fn get_latest_version(
+    http: &HttpClient,
+    package: &str,
+    include_prerelease: bool,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let package = package.trim();
+    let url = format!("https://hex.pm/api/packages/{}", package);
+
+    let request = http.get(&url).header("Accept", "application/json");
+    let response = http.send("Hex", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Hex", &response));
+    }
+
+    let info: HexPackage = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Hex response: {}", e)))?;
+
+    // 只有预发布版本的包没有 latest_stable_version
+    let version = if !filter.is_empty() {
+        select_filtered(&info.releases, include_prerelease, filter)
+    } else if include_prerelease {
+        info.latest_version.or(info.latest_stable_version)
+    } else {
+        info.latest_stable_version.or(info.latest_version)
+    }
+    .ok_or_else(|| ServiceError::NotFound(format!("No releases found for {}", package)))?;
+
+    let published_at = info
+        .releases
+        .into_iter()
+        .find(|release| release.version == version)
+        .and_then(|release| release.inserted_at)
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    Ok((version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    const PHOENIX: &str = r#"{
+        "name": "phoenix",
+        "latest_version": "1.8.0-rc.0",
+        "latest_stable_version": "1.7.14",
+        "releases": [
+            {"version": "1.8.0-rc.0", "inserted_at": "2024-08-06T18:11:04.000000Z"},
+            {"version": "1.7.14", "inserted_at": "2024-06-18T13:24:06.000000Z"},
+            {"version": "1.7.12", "inserted_at": "2024-04-22T08:00:00.000000Z"}
+        ]
+    }"#;
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://hex.pm", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_prefers_stable_version() {
+        let server = MockServer::start(|_| MockResponse::json(200, PHOENIX)).await;
+
+        let (version, published_at) = get_latest_version(
+            &test_client(&server),
+            "phoenix",
+            false,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "1.7.14");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-06-18T13:24:06+00:00".to_string())
+        );
+        assert_eq!(server.requests()[0].path, "/api/packages/phoenix");
+    }
+
+    #[tokio::test]
+    async fn test_includes_prerelease_when_enabled() {
+        let server = MockServer::start(|_| MockResponse::json(200, PHOENIX)).await;
+
+        let (version, _) = get_latest_version(
+            &test_client(&server),
+            "phoenix",
+            true,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "1.8.0-rc.0");
+    }
+
+    #[tokio::test]
+    async fn test_channel_selects_from_releases() {
+        let server = MockServer::start(|_| MockResponse::json(200, PHOENIX)).await;
+
+        for (channel, expected) in [
+            (ReleaseChannel::Stable, "1.7.14"),
+            (ReleaseChannel::Prerelease, "1.8.0-rc.0"),
+        ] {
+            let (version, _) = get_latest_version(
+                &test_client(&server),
+                "phoenix",
+                true,
+                &VersionFilter::for_channel(channel),
+            )
+            .await
+            .unwrap();
+            assert_eq!(version, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_package() {
+        let server = MockServer::start(|_| MockResponse::json(404, "{}")).await;
+
+        let err = get_latest_version(
+            &test_client(&server),
+            "missing",
+            false,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
@@ -1,9 +1,15 @@
-use reqwest::Client;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
+use super::error::ServiceError;
+use super::github;
+use super::http::HttpClient;
+
 #[derive(Deserialize)]
 struct HomebrewFormula {
     versions: HomebrewVersions,
+    /// 定义文件在 tap 仓库中的路径，如 `Formula/w/wget.rb`
+    ruby_source_path: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -11,21 +17,212 @@ struct HomebrewVersions {
     stable: String,
 }
 
-pub async fn get_version(formula: &str) -> Result<String, String> {
-    let client = Client::new();
+#[derive(Deserialize)]
+struct HomebrewCask {
+    version: String,
+    ruby_source_path: Option<String>,
+}
+
+/// Homebrew 的 API 没有发布时间，用定义文件在 GitHub 上最后一次提交的时间代替
+async fn source_updated_at(
+    http: &HttpClient,
+    repo: &str,
+    path: Option<&str>,
+    github_token: Option<&str>,
+) -> Option<DateTime<Utc>> {
+    let path = path?;
+    github::get_file_last_commit_date(http, github::DEFAULT_API_BASE_URL, repo, path, github_token)
+        .await
+}
+
+/// 获取 Homebrew formula 的稳定版本及其更新时间
+pub async fn get_version(
+    http: &HttpClient,
+    formula: &str,
+    github_token: Option<&str>,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
     let url = format!("https://formulae.brew.sh/api/formula/{}.json", formula);
 
-    let response = client
-        .get(&url)
-        .send()
+    let response = http.send("Homebrew", http.get(&url)).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Homebrew", &response));
+    }
+
+    let formula_info: HomebrewFormula = response
+        .json()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Homebrew response: {}", e)))?;
+
+    let updated_at = source_updated_at(
+        http,
+        "Homebrew/homebrew-core",
+        formula_info.ruby_source_path.as_deref(),
+        github_token,
+    )
+    .await;
+
+    Ok((formula_info.versions.stable, updated_at))
+}
+
+/// 获取 Homebrew cask 的版本，`1.2.3,4567` 这类带构建号的版本只保留逗号前的部分
+pub async fn get_cask_version(
+    http: &HttpClient,
+    token: &str,
+    github_token: Option<&str>,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let url = format!("https://formulae.brew.sh/api/cask/{}.json", token.trim());
+
+    let response = http.send("Homebrew", http.get(&url)).await?;
 
     if !response.status().is_success() {
-        return Err(format!("Homebrew API error: {}", response.status()));
+        return Err(ServiceError::from_response("Homebrew", &response));
+    }
+
+    let cask: HomebrewCask = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Homebrew response: {}", e)))?;
+
+    let version = cask.version.split(',').next().unwrap_or_default().trim();
+    if version.is_empty() || version == "latest" {
+        return Err(ServiceError::NotFound(format!(
+            "Cask '{}' has no fixed version",
+            token.trim()
+        )));
     }
 
-    let formula_info: HomebrewFormula = response.json().await.map_err(|e| e.to_string())?;
+    let updated_at = source_updated_at(
+        http,
+        "Homebrew/homebrew-cask",
+        cask.ruby_source_path.as_deref(),
+        github_token,
+    )
+    .await;
 
-    Ok(formula_info.versions.stable)
+    Ok((version.to_string(), updated_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://formulae.brew.sh", server.url())
+            .redirect("https://api.github.com", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_formula_version_with_commit_date() {
+        let server = MockServer::start(|req| {
+            if req
+                .path
+                .starts_with("/repos/Homebrew/homebrew-core/commits")
+            {
+                MockResponse::json(
+                    200,
+                    r#"[{"sha":"abc","commit":{"author":{"date":"2024-03-11T06:12:44Z"}}}]"#,
+                )
+            } else {
+                MockResponse::json(
+                    200,
+                    r#"{
+                        "name": "wget",
+                        "versions": {"stable": "1.24.5", "head": "HEAD", "bottle": true},
+                        "ruby_source_path": "Formula/w/wget.rb"
+                    }"#,
+                )
+            }
+        })
+        .await;
+
+        let (version, updated_at) = get_version(&test_client(&server), "wget", Some("token"))
+            .await
+            .unwrap();
+
+        assert_eq!(version, "1.24.5");
+        assert_eq!(
+            updated_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-03-11T06:12:44+00:00".to_string())
+        );
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/api/formula/wget.json");
+        assert_eq!(
+            requests[1].path,
+            "/repos/Homebrew/homebrew-core/commits?path=Formula%2Fw%2Fwget.rb&per_page=1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_formula_commit_lookup_failure_keeps_version() {
+        let server = MockServer::start(|req| {
+            if req.path.starts_with("/repos/") {
+                MockResponse::json(403, r#"{"message":"API rate limit exceeded"}"#)
+            } else {
+                MockResponse::json(
+                    200,
+                    r#"{"versions":{"stable":"2.45.2"},"ruby_source_path":"Formula/g/git.rb"}"#,
+                )
+            }
+        })
+        .await;
+
+        let (version, updated_at) = get_version(&test_client(&server), "git", None)
+            .await
+            .unwrap();
+
+        assert_eq!(version, "2.45.2");
+        assert_eq!(updated_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_cask_version() {
+        let server = MockServer::start(|req| {
+            let body = if req.path.contains("jetbrains-toolbox") {
+                r#"{"token":"jetbrains-toolbox","version":"2.3.2,2.3.2.31487"}"#
+            } else {
+                r#"{
+                    "token": "visual-studio-code",
+                    "name": ["Microsoft Visual Studio Code"],
+                    "version": "1.90.0",
+                    "sha256": "no_check",
+                    "url": "https://update.code.visualstudio.com/1.90.0/darwin/stable"
+                }"#
+            };
+            MockResponse::json(200, body)
+        })
+        .await;
+        let http = test_client(&server);
+
+        let (version, _) = get_cask_version(&http, "visual-studio-code", None)
+            .await
+            .unwrap();
+        assert_eq!(version, "1.90.0");
+        assert_eq!(
+            server.requests()[0].path,
+            "/api/cask/visual-studio-code.json"
+        );
+
+        let (version, _) = get_cask_version(&http, "jetbrains-toolbox", None)
+            .await
+            .unwrap();
+        assert_eq!(version, "2.3.2");
+    }
+
+    #[tokio::test]
+    async fn test_cask_without_fixed_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, r#"{"token":"chromium","version":"latest"}"#)
+        })
+        .await;
+
+        let err = get_cask_version(&test_client(&server), "chromium", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
 }
@@ -0,0 +1,332 @@
+use reqwest::header::{HeaderMap, HeaderValue, FROM};
+use reqwest::{Client, Method, Request, RequestBuilder, Response};
+use std::time::Duration;
+
+use super::error::ServiceError;
+use crate::models::NetworkConfig;
+
+/// 未配置时使用的 User-Agent
+pub const DEFAULT_USER_AGENT: &str = "app-version-gui";
+
+/// 所有数据源共享的 HTTP 客户端
+///
+/// 统一配置超时等网络参数，`Client` 内部使用连接池，clone 开销很小
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+    timeout_seconds: u64,
+    max_retries: u32,
+    retry_base_delay: Duration,
+    /// 用户是否配置了自己的 User-Agent
+    custom_user_agent: bool,
+    /// 测试用：将指定前缀的 URL 重定向到本地 mock 服务
+    #[cfg(test)]
+    redirects: Vec<(String, String)>,
+}
+
+impl HttpClient {
+    pub fn new(config: &NetworkConfig) -> Result<Self, String> {
+        let timeout_seconds = config.request_timeout_seconds.max(1) as u64;
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let user_agent = non_empty(&config.user_agent);
+
+        let mut headers = HeaderMap::new();
+        if let Some(contact) = non_empty(&config.contact) {
+            let value = HeaderValue::from_str(&contact)
+                .map_err(|e| format!("Invalid contact header: {}", e))?;
+            headers.insert(FROM, value);
+        }
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(timeout_seconds))
+            .user_agent(user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+            .default_headers(headers)
+            .build()
+            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+        Ok(Self {
+            client,
+            timeout_seconds,
+            max_retries: config.max_retries,
+            retry_base_delay: Duration::from_millis(config.retry_base_delay_ms as u64),
+            custom_user_agent: user_agent.is_some(),
+            #[cfg(test)]
+            redirects: Vec::new(),
+        })
+    }
+
+    /// 是否使用用户配置的 User-Agent
+    pub fn has_custom_user_agent(&self) -> bool {
+        self.custom_user_agent
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(self.resolve_url(url))
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(self.resolve_url(url))
+    }
+
+    /// 发送请求，超时和网络错误统一转换为带数据源名称的错误信息
+    ///
+    /// GET/HEAD 请求遇到网络错误或 5xx 响应时按指数退避重试，4xx 不重试；
+    /// 重试用尽后返回最后一次的响应或错误
+    pub async fn send(
+        &self,
+        source: &str,
+        request: RequestBuilder,
+    ) -> Result<Response, ServiceError> {
+        let request = request.build().map_err(|e| self.map_error(source, e))?;
+        let method = request.method();
+        let retries = if *method == Method::GET || *method == Method::HEAD {
+            self.max_retries
+        } else {
+            0
+        };
+
+        let mut attempt = 0;
+        loop {
+            // 请求体无法复制时只发送一次
+            let current = match request.try_clone() {
+                Some(current) if attempt < retries => current,
+                _ => return self.execute(source, request).await,
+            };
+
+            match self.execute(source, current).await {
+                Ok(response) if !response.status().is_server_error() => return Ok(response),
+                Ok(response) => {
+                    eprintln!(
+                        "[HTTP] {} returned {}, retrying ({}/{})",
+                        source,
+                        response.status(),
+                        attempt + 1,
+                        retries
+                    );
+                }
+                Err(e) => {
+                    eprintln!("[HTTP] {}, retrying ({}/{})", e, attempt + 1, retries);
+                }
+            }
+
+            tokio::time::sleep(retry_delay(self.retry_base_delay, attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    async fn execute(&self, source: &str, request: Request) -> Result<Response, ServiceError> {
+        self.client
+            .execute(request)
+            .await
+            .map_err(|e| self.map_error(source, e))
+    }
+
+    fn map_error(&self, source: &str, e: reqwest::Error) -> ServiceError {
+        if e.is_timeout() {
+            ServiceError::Network(format!(
+                "Request to {} timed out after {}s",
+                source, self.timeout_seconds
+            ))
+        } else {
+            ServiceError::Network(format!("{} request failed: {}", source, e))
+        }
+    }
+
+    #[cfg(not(test))]
+    fn resolve_url(&self, url: &str) -> String {
+        url.to_string()
+    }
+
+    #[cfg(test)]
+    fn resolve_url(&self, url: &str) -> String {
+        for (prefix, target) in &self.redirects {
+            if let Some(rest) = url.strip_prefix(prefix.as_str()) {
+                return format!("{}{}", target, rest);
+            }
+        }
+        url.to_string()
+    }
+
+    /// 测试用：将以 `prefix` 开头的请求转发到 `target`
+    #[cfg(test)]
+    pub fn redirect(mut self, prefix: &str, target: &str) -> Self {
+        self.redirects.push((prefix.to_string(), target.to_string()));
+        self
+    }
+}
+
+/// 第 `attempt` 次重试前的等待时间：`base * 2^attempt`
+fn retry_delay(base: Duration, attempt: u32) -> Duration {
+    base.saturating_mul(1 << attempt.min(10))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::filter::VersionFilter;
+    use crate::services::npm;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_request_timeout_error() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, "{}").with_delay(Duration::from_secs(3))
+        })
+        .await;
+
+        let config = NetworkConfig {
+            request_timeout_seconds: 1,
+            max_retries: 0,
+            ..Default::default()
+        };
+        let http = HttpClient::new(&config)
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url());
+
+        let err = npm::get_latest_version(&http, "react", false, &VersionFilter::default())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ServiceError::Network("Request to npm timed out after 1s".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_keeps_path() {
+        let server = MockServer::start(|_| MockResponse::json(200, "{}")).await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://example.com", server.url());
+
+        let request = http.get("https://example.com/a/b?c=d");
+        http.send("example", request).await.unwrap();
+
+        assert_eq!(server.requests()[0].path, "/a/b?c=d");
+    }
+
+    #[tokio::test]
+    async fn test_sends_configured_user_agent() {
+        let server = MockServer::start(|_| MockResponse::json(200, "{}")).await;
+
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://example.com", server.url());
+        http.send("example", http.get("https://example.com/a"))
+            .await
+            .unwrap();
+        assert!(!http.has_custom_user_agent());
+
+        let config = NetworkConfig {
+            user_agent: Some("my-mirror/1.0 (ops@example.com)".to_string()),
+            contact: Some("ops@example.com".to_string()),
+            ..Default::default()
+        };
+        let http = HttpClient::new(&config)
+            .unwrap()
+            .redirect("https://example.com", server.url());
+        http.send("example", http.get("https://example.com/b"))
+            .await
+            .unwrap();
+        assert!(http.has_custom_user_agent());
+
+        let requests = server.requests();
+        assert_eq!(requests[0].header("user-agent"), Some(DEFAULT_USER_AGENT));
+        assert_eq!(requests[0].header("from"), None);
+        assert_eq!(
+            requests[1].header("user-agent"),
+            Some("my-mirror/1.0 (ops@example.com)")
+        );
+        assert_eq!(requests[1].header("from"), Some("ops@example.com"));
+    }
+
+    fn retrying_client(server: &MockServer) -> HttpClient {
+        let config = NetworkConfig {
+            max_retries: 2,
+            retry_base_delay_ms: 10,
+            ..Default::default()
+        };
+        HttpClient::new(&config)
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url())
+    }
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        let base = Duration::from_millis(100);
+        assert_eq!(retry_delay(base, 0), Duration::from_millis(100));
+        assert_eq!(retry_delay(base, 1), Duration::from_millis(200));
+        assert_eq!(retry_delay(base, 3), Duration::from_millis(800));
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors_then_succeeds() {
+        let attempts = AtomicUsize::new(0);
+        let server = MockServer::start(move |_| {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                MockResponse::text(503, "unavailable")
+            } else {
+                MockResponse::json(200, r#"{"dist-tags": {"latest": "18.3.1"}}"#)
+            }
+        })
+        .await;
+
+        let (version, _) = npm::get_latest_version(
+            &retrying_client(&server),
+            "react",
+            false,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "18.3.1");
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let server = MockServer::start(|_| MockResponse::text(502, "bad gateway")).await;
+
+        let err = npm::get_latest_version(
+            &retrying_client(&server),
+            "react",
+            false,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err, ServiceError::Api("npm API error: 502 Bad Gateway".to_string()));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors_or_posts() {
+        let server = MockServer::start(|req| {
+            if req.path.starts_with("/missing") {
+                MockResponse::text(404, "not found")
+            } else {
+                MockResponse::text(503, "unavailable")
+            }
+        })
+        .await;
+        let http = retrying_client(&server);
+
+        let none = VersionFilter::default();
+        assert!(npm::get_latest_version(&http, "missing", false, &none).await.is_err());
+        assert_eq!(server.requests().len(), 1);
+
+        let request = http.post("https://registry.npmjs.org/hook").body("{}");
+        let response = http.send("example", request).await.unwrap();
+        assert_eq!(response.status(), 503);
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,300 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::{compare_versions, is_prerelease, VersionComparison};
+
+const API_BASE_URL: &str = "https://api.launchpad.net/1.0";
+
+/// Ubuntu 数据源标识：`series/package`，PPA 为 `ppa:owner/name/series/package`
+#[derive(Debug, PartialEq)]
+pub(crate) struct PackageSpec<'a> {
+    /// PPA 的所有者和名称，官方仓库为 None
+    pub ppa: Option<(&'a str, &'a str)>,
+    pub series: &'a str,
+    pub package: &'a str,
+}
+
+pub(crate) fn parse_spec(spec: &str) -> Result<PackageSpec<'_>, ServiceError> {
+    let spec = spec.trim();
+    let invalid = || {
+        ServiceError::Parse(format!(
+            "Invalid Ubuntu package '{}', expected 'series/package' or 'ppa:owner/name/series/package'",
+            spec
+        ))
+    };
+
+    let parts: Vec<&str> = spec.split('/').map(str::trim).collect();
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(invalid());
+    }
+    match parts.as_slice() {
+        [series, package] if !series.starts_with("ppa:") => Ok(PackageSpec {
+            ppa: None,
+            series,
+            package,
+        }),
+        [owner, name, series, package] => {
+            let owner = owner.strip_prefix("ppa:").filter(|o| !o.is_empty());
+            Ok(PackageSpec {
+                ppa: Some((owner.ok_or_else(invalid)?, name)),
+                series,
+                package,
+            })
+        }
+        _ => Err(invalid()),
+    }
+}
+
+#[derive(Deserialize)]
+struct PublishedSource {
+    source_package_version: String,
+    date_published: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PublishedSources {
+    #[serde(default)]
+    entries: Vec<PublishedSource>,
+}
+
+/// 同一个包可能同时发布在 release、updates、security 等 pocket 中，按 Debian 版本规则取最大的
+fn newest_source(entries: Vec<PublishedSource>, filter: &VersionFilter) -> Option<PublishedSource> {
+    entries
+        .into_iter()
+        .filter(|entry| filter.matches(&entry.source_package_version))
+        .filter(|entry| filter.matches_channel(is_prerelease(&entry.source_package_version)))
+        .reduce(|newest, entry| {
+            let local = Some(newest.source_package_version.clone());
+            if compare_versions(&entry.source_package_version, &local) == VersionComparison::Greater
+            {
+                entry
+            } else {
+                newest
+            }
+        })
+}
+
+/// 获取 Ubuntu 官方仓库或 PPA 中某个发行版已发布的最新源码包版本
+pub async fn get_latest_version(
+    http: &HttpClient,
+    spec: &str,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let PackageSpec {
+        ppa,
+        series,
+        package,
+    } = parse_spec(spec)?;
+
+    let archive_url = match ppa {
+        Some((owner, name)) => format!("{}/~{}/+archive/ubuntu/{}", API_BASE_URL, owner, name),
+        None => format!("{}/ubuntu/+archive/primary", API_BASE_URL),
+    };
+    let distro_series = format!("{}/ubuntu/{}", API_BASE_URL, series);
+    let request = http.get(&archive_url).query(&[
+        ("ws.op", "getPublishedSources"),
+        ("source_name", package),
+        ("exact_match", "true"),
+        ("status", "Published"),
+        ("distro_series", distro_series.as_str()),
+    ]);
+    let response = http.send("Launchpad", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Launchpad", &response));
+    }
+
+    let body: PublishedSources = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Launchpad response: {}", e)))?;
+
+    let newest = newest_source(body.entries, filter).ok_or_else(|| {
+        ServiceError::NotFound(format!(
+            "No published source of {} found in {}",
+            package, series
+        ))
+    })?;
+
+    let published_at = newest
+        .date_published
+        .as_deref()
+        .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    Ok((newest.source_package_version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://api.launchpad.net", server.url())
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        assert_eq!(
+            parse_spec("noble/openssl").unwrap(),
+            PackageSpec {
+                ppa: None,
+                series: "noble",
+                package: "openssl",
+            }
+        );
+        assert_eq!(
+            parse_spec(" ppa:mozillateam/ppa/jammy/firefox ").unwrap(),
+            PackageSpec {
+                ppa: Some(("mozillateam", "ppa")),
+                series: "jammy",
+                package: "firefox",
+            }
+        );
+        for invalid in [
+            "openssl",
+            "noble/",
+            "ppa:/ppa/noble/firefox",
+            "a/b/c/d",
+            "ppa:x/noble",
+        ] {
+            assert!(parse_spec(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    // 真实响应中的条目按发布时间倒序排列，这里打乱顺序以验证按版本选取
+    const PUBLISHED_SOURCES: &str = r#"{
+        "start": 0,
+        "total_size": 3,
+        "entries": [
+            {
+                "source_package_name": "openssl",
+                "source_package_version": "3.0.13-0ubuntu3.1",
+                "pocket": "Security",
+                "status": "Published",
+                "date_published": "2024-06-05T10:12:40.118218+00:00"
+            },
+            {
+                "source_package_name": "openssl",
+                "source_package_version": "3.0.13-0ubuntu3.4",
+                "pocket": "Updates",
+                "status": "Published",
+                "date_published": "2024-09-12T08:30:11.402755+00:00"
+            },
+            {
+                "source_package_name": "openssl",
+                "source_package_version": "3.0.13-0ubuntu3",
+                "pocket": "Release",
+                "status": "Published",
+                "date_published": "2024-04-08T15:42:06.591234+00:00"
+            }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|_| MockResponse::json(200, PUBLISHED_SOURCES)).await;
+
+        let (version, published_at) = get_latest_version(
+            &test_client(&server),
+            "noble/openssl",
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "3.0.13-0ubuntu3.4");
+        assert_eq!(
+            published_at.map(|dt| dt.timestamp()),
+            DateTime::parse_from_rfc3339("2024-09-12T08:30:11Z")
+                .ok()
+                .map(|dt| dt.timestamp())
+        );
+        let path = &server.requests()[0].path;
+        assert!(path.starts_with("/1.0/ubuntu/+archive/primary?ws.op=getPublishedSources"));
+        assert!(path.contains("source_name=openssl"));
+        assert!(
+            path.contains("distro_series=https%3A%2F%2Fapi.launchpad.net%2F1.0%2Fubuntu%2Fnoble")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ppa_with_epoch_and_tilde() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"entries": [
+                    {"source_package_version": "1:2.0-1~ppa1", "date_published": null},
+                    {"source_package_version": "1:2.0-1", "date_published": null},
+                    {"source_package_version": "9.9-1", "date_published": null}
+                ]}"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) = get_latest_version(
+            &test_client(&server),
+            "ppa:owner/tools/jammy/tool",
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        // epoch 优先，`~` 排在正式修订号之前
+        assert_eq!(version, "1:2.0-1");
+        assert_eq!(published_at, None);
+        assert!(server.requests()[0]
+            .path
+            .starts_with("/1.0/~owner/+archive/ubuntu/tools?"));
+    }
+
+    #[test]
+    fn test_newest_source_by_channel() {
+        let entries = || {
+            ["2.0~rc1-1", "1.9-2ubuntu1", "1.9-1"]
+                .into_iter()
+                .map(|version| PublishedSource {
+                    source_package_version: version.to_string(),
+                    date_published: None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let newest = |channel| {
+            newest_source(entries(), &VersionFilter::for_channel(channel))
+                .map(|entry| entry.source_package_version)
+        };
+        assert_eq!(newest(ReleaseChannel::Any).as_deref(), Some("2.0~rc1-1"));
+        assert_eq!(
+            newest(ReleaseChannel::Stable).as_deref(),
+            Some("1.9-2ubuntu1")
+        );
+        assert_eq!(
+            newest(ReleaseChannel::Prerelease).as_deref(),
+            Some("2.0~rc1-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_published_sources() {
+        let server =
+            MockServer::start(|_| MockResponse::json(200, r#"{"total_size": 0, "entries": []}"#))
+                .await;
+
+        let err = get_latest_version(
+            &test_client(&server),
+            "noble/missing",
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
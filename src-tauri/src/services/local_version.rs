@@ -1,25 +1,454 @@
 use regex::Regex;
-use std::process::Command;
+use serde_json::Value;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
-pub fn get_version(command_name: &str, version_arg: Option<&str>) -> Result<String, String> {
-    let arg = version_arg.unwrap_or("--version");
+use super::custom_json;
+use crate::models::{LocalVersionConfig, LocalVersionSource};
 
-    let output = Command::new(command_name)
-        .arg(arg)
-        .output()
-        .map_err(|e| format!("Failed to execute {}: {}", command_name, e))?;
+/// 默认的版本号提取规则（支持多种格式）
+const DEFAULT_VERSION_PATTERN: &str = r"(\d+\.\d+(?:\.\d+)?(?:-[\w.]+)?)";
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let output_str = format!("{}{}", stdout, stderr);
+/// 本地命令的默认超时时间（秒）
+pub const DEFAULT_TIMEOUT_SECONDS: u32 = 10;
 
-    // Try to extract version number (supports multiple formats)
-    let version_regex = Regex::new(r"(\d+\.\d+(?:\.\d+)?(?:-[\w.]+)?)")
-        .map_err(|e| e.to_string())?;
+/// 检查子进程是否退出的轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
 
+/// 编译版本号提取正则，未配置时使用默认规则
+pub fn compile_version_regex(pattern: Option<&str>) -> Result<Regex, String> {
+    match pattern.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(pattern) => {
+            Regex::new(pattern).map_err(|e| format!("Invalid version regex '{}': {}", pattern, e))
+        }
+        None => Regex::new(DEFAULT_VERSION_PATTERN).map_err(|e| e.to_string()),
+    }
+}
+
+/// 从命令输出中提取第一个捕获组作为版本号
+fn extract_version(output: &str, version_regex: &Regex) -> Result<String, String> {
     version_regex
-        .captures(&output_str)
+        .captures(output)
         .and_then(|caps| caps.get(1))
         .map(|m| m.as_str().to_string())
-        .ok_or_else(|| format!("Could not parse version from: {}", output_str.trim()))
+        .ok_or_else(|| format!("Could not parse version from: {}", output.trim()))
+}
+
+pub fn get_version(config: &LocalVersionConfig) -> Result<String, String> {
+    let version_regex = compile_version_regex(config.version_regex.as_deref())?;
+
+    match &config.source {
+        LocalVersionSource::Command => extract_version(&run_command(config)?, &version_regex),
+        LocalVersionSource::CommandJson { json_pointer } => {
+            let output = run_command(config)?;
+            extract_json_version(&output, json_pointer)
+                .unwrap_or_else(|| extract_version(&output, &version_regex))
+        }
+        LocalVersionSource::FileRead { path, json_pointer } => {
+            get_version_from_file(path, json_pointer.as_deref(), &version_regex)
+        }
+    }
+}
+
+/// 从文件中读取版本号，给出 JSON 路径时按路径取值，否则用正则匹配文件内容
+pub fn get_version_from_file(
+    path: &str,
+    json_pointer: Option<&str>,
+    version_regex: &Regex,
+) -> Result<String, String> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    let Some(pointer) = json_pointer.map(str::trim).filter(|p| !p.is_empty()) else {
+        return extract_version(&contents, version_regex);
+    };
+
+    let json: Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse {} as JSON: {}", path, e))?;
+    custom_json::select_path(&json, pointer)
+        .map_err(|e| e.to_string())?
+        .and_then(custom_json::value_to_string)
+        .ok_or_else(|| format!("No version found at '{}' in {}", pointer, path))
+}
+
+/// 按 JSON 路径从命令输出中取版本号，输出不是 JSON 时返回 `None`，改用正则匹配
+fn extract_json_version(output: &str, pointer: &str) -> Option<Result<String, String>> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    let pointer = pointer.trim();
+    let version = custom_json::select_path(&json, pointer)
+        .map_err(|e| e.to_string())
+        .and_then(|value| {
+            value
+                .and_then(custom_json::value_to_string)
+                .ok_or_else(|| format!("No version found at '{}' in command output", pointer))
+        });
+    Some(version)
+}
+
+/// 执行配置的命令，返回 stdout + stderr
+fn run_command(config: &LocalVersionConfig) -> Result<String, String> {
+    let timeout = Duration::from_secs(
+        config
+            .timeout_seconds
+            .unwrap_or(DEFAULT_TIMEOUT_SECONDS)
+            .max(1) as u64,
+    );
+
+    let mut command = if config.use_shell {
+        shell_command(&shell_line(config))
+    } else {
+        let mut command = Command::new(&config.command);
+        command.args(config.command_args());
+        command
+    };
+    if let Some(dir) = config
+        .working_dir
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        command.current_dir(dir);
+    }
+    if let Some(env) = &config.env {
+        command.envs(env);
+    }
+    run_with_timeout(&mut command, &config.command, timeout).map_err(|e| {
+        if config.use_shell {
+            // shell 会解释命令中的 `;`、`|`、`$()` 等语法，出错时提示命令是经 shell 执行的
+            format!("{} (run via shell, make sure the command is trusted)", e)
+        } else {
+            e
+        }
+    })
+}
+
+/// shell 模式下执行的命令行：命令后接显式配置的参数，不追加默认的 `--version`
+fn shell_line(config: &LocalVersionConfig) -> String {
+    let has_args = !config.args.is_empty()
+        || config
+            .version_arg
+            .as_deref()
+            .is_some_and(|arg| !arg.trim().is_empty());
+    if has_args {
+        format!(
+            "{} {}",
+            config.command.trim(),
+            config.command_args().join(" ")
+        )
+    } else {
+        config.command.trim().to_string()
+    }
+}
+
+#[cfg(windows)]
+fn shell_command(line: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.args(["/C", line]);
+    command
+}
+
+#[cfg(not(windows))]
+fn shell_command(line: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.args(["-c", line]);
+    command
+}
+
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+/// 执行命令并返回 stdout + stderr，超时后结束子进程
+fn run_with_timeout(
+    command: &mut Command,
+    name: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    // 关闭 stdin，避免命令等待输入
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute {}: {}", name, e))?;
+
+    // 在后台线程读取输出，防止管道写满导致子进程阻塞
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Command '{}' timed out", name));
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => return Err(format!("Failed to wait for {}: {}", name, e)),
+        }
+    }
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    Ok(format!(
+        "{}{}",
+        String::from_utf8_lossy(&stdout),
+        String::from_utf8_lossy(&stderr)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_default_pattern() {
+        let regex = compile_version_regex(None).unwrap();
+        assert_eq!(
+            extract_version("git version 2.43.0.windows.1", &regex).unwrap(),
+            "2.43.0"
+        );
+        assert_eq!(
+            extract_version("node v20.11.1\n", &regex).unwrap(),
+            "20.11.1"
+        );
+    }
+
+    #[test]
+    fn test_custom_pattern() {
+        let regex = compile_version_regex(Some(r"git version (\S+)")).unwrap();
+        assert_eq!(
+            extract_version("git version 2.43.0.windows.1\n", &regex).unwrap(),
+            "2.43.0.windows.1"
+        );
+
+        let regex = compile_version_regex(Some(r"tool v(\d+\.\d+)")).unwrap();
+        assert_eq!(
+            extract_version("tool v1.2 (build 2024-01-01)", &regex).unwrap(),
+            "1.2"
+        );
+        assert!(extract_version("something else", &regex).is_err());
+    }
+
+    #[test]
+    fn test_blank_pattern_uses_default() {
+        let regex = compile_version_regex(Some("  ")).unwrap();
+        assert_eq!(regex.as_str(), DEFAULT_VERSION_PATTERN);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_timeout() {
+        let config = LocalVersionConfig {
+            command: "sleep".to_string(),
+            version_arg: Some("5".to_string()),
+            timeout_seconds: Some(1),
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        let err = get_version(&config).unwrap_err();
+        assert_eq!(err, "Command 'sleep' timed out");
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_output() {
+        let mut command = Command::new("echo");
+        command.arg("tool 1.2.3");
+        let output = run_with_timeout(&mut command, "echo", Duration::from_secs(5)).unwrap();
+        assert_eq!(output.trim(), "tool 1.2.3");
+    }
+
+    #[test]
+    fn test_legacy_version_arg_is_split() {
+        let mut config = LocalVersionConfig {
+            command: "go".to_string(),
+            version_arg: Some(" version -m ./bin/app ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(config.command_args(), vec!["version", "-m", "./bin/app"]);
+
+        config.args = vec!["version".to_string(), "-m".to_string()];
+        assert_eq!(config.command_args(), vec!["version", "-m"]);
+
+        config.args.clear();
+        config.version_arg = None;
+        assert_eq!(config.command_args(), vec!["--version"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_multiple_args() {
+        let config = LocalVersionConfig {
+            command: "echo".to_string(),
+            args: vec![
+                "tool".to_string(),
+                "version".to_string(),
+                "v3.1.4".to_string(),
+            ],
+            version_regex: Some(r"tool version v(\S+)".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(get_version(&config).unwrap(), "3.1.4");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_working_dir_and_env() {
+        let root = std::env::temp_dir().join(format!("local-version-{}", uuid::Uuid::new_v4()));
+        let dir = root.join("app-2.7.1");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let config = LocalVersionConfig {
+            command: "pwd".to_string(),
+            args: vec!["-P".to_string()],
+            working_dir: Some(dir.to_string_lossy().into_owned()),
+            version_regex: Some(r"app-(\d+\.\d+\.\d+)".to_string()),
+            ..Default::default()
+        };
+        let version = get_version(&config);
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(version.unwrap(), "2.7.1");
+
+        let config = LocalVersionConfig {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), "echo tool $TOOL_VERSION".to_string()],
+            env: Some(HashMap::from([(
+                "TOOL_VERSION".to_string(),
+                "5.0.2".to_string(),
+            )])),
+            ..Default::default()
+        };
+        assert_eq!(get_version(&config).unwrap(), "5.0.2");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_pipeline() {
+        let mut config = LocalVersionConfig {
+            command: "printf 'Name: foo\\nVersion: 4.2.0\\n' | grep Version".to_string(),
+            version_regex: Some(r"^Version: (\S+)".to_string()),
+            use_shell: true,
+            ..Default::default()
+        };
+        assert_eq!(get_version(&config).unwrap(), "4.2.0");
+
+        // 默认直接执行，管道符会作为普通参数传给命令
+        config.use_shell = false;
+        assert!(get_version(&config).is_err());
+    }
+
+    #[test]
+    fn test_shell_line_appends_explicit_args() {
+        let mut config = LocalVersionConfig {
+            command: "pip show foo | grep Version".to_string(),
+            use_shell: true,
+            ..Default::default()
+        };
+        assert_eq!(shell_line(&config), "pip show foo | grep Version");
+
+        config.command = "tool".to_string();
+        config.args = vec!["version".to_string(), "--short".to_string()];
+        assert_eq!(shell_line(&config), "tool version --short");
+    }
+
+    #[cfg(unix)]
+    fn json_command_config(output: &str) -> LocalVersionConfig {
+        LocalVersionConfig {
+            source: LocalVersionSource::CommandJson {
+                json_pointer: "/version".to_string(),
+            },
+            command: "echo".to_string(),
+            args: vec![output.to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_version_from_json_command() {
+        let config = json_command_config(r#"{"version":"1.2.3","commit":"4.5.6"}"#);
+        assert_eq!(get_version(&config).unwrap(), "1.2.3");
+
+        let config = json_command_config(r#"{"name":"tool"}"#);
+        assert_eq!(
+            get_version(&config).unwrap_err(),
+            "No version found at '/version' in command output"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_malformed_json_falls_back_to_regex() {
+        let config = json_command_config(r#"{"version": "2.0.1", "#);
+        assert_eq!(get_version(&config).unwrap(), "2.0.1");
+    }
+
+    /// 在临时目录中写入文件，返回 (目录, 文件路径)
+    fn write_temp_file(name: &str, contents: &str) -> (std::path::PathBuf, String) {
+        let dir = std::env::temp_dir().join(format!("local-version-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        (dir, path.to_string_lossy().into_owned())
+    }
+
+    fn file_config(path: &str, json_pointer: Option<&str>) -> LocalVersionConfig {
+        LocalVersionConfig {
+            source: LocalVersionSource::FileRead {
+                path: path.to_string(),
+                json_pointer: json_pointer.map(String::from),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_version_from_plain_file() {
+        let (dir, path) = write_temp_file("VERSION", "1.12.0\n");
+        let version = get_version(&file_config(&path, None));
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(version.unwrap(), "1.12.0");
+    }
+
+    #[test]
+    fn test_version_from_package_json() {
+        let (dir, path) = write_temp_file(
+            "package.json",
+            r#"{"name": "my-tool", "version": "0.9.3", "engines": {"node": ">=18.0.0"}}"#,
+        );
+        let version = get_version(&file_config(&path, Some("$.version")));
+        let missing = get_version(&file_config(&path, Some("$.publishConfig.tag")));
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(version.unwrap(), "0.9.3");
+        assert!(missing
+            .unwrap_err()
+            .starts_with("No version found at '$.publishConfig.tag'"));
+    }
+
+    #[test]
+    fn test_version_file_missing() {
+        let err = get_version(&file_config("/nonexistent/VERSION", None)).unwrap_err();
+        assert!(err.starts_with("Failed to read /nonexistent/VERSION"));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let err = compile_version_regex(Some(r"version (\d+")).unwrap_err();
+        assert!(err.starts_with("Invalid version regex 'version (\\d+'"));
+    }
 }
@@ -0,0 +1,193 @@
+use chrono::{DateTime, TimeZone, Utc};
+use semver::Version;
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::{is_prerelease, parse_version};
+
+#[derive(Deserialize)]
+struct MavenDoc {
+    v: String,
+    /// 发布时间（毫秒时间戳）
+    timestamp: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct MavenResponseBody {
+    #[serde(default)]
+    docs: Vec<MavenDoc>,
+}
+
+#[derive(Deserialize)]
+struct MavenSearchResponse {
+    response: MavenResponseBody,
+}
+
+/// 拆分 `groupId:artifactId` 坐标
+pub(crate) fn parse_coordinates(coords: &str) -> Result<(&str, &str), ServiceError> {
+    match coords.trim().split_once(':') {
+        Some((group, artifact)) if !group.trim().is_empty() && !artifact.trim().is_empty() => {
+            Ok((group.trim(), artifact.trim()))
+        }
+        _ => Err(ServiceError::Api(format!(
+            "Invalid Maven coordinates '{}', expected groupId:artifactId",
+            coords
+        ))),
+    }
+}
+
+/// 取能解析为语义化版本的最大值，都无法解析时退回第一条，只在匹配 `filter` 的版本中选取
+fn select_latest(docs: Vec<MavenDoc>, filter: &VersionFilter) -> Option<MavenDoc> {
+    let mut latest: Option<(Version, MavenDoc)> = None;
+    let mut fallback = None;
+
+    for doc in docs {
+        if !filter.matches(&doc.v) || !filter.matches_channel(is_prerelease(&doc.v)) {
+            continue;
+        }
+        match parse_version(&doc.v).into_semver() {
+            Some(version) => {
+                let is_newer = match &latest {
+                    Some((max, _)) => version > *max,
+                    None => true,
+                };
+                if is_newer {
+                    latest = Some((version, doc));
+                }
+            }
+            None => {
+                fallback.get_or_insert(doc);
+            }
+        }
+    }
+
+    latest.map(|(_, doc)| doc).or(fallback)
+}
+
+/// 获取 Maven Central 上构件的最新版本，`coords` 为 `groupId:artifactId`
+pub async fn get_latest_version(
+    http: &HttpClient,
+    coords: &str,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let (group, artifact) = parse_coordinates(coords)?;
+    let url = format!(
+        "https://search.maven.org/solrsearch/select?q=g:\"{}\"+AND+a:\"{}\"&core=gav&rows=20&wt=json",
+        group, artifact
+    );
+
+    let request = http.get(&url);
+    let response = http.send("Maven Central", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Maven Central", &response));
+    }
+
+    let search: MavenSearchResponse = response.json().await.map_err(|e| {
+        ServiceError::Parse(format!("Failed to parse Maven Central response: {}", e))
+    })?;
+
+    let latest = select_latest(search.response.docs, filter).ok_or_else(|| {
+        ServiceError::NotFound(format!("Artifact '{}' not found on Maven Central", coords))
+    })?;
+
+    let published_at = latest
+        .timestamp
+        .and_then(|millis| Utc.timestamp_millis_opt(millis).single());
+
+    Ok((latest.v, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://search.maven.org", server.url())
+    }
+
+    #[test]
+    fn test_parse_coordinates() {
+        assert_eq!(
+            parse_coordinates("com.google.guava:guava").unwrap(),
+            ("com.google.guava", "guava")
+        );
+        assert!(parse_coordinates("guava").is_err());
+        assert!(parse_coordinates(":guava").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picks_highest_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"response": {"numFound": 3, "docs": [
+                    {"id": "org.example:lib:1.10.2", "v": "1.10.2", "timestamp": 1704067200000},
+                    {"id": "org.example:lib:1.9.0", "v": "1.9.0", "timestamp": 1711929600000},
+                    {"id": "org.example:lib:2.0.1", "v": "2.0.1", "timestamp": 1709251200000}
+                ]}}"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) = get_latest_version(
+            &test_client(&server),
+            "org.example:lib",
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "2.0.1");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-03-01T00:00:00+00:00".to_string())
+        );
+
+        let path = &server.requests()[0].path;
+        assert!(path.starts_with("/solrsearch/select?"));
+        assert!(path.contains("a:%22lib%22"));
+        assert!(path.contains("core=gav"));
+    }
+
+    #[test]
+    fn test_select_latest_by_channel() {
+        let docs = || {
+            ["3.9.6", "4.0.0-rc-1", "4.0.0-alpha-13"]
+                .into_iter()
+                .map(|v| MavenDoc {
+                    v: v.to_string(),
+                    timestamp: None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let stable = VersionFilter::for_channel(ReleaseChannel::Stable);
+        assert_eq!(select_latest(docs(), &stable).unwrap().v, "3.9.6");
+        let prerelease = VersionFilter::for_channel(ReleaseChannel::Prerelease);
+        assert_eq!(select_latest(docs(), &prerelease).unwrap().v, "4.0.0-rc-1");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_artifact() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, r#"{"response": {"numFound": 0, "docs": []}}"#)
+        })
+        .await;
+
+        let err = get_latest_version(
+            &test_client(&server),
+            "org.example:missing",
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
@@ -1,6 +1,448 @@
+pub mod aur;
+pub mod batch;
+pub mod bitbucket;
 pub mod cargo;
+pub mod conda;
+pub mod cran;
+pub mod custom_json;
+pub mod dockerhub;
+pub mod error;
+pub mod fdroid;
+pub mod feed;
+pub mod filter;
+pub mod flathub;
+pub mod git;
+pub mod gitea;
 pub mod github;
+pub mod gomod;
+pub mod hex;
 pub mod homebrew;
+pub mod http;
+pub mod launchpad;
 pub mod local_version;
+pub mod maven;
 pub mod npm;
+pub mod packagist;
+pub mod nuget;
 pub mod pypi;
+pub mod rubygems;
+pub mod scrape;
+pub mod sourceforge;
+pub mod urls;
+pub mod winget;
+
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use crate::models::{AppSettings, SourceConfig, SourceType, VersionPreview};
+use error::ServiceError;
+use filter::VersionFilter;
+use http::HttpClient;
+
+/// 远程最新版本信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteVersion {
+    pub version: String,
+    pub published_at: Option<DateTime<Utc>>,
+    /// 发布说明（Markdown），目前只有 GitHub Release 提供
+    pub release_notes: Option<String>,
+    /// 匹配 `required_asset` 的附件下载地址
+    pub asset_url: Option<String>,
+}
+
+/// 根据数据源类型获取远程最新版本及发布时间
+pub async fn fetch_latest_version(
+    http: &HttpClient,
+    source: &SourceConfig,
+    settings: &AppSettings,
+) -> Result<RemoteVersion, ServiceError> {
+    let global_github_token = settings.github_token.as_deref();
+    // 数据源自己的 token 只用于该仓库的请求，优先于全局 token
+    let github_token = source
+        .auth_token
+        .as_deref()
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .or(global_github_token);
+    let github_base_url = settings.github_api_base_url();
+    // Homebrew 和 winget 的仓库在 github.com 上，配置了 GitHub Enterprise 时不发送其 token
+    let public_github_token =
+        global_github_token.filter(|_| github_base_url == github::DEFAULT_API_BASE_URL);
+    let filter = VersionFilter::from_source(source)?;
+    // 设置了发布通道时由通道决定是否接受预发布版本
+    let include_prerelease = source.include_prerelease || filter.has_channel();
+    let release_version = github::ReleaseVersion::from_source(source)?;
+    let required_asset = match source.required_asset.as_deref().map(str::trim) {
+        Some(pattern) if !pattern.is_empty() => Some(github::asset_pattern(pattern)?),
+        _ => None,
+    };
+
+    let (version, published_at) = match source.source_type {
+        // `/releases/latest` 无法按版本或附件筛选，配置了筛选时同样改为从列表中选取
+        SourceType::GithubRelease
+            if include_prerelease || !filter.is_empty() || required_asset.is_some() =>
+        {
+            return github::get_latest_release_from_list(
+                http,
+                github_base_url,
+                &source.identifier,
+                github_token,
+                include_prerelease,
+                &filter,
+                &release_version,
+                required_asset.as_ref(),
+            )
+            .await;
+        }
+        SourceType::GithubRelease => {
+            return github::get_latest_release(
+                http,
+                github_base_url,
+                &source.identifier,
+                github_token,
+                &release_version,
+            )
+            .await;
+        }
+        SourceType::GithubTags => {
+            github::get_latest_tag(
+                http,
+                github_base_url,
+                &source.identifier,
+                github_token,
+                include_prerelease,
+                &filter,
+                source.tag_prefix.as_deref().map(str::trim).filter(|p| !p.is_empty()),
+            )
+            .await?
+        }
+        SourceType::Homebrew => {
+            homebrew::get_version(http, &source.identifier, public_github_token).await?
+        }
+        SourceType::HomebrewCask => {
+            homebrew::get_cask_version(http, &source.identifier, public_github_token).await?
+        }
+        SourceType::Npm => {
+            npm::get_latest_version(http, &source.identifier, include_prerelease, &filter)
+                .await?
+        }
+        SourceType::Pypi => {
+            pypi::get_latest_version(http, &source.identifier, include_prerelease, &filter)
+                .await?
+        }
+        SourceType::Cargo => cargo::get_latest_version(http, &source.identifier).await?,
+        SourceType::DockerHub => {
+            dockerhub::get_latest_version(http, &source.identifier, &filter).await?
+        }
+        SourceType::RubyGems => rubygems::get_latest_version(http, &source.identifier).await?,
+        SourceType::NuGet => nuget::get_latest_version(http, &source.identifier, &filter).await?,
+        SourceType::GoModule => gomod::get_latest_version(http, &source.identifier).await?,
+        SourceType::Maven => maven::get_latest_version(http, &source.identifier, &filter).await?,
+        SourceType::Conda => conda::get_latest_version(http, &source.identifier).await?,
+        SourceType::Packagist => packagist::get_latest_version(http, &source.identifier).await?,
+        SourceType::Aur => aur::get_latest_version(http, &source.identifier).await?,
+        SourceType::SourceForge => {
+            sourceforge::get_latest_version(http, &source.identifier).await?
+        }
+        SourceType::Flathub => flathub::get_latest_version(http, &source.identifier).await?,
+        SourceType::Gitea => {
+            let (base_url, owner_repo) = gitea::parse_identifier(&source.identifier)?;
+            gitea::get_latest_release(
+                http,
+                base_url,
+                owner_repo,
+                settings.gitea_token.as_deref(),
+                &filter,
+            )
+            .await?
+        }
+        SourceType::Cran => cran::get_latest_version(http, &source.identifier).await?,
+        SourceType::Fdroid => fdroid::get_latest_version(http, &source.identifier).await?,
+        SourceType::UbuntuPackage => {
+            launchpad::get_latest_version(http, &source.identifier, &filter).await?
+        }
+        SourceType::GitTags => {
+            let timeout = Duration::from_secs(settings.network.request_timeout_seconds.into());
+            git::get_latest_tag(&source.identifier, include_prerelease, &filter, timeout)
+                .await?
+        }
+        SourceType::Winget => {
+            winget::get_latest_version(http, &source.identifier, public_github_token).await?
+        }
+        SourceType::Hex => {
+            hex::get_latest_version(http, &source.identifier, include_prerelease, &filter).await?
+        }
+        SourceType::Bitbucket => {
+            bitbucket::get_latest_tag(
+                http,
+                &source.identifier,
+                settings.bitbucket_token.as_deref(),
+                &filter,
+            )
+            .await?
+        }
+        SourceType::CustomJson => {
+            custom_json::get_latest_version(http, &source.identifier).await?
+        }
+        SourceType::Feed => feed::get_latest_version(http, &source.identifier).await?,
+        SourceType::WebScrape => {
+            let (url, pattern) = scrape::split_spec(&source.identifier)?;
+            scrape::get_latest_version(http, url, pattern).await?
+        }
+    };
+
+    Ok(RemoteVersion {
+        version,
+        published_at,
+        release_notes: None,
+        asset_url: None,
+    })
+}
+
+/// 按数据源配置获取一次最新版本，用于保存前预览
+pub async fn preview_source(
+    http: &HttpClient,
+    source: &SourceConfig,
+    settings: &AppSettings,
+) -> Result<VersionPreview, ServiceError> {
+    if source.identifier.trim().is_empty() {
+        return Err(ServiceError::Api("Identifier must not be empty".to_string()));
+    }
+
+    let remote = fetch_latest_version(http, source, settings).await?;
+    Ok(VersionPreview {
+        latest_version: remote.version,
+        published_at: remote.published_at,
+        asset_url: remote.asset_url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer, RecordedRequest};
+
+    fn registry_response(request: &RecordedRequest) -> MockResponse {
+        let path = request.path.as_str();
+        let body = match path {
+            "/repos/o/release/releases/latest" => {
+                r#"{"tag_name":"v1.2.0","published_at":"2024-05-01T00:00:00Z"}"#
+            }
+            "/repos/o/tags/tags?per_page=100&page=1" => r#"[{"name":"v1.1.0","commit":{"sha":"abc"}}]"#,
+            "/repos/o/tags/commits/abc" => {
+                r#"{"commit":{"author":{"date":"2024-04-01T00:00:00Z"}}}"#
+            }
+            "/api/formula/wget.json" => r#"{"versions":{"stable":"1.24.5"}}"#,
+            "/api/cask/firefox.json" => r#"{"token":"firefox","version":"127.0"}"#,
+            "/react" => {
+                r#"{"dist-tags":{"latest":"18.3.1"},"time":{"18.3.1":"2024-04-26T16:42:52Z"}}"#
+            }
+            "/pypi/requests/json" => {
+                r#"{"info":{"version":"2.32.3"},"releases":{"2.32.3":[{"upload_time":"2024-05-29T15:37:47"}]}}"#
+            }
+            "/api/v1/crates/serde" => {
+                r#"{"crate":{"max_version":"1.0.210","updated_at":"2024-09-06T00:00:00Z"}}"#
+            }
+            "/api/v1/versions/rails/latest.json" => r#"{"version":"7.1.3"}"#,
+            "/api/v1/gems/rails.json" => {
+                r#"{"version":"7.1.3","version_created_at":"2024-01-16T00:00:00Z"}"#
+            }
+            "/v3-flatcontainer/newtonsoft.json/index.json" => {
+                r#"{"versions":["13.0.2","13.0.3","14.0.0-beta1"]}"#
+            }
+            "/golang.org/x/text/@latest" => {
+                r#"{"Version":"v0.14.0","Time":"2023-10-11T00:00:00Z"}"#
+            }
+            "/custom.json" => r#"{"data":{"version":"3.4.5"}}"#,
+            "/releases.atom" => {
+                return MockResponse::text(
+                    200,
+                    r#"<feed><entry><title>Release v2.0.1</title><updated>2024-03-01T00:00:00Z</updated></entry></feed>"#,
+                )
+            }
+            "/download" => return MockResponse::text(200, "<a>tool-4.12.1.dmg</a>"),
+            "/projects/keepass/rss" => {
+                return MockResponse::text(
+                    200,
+                    r#"<rss><channel><item><title>/KeePass 2.x/2.57/KeePass-2.57.zip</title></item></channel></rss>"#,
+                )
+            }
+            "/repos/microsoft/winget-pkgs/contents/manifests/m/Microsoft/PowerToys" => {
+                r#"[{"name":"0.80.1","type":"dir"},{"name":"0.81.0","type":"dir"}]"#
+            }
+            "/api/v1/repos/gitea/tea/releases?limit=1" => {
+                r#"[{"tag_name":"v0.9.2","published_at":"2024-03-01T00:00:00Z"}]"#
+            }
+            "/api/v1/packages/org.fdroid.fdroid" => {
+                r#"{"suggestedVersionName":"1.19.1","suggestedVersionCode":1019050,"packages":[]}"#
+            }
+            "/ggplot2" => r#"{"Version":"3.5.1","date":"2024-04-23T07:00:08+00:00"}"#,
+            "/2.0/repositories/team/tool/refs/tags?sort=-target.date" => {
+                r#"{"values":[{"name":"v0.9.0","target":{"date":"2024-02-01T00:00:00+00:00"}}]}"#
+            }
+            "/rpc/v5/info?arg%5B%5D=yay" => {
+                r#"{"resultcount":1,"results":[{"Version":"12.3.5-1","LastModified":1718064000}]}"#
+            }
+            "/api/v2/appstream/org.mozilla.firefox" => {
+                r#"{"releases":[{"version":"127.0","timestamp":"1718064000"}]}"#
+            }
+            "/api/packages/phoenix" => {
+                r#"{"latest_stable_version":"1.7.14","releases":[{"version":"1.7.14","inserted_at":"2024-06-18T13:24:06.000000Z"}]}"#
+            }
+            "/p2/laravel/framework.json" => {
+                r#"{"packages":{"laravel/framework":[{"version":"v11.9.2","time":"2024-05-30T09:40:11+00:00"}]}}"#
+            }
+            "/package/conda-forge/numpy" => {
+                r#"{"latest_version":"1.26.4","modified_at":"2024-02-06 10:19:39.823000+00:00"}"#
+            }
+            _ if path.starts_with("/1.0/ubuntu/+archive/primary?") => {
+                r#"{"entries":[{"source_package_version":"3.0.13-0ubuntu3.4","date_published":"2024-09-12T08:30:11+00:00"}]}"#
+            }
+            _ if path.starts_with("/solrsearch/select") => {
+                r#"{"response":{"docs":[{"v":"1.9.0","timestamp":1711929600000},{"v":"2.0.1","timestamp":1709251200000}]}}"#
+            }
+            _ if path.starts_with("/v2/repositories/library/nginx/tags") => {
+                r#"{"results":[{"name":"latest","last_updated":null},{"name":"1.27.0","last_updated":"2024-05-29T00:00:00Z"}]}"#
+            }
+            _ => return MockResponse::json(404, "{}"),
+        };
+        MockResponse::json(200, body)
+    }
+
+    #[tokio::test]
+    async fn test_preview_every_source_type() {
+        let server = MockServer::start(registry_response).await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://formulae.brew.sh", server.url())
+            .redirect("https://registry.npmjs.org", server.url())
+            .redirect("https://pypi.org", server.url())
+            .redirect("https://crates.io", server.url())
+            .redirect("https://hub.docker.com", server.url())
+            .redirect("https://rubygems.org", server.url())
+            .redirect("https://api.nuget.org", server.url())
+            .redirect("https://proxy.golang.org", server.url())
+            .redirect("https://api.bitbucket.org", server.url())
+            .redirect("https://search.maven.org", server.url())
+            .redirect("https://api.anaconda.org", server.url())
+            .redirect("https://repo.packagist.org", server.url())
+            .redirect("https://hex.pm", server.url())
+            .redirect("https://flathub.org", server.url())
+            .redirect("https://aur.archlinux.org", server.url())
+            .redirect("https://sourceforge.net", server.url())
+            .redirect("https://crandb.r-pkg.org", server.url())
+            .redirect("https://f-droid.org", server.url())
+            .redirect("https://api.launchpad.net", server.url())
+            .redirect("https://api.github.com", server.url());
+        let settings = AppSettings {
+            github_api_base_url: Some(server.url().to_string()),
+            ..AppSettings::default()
+        };
+
+        let base = server.url();
+        let cases = [
+            (SourceType::GithubRelease, "o/release".to_string(), "v1.2.0"),
+            (SourceType::GithubTags, "o/tags".to_string(), "v1.1.0"),
+            (SourceType::Homebrew, "wget".to_string(), "1.24.5"),
+            (SourceType::HomebrewCask, "firefox".to_string(), "127.0"),
+            (SourceType::Npm, "react".to_string(), "18.3.1"),
+            (SourceType::Pypi, "requests".to_string(), "2.32.3"),
+            (SourceType::Cargo, "serde".to_string(), "1.0.210"),
+            (SourceType::DockerHub, "nginx".to_string(), "1.27.0"),
+            (SourceType::RubyGems, "rails".to_string(), "7.1.3"),
+            (SourceType::NuGet, "Newtonsoft.Json".to_string(), "13.0.3"),
+            (SourceType::GoModule, "golang.org/x/text".to_string(), "v0.14.0"),
+            (SourceType::Bitbucket, "team/tool".to_string(), "v0.9.0"),
+            (SourceType::Maven, "org.example:lib".to_string(), "2.0.1"),
+            (SourceType::Conda, "conda-forge/numpy".to_string(), "1.26.4"),
+            (SourceType::Packagist, "laravel/framework".to_string(), "v11.9.2"),
+            (SourceType::Hex, "phoenix".to_string(), "1.7.14"),
+            (SourceType::Flathub, "org.mozilla.firefox".to_string(), "127.0"),
+            (SourceType::Aur, "yay".to_string(), "12.3.5-1"),
+            (SourceType::SourceForge, "keepass".to_string(), "2.57"),
+            (SourceType::Winget, "Microsoft.PowerToys".to_string(), "0.81.0"),
+            (SourceType::Cran, "ggplot2".to_string(), "3.5.1"),
+            (SourceType::Gitea, format!("{}/gitea/tea", base), "v0.9.2"),
+            (SourceType::Fdroid, "org.fdroid.fdroid".to_string(), "1.19.1"),
+            (SourceType::UbuntuPackage, "noble/openssl".to_string(), "3.0.13-0ubuntu3.4"),
+            (
+                SourceType::CustomJson,
+                format!("{}/custom.json|$.data.version", base),
+                "3.4.5",
+            ),
+            (
+                SourceType::Feed,
+                format!(r"{}/releases.atom|v?(\d+\.\d+\.\d+)", base),
+                "2.0.1",
+            ),
+            (
+                SourceType::WebScrape,
+                format!(r"{}/download|tool-(\d+\.\d+\.\d+)", base),
+                "4.12.1",
+            ),
+        ];
+
+        for (source_type, identifier, expected) in cases {
+            let source = SourceConfig::new(source_type.clone(), identifier);
+            let preview = preview_source(&http, &source, &settings)
+                .await
+                .unwrap_or_else(|e| panic!("{:?} failed: {:?}", source_type, e));
+            assert_eq!(preview.latest_version, expected, "{:?}", source_type);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_source_auth_token_overrides_global_token() {
+        let server = MockServer::start(registry_response).await;
+        let http = HttpClient::new(&NetworkConfig::default()).unwrap();
+        let settings = AppSettings {
+            github_api_base_url: Some(server.url().to_string()),
+            github_token: Some("global-token".to_string()),
+            ..AppSettings::default()
+        };
+        let mut source = SourceConfig {
+            auth_token: Some("org-token".to_string()),
+            ..SourceConfig::new(SourceType::GithubRelease, "o/release")
+        };
+
+        fetch_latest_version(&http, &source, &settings).await.unwrap();
+        source.auth_token = Some("  ".to_string());
+        fetch_latest_version(&http, &source, &settings).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].header("Authorization"), Some("Bearer org-token"));
+        // 空白 token 视为未配置，回退到全局 token
+        assert_eq!(requests[1].header("Authorization"), Some("Bearer global-token"));
+    }
+
+    #[tokio::test]
+    async fn test_preview_reports_errors_without_side_effects() {
+        let server = MockServer::start(registry_response).await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url());
+        let settings = AppSettings::default();
+
+        let missing = SourceConfig::new(SourceType::Npm, "does-not-exist");
+        assert!(preview_source(&http, &missing, &settings).await.is_err());
+
+        let empty = SourceConfig::new(SourceType::Npm, " ");
+        assert!(matches!(
+            preview_source(&http, &empty, &settings).await,
+            Err(ServiceError::Api(_))
+        ));
+        // 空标识符不应发出请求
+        assert_eq!(server.requests().len(), 1);
+
+        let invalid_filter = SourceConfig {
+            version_exclude_regex: Some("(".to_string()),
+            ..SourceConfig::new(SourceType::Npm, "react")
+        };
+        assert!(matches!(
+            preview_source(&http, &invalid_filter, &settings).await,
+            Err(ServiceError::Parse(_))
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+}
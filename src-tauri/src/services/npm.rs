@@ -1,8 +1,12 @@
 use chrono::{DateTime, Utc};
-use reqwest::Client;
 use serde::Deserialize;
 use std::collections::HashMap;
 
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::{is_prerelease, parse_version};
+
 #[derive(Deserialize)]
 struct NpmPackageInfo {
     #[serde(rename = "dist-tags")]
@@ -10,35 +14,79 @@ struct NpmPackageInfo {
     time: Option<HashMap<String, String>>,
 }
 
+/// 默认只看 `latest` 标签；包含预发布版本时取所有 dist-tags 中最高的版本
+fn select_version(dist_tags: &HashMap<String, String>, include_prerelease: bool) -> Option<String> {
+    let latest = dist_tags.get("latest");
+    if !include_prerelease {
+        return latest.cloned();
+    }
+
+    dist_tags
+        .values()
+        .filter_map(|tag| Some((tag, parse_version(tag).into_semver()?)))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(tag, _)| tag)
+        .or(latest)
+        .cloned()
+}
+
+/// 配置了版本筛选时从所有已发布的版本中选取最高的匹配版本，
+/// 不包含预发布版本时有正式版本就忽略预发布版本
+fn select_filtered_version<'a>(
+    versions: impl Iterator<Item = &'a String>,
+    include_prerelease: bool,
+    filter: &VersionFilter,
+) -> Option<String> {
+    let versioned: Vec<_> = versions
+        .filter(|version| filter.matches(version))
+        .filter(|version| filter.matches_channel(is_prerelease(version)))
+        .filter_map(|version| Some((version, parse_version(version).into_semver()?)))
+        .collect();
+    let skip_prerelease =
+        !include_prerelease && versioned.iter().any(|(_, version)| version.pre.is_empty());
+
+    versioned
+        .into_iter()
+        .filter(|(_, version)| !skip_prerelease || version.pre.is_empty())
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(version, _)| version.clone())
+}
+
 /// 获取 npm 包的最新版本
 pub async fn get_latest_version(
+    http: &HttpClient,
     package_name: &str,
-) -> Result<(String, Option<DateTime<Utc>>), String> {
-    let client = Client::new();
+    include_prerelease: bool,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
     let url = format!("https://registry.npmjs.org/{}", package_name);
 
-    let response = client
-        .get(&url)
-        .header("Accept", "application/json")
-        .header("User-Agent", "app-version-gui")
-        .send()
-        .await
-        .map_err(|e| format!("npm request failed: {}", e))?;
+    let request = http.get(&url).header("Accept", "application/json");
+    let response = http.send("npm", request).await?;
 
     if !response.status().is_success() {
-        return Err(format!("npm API error: {}", response.status()));
+        return Err(ServiceError::from_response("npm", &response));
     }
 
     let package_info: NpmPackageInfo = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse npm response: {}", e))?;
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse npm response: {}", e)))?;
 
-    let latest_version = package_info
-        .dist_tags
-        .get("latest")
-        .ok_or("No 'latest' tag found")?
-        .clone();
+    let latest_version = if filter.is_empty() {
+        select_version(&package_info.dist_tags, include_prerelease)
+            .ok_or_else(|| ServiceError::NotFound("No 'latest' tag found".to_string()))?
+    } else {
+        // `time` 中记录了每个已发布版本的发布时间
+        let versions = package_info
+            .time
+            .iter()
+            .flatten()
+            .map(|(version, _)| version);
+        select_filtered_version(versions, include_prerelease, filter).ok_or_else(|| {
+            ServiceError::NotFound("No versions match the version filter".to_string())
+        })?
+    };
 
     let published_at = package_info
         .time
@@ -48,3 +96,100 @@ pub async fn get_latest_version(
 
     Ok((latest_version, published_at))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    const NEXT_JS: &str = r#"{
+        "name": "next",
+        "dist-tags": {"latest": "14.2.3", "canary": "15.0.0-canary.12", "backport": "13.5.6"},
+        "time": {
+            "14.2.3": "2024-04-24T20:00:00.000Z",
+            "15.0.0-canary.12": "2024-05-10T08:00:00.000Z"
+        }
+    }"#;
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://registry.npmjs.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_uses_latest_tag_by_default() {
+        let server = MockServer::start(|_| MockResponse::json(200, NEXT_JS)).await;
+
+        let (version, published_at) = get_latest_version(
+            &test_client(&server),
+            "next",
+            false,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "14.2.3");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-04-24T20:00:00+00:00".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_includes_prerelease_tags_when_enabled() {
+        let server = MockServer::start(|_| MockResponse::json(200, NEXT_JS)).await;
+
+        let (version, _) = get_latest_version(
+            &test_client(&server),
+            "next",
+            true,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "15.0.0-canary.12");
+    }
+
+    #[tokio::test]
+    async fn test_filter_selects_from_all_versions() {
+        let server = MockServer::start(|_| MockResponse::json(200, NEXT_JS)).await;
+        let filter = VersionFilter::from_source(&crate::models::SourceConfig {
+            include_prerelease: true,
+            version_exclude_regex: Some("canary".to_string()),
+            ..crate::models::SourceConfig::new(crate::models::SourceType::Npm, "next")
+        })
+        .unwrap();
+
+        let (version, published_at) =
+            get_latest_version(&test_client(&server), "next", true, &filter)
+                .await
+                .unwrap();
+
+        assert_eq!(version, "14.2.3");
+        assert!(published_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_channel_selects_from_all_versions() {
+        let server = MockServer::start(|_| MockResponse::json(200, NEXT_JS)).await;
+
+        for (channel, expected) in [
+            (ReleaseChannel::Stable, "14.2.3"),
+            (ReleaseChannel::Prerelease, "15.0.0-canary.12"),
+        ] {
+            let (version, _) = get_latest_version(
+                &test_client(&server),
+                "next",
+                true,
+                &VersionFilter::for_channel(channel),
+            )
+            .await
+            .unwrap();
+            assert_eq!(version, expected);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::parse_version;
+
+#[derive(Deserialize)]
+struct NugetVersionIndex {
+    versions: Vec<String>,
+}
+
+/// 从匹配 `filter` 的版本中选出最大的版本，默认只取正式版本，限定了发布渠道时按渠道选取
+fn select_latest<'a>(versions: &'a [String], filter: &VersionFilter) -> Option<&'a String> {
+    versions
+        .iter()
+        .filter(|raw| filter.matches(raw))
+        .filter_map(|raw| parse_version(raw).into_semver().map(|v| (v, raw)))
+        .filter(|(v, _)| {
+            let prerelease = !v.pre.is_empty();
+            if filter.has_channel() {
+                filter.matches_channel(prerelease)
+            } else {
+                !prerelease
+            }
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, raw)| raw)
+}
+
+/// 获取 NuGet 包的最新版本，默认只取正式版本
+///
+/// flat container 接口不提供发布时间
+pub async fn get_latest_version(
+    http: &HttpClient,
+    package: &str,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    // NuGet 的 flat container 只接受小写的包 id
+    let url = format!(
+        "https://api.nuget.org/v3-flatcontainer/{}/index.json",
+        package.trim().to_lowercase()
+    );
+
+    let request = http.get(&url);
+    let response = http.send("NuGet", request).await?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(ServiceError::NotFound(format!(
+            "Package '{}' not found on NuGet",
+            package
+        )));
+    }
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("NuGet", &response));
+    }
+
+    let index: NugetVersionIndex = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse NuGet response: {}", e)))?;
+
+    let latest = select_latest(&index.versions, filter).ok_or_else(|| {
+        let message = if filter.has_channel() {
+            "No versions found in the selected channel"
+        } else if !filter.is_empty() {
+            "No versions match the version filter"
+        } else {
+            "No stable versions found"
+        };
+        ServiceError::NotFound(message.to_string())
+    })?;
+
+    Ok((latest.clone(), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn versions(list: &[&str]) -> Vec<String> {
+        list.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_select_latest_stable_from_unsorted() {
+        let list = versions(&["12.0.3", "13.0.1", "9.0.1", "13.0.3", "13.0.2", "10.0.3"]);
+        assert_eq!(
+            select_latest(&list, &VersionFilter::default()).map(String::as_str),
+            Some("13.0.3")
+        );
+    }
+
+    #[test]
+    fn test_select_latest_stable_skips_prerelease() {
+        let list = versions(&["8.0.0", "9.0.0-preview.1", "8.0.1", "9.0.0-rc.2"]);
+        assert_eq!(
+            select_latest(&list, &VersionFilter::default()).map(String::as_str),
+            Some("8.0.1")
+        );
+
+        let only_pre = versions(&["1.0.0-beta", "1.0.0-rc.1"]);
+        assert_eq!(select_latest(&only_pre, &VersionFilter::default()), None);
+    }
+
+    #[test]
+    fn test_select_latest_by_channel() {
+        let list = versions(&["8.0.0", "9.0.0-preview.1", "8.0.1", "9.0.0-rc.2"]);
+        let stable = VersionFilter::for_channel(ReleaseChannel::Stable);
+        assert_eq!(
+            select_latest(&list, &stable).map(String::as_str),
+            Some("8.0.1")
+        );
+        let prerelease = VersionFilter::for_channel(ReleaseChannel::Prerelease);
+        assert_eq!(
+            select_latest(&list, &prerelease).map(String::as_str),
+            Some("9.0.0-rc.2")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lowercases_package_id() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, r#"{"versions": ["13.0.1", "12.0.3", "13.0.3"]}"#)
+        })
+        .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://api.nuget.org", server.url());
+
+        let (version, published_at) =
+            get_latest_version(&http, "Newtonsoft.Json", &VersionFilter::default())
+                .await
+                .unwrap();
+
+        assert_eq!(version, "13.0.3");
+        assert_eq!(published_at, None);
+        assert_eq!(
+            server.requests()[0].path,
+            "/v3-flatcontainer/newtonsoft.json/index.json"
+        );
+    }
+}
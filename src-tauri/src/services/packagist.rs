@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use semver::Version;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+use crate::version::parse_version;
+
+#[derive(Deserialize)]
+struct PackagistVersion {
+    version: String,
+    time: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PackagistResponse {
+    #[serde(default)]
+    packages: HashMap<String, Vec<PackagistVersion>>,
+}
+
+/// 取最高的稳定版本，跳过 `dev-` 分支和预发布版本
+fn select_latest_stable(versions: Vec<PackagistVersion>) -> Option<PackagistVersion> {
+    let mut latest: Option<(Version, PackagistVersion)> = None;
+
+    for entry in versions {
+        if entry.version.starts_with("dev-") {
+            continue;
+        }
+        let Some(version) = parse_version(&entry.version).into_semver() else {
+            continue;
+        };
+        if !version.pre.is_empty() {
+            continue;
+        }
+
+        let is_newer = match &latest {
+            Some((max, _)) => version > *max,
+            None => true,
+        };
+        if is_newer {
+            latest = Some((version, entry));
+        }
+    }
+
+    latest.map(|(_, entry)| entry)
+}
+
+/// 获取 Packagist 上 Composer 包的最新稳定版本，`package` 为 `vendor/name`
+pub async fn get_latest_version(
+    http: &HttpClient,
+    package: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let package = package.trim().to_lowercase();
+    if package.split('/').count() != 2 || package.split('/').any(str::is_empty) {
+        return Err(ServiceError::Api(format!(
+            "Invalid Packagist package '{}', expected vendor/name",
+            package
+        )));
+    }
+
+    let url = format!("https://repo.packagist.org/p2/{}.json", package);
+    let request = http.get(&url);
+    let response = http.send("Packagist", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Packagist", &response));
+    }
+
+    let mut body: PackagistResponse = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse Packagist response: {}", e)))?;
+
+    let versions = body.packages.remove(&package).unwrap_or_default();
+    let latest = select_latest_stable(versions).ok_or_else(|| {
+        ServiceError::NotFound(format!("No stable release found for {}", package))
+    })?;
+
+    let published_at = latest
+        .time
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    Ok((latest.version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://repo.packagist.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_selects_highest_stable_version() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{
+                    "minified": "composer/2.0",
+                    "packages": {
+                        "monolog/monolog": [
+                            {"version": "dev-main", "time": "2024-06-01T00:00:00+00:00"},
+                            {"version": "4.0.0-RC1", "time": "2024-05-20T00:00:00+00:00"},
+                            {"version": "3.10.0", "time": "2024-05-10T00:00:00+00:00"},
+                            {"version": "2.9.3", "time": "2024-05-15T00:00:00+00:00"},
+                            {"version": "3.9.0", "time": "2024-03-01T00:00:00+00:00"}
+                        ]
+                    }
+                }"#,
+            )
+        })
+        .await;
+
+        let (version, published_at) = get_latest_version(&test_client(&server), "Monolog/Monolog")
+            .await
+            .unwrap();
+
+        assert_eq!(version, "3.10.0");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-05-10T00:00:00+00:00".to_string())
+        );
+        assert_eq!(server.requests()[0].path, "/p2/monolog/monolog.json");
+    }
+
+    #[tokio::test]
+    async fn test_only_dev_versions() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(
+                200,
+                r#"{"packages": {"acme/tool": [{"version": "dev-master"}]}}"#,
+            )
+        })
+        .await;
+
+        let err = get_latest_version(&test_client(&server), "acme/tool")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
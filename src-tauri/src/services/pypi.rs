@@ -1,8 +1,12 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
-use reqwest::Client;
 use serde::Deserialize;
 use std::collections::HashMap;
 
+use super::error::ServiceError;
+use super::filter::VersionFilter;
+use super::http::HttpClient;
+use crate::version::pep440::Pep440Version;
+
 #[derive(Deserialize)]
 struct PyPIInfo {
     version: String,
@@ -19,30 +23,65 @@ struct PyPIPackage {
     releases: Option<HashMap<String, Vec<PyPIRelease>>>,
 }
 
+/// `info.version` 是 PyPI 认定的最新版本，可能是预发布版本；
+/// 不包含预发布版本时从 `releases` 中取最高的正式版本，包含时取所有版本中最高的；
+/// 只在匹配 `filter` 的版本中选取，没有匹配的版本时返回 `None`
+fn select_version(
+    current: String,
+    releases: Option<&HashMap<String, Vec<PyPIRelease>>>,
+    include_prerelease: bool,
+    filter: &VersionFilter,
+) -> Option<String> {
+    let current_is_pre = Pep440Version::parse(&current).is_some_and(|v| v.is_prerelease());
+    let current_matches = filter.matches(&current) && filter.matches_channel(current_is_pre);
+    if !include_prerelease && !current_is_pre && current_matches {
+        return Some(current);
+    }
+    let Some(releases) = releases else {
+        return current_matches.then_some(current);
+    };
+
+    releases
+        .iter()
+        // 没有上传文件的版本已被撤回
+        .filter(|(_, files)| !files.is_empty())
+        .filter(|(version, _)| filter.matches(version))
+        .filter_map(|(version, _)| Some((version, Pep440Version::parse(version)?)))
+        .filter(|(_, parsed)| include_prerelease || !parsed.is_prerelease())
+        .filter(|(_, parsed)| filter.matches_channel(parsed.is_prerelease()))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(version, _)| version.clone())
+        .or_else(|| current_matches.then_some(current))
+}
+
 /// 获取 PyPI 包的最新版本
 pub async fn get_latest_version(
+    http: &HttpClient,
     package_name: &str,
-) -> Result<(String, Option<DateTime<Utc>>), String> {
-    let client = Client::new();
+    include_prerelease: bool,
+    filter: &VersionFilter,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
     let url = format!("https://pypi.org/pypi/{}/json", package_name);
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "app-version-gui")
-        .send()
-        .await
-        .map_err(|e| format!("PyPI request failed: {}", e))?;
+    let request = http.get(&url);
+    let response = http.send("PyPI", request).await?;
 
     if !response.status().is_success() {
-        return Err(format!("PyPI API error: {}", response.status()));
+        return Err(ServiceError::from_response("PyPI", &response));
     }
 
     let package: PyPIPackage = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse PyPI response: {}", e))?;
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse PyPI response: {}", e)))?;
 
-    let latest_version = package.info.version;
+    let latest_version = select_version(
+        package.info.version,
+        package.releases.as_ref(),
+        include_prerelease,
+        filter,
+    )
+    .ok_or_else(|| ServiceError::NotFound("No versions match the version filter".to_string()))?;
 
     // PyPI 使用的时间格式是 "2024-01-15T10:30:00"（不带时区）
     let published_at = package
@@ -65,3 +104,99 @@ pub async fn get_latest_version(
 
     Ok((latest_version, published_at))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NetworkConfig, ReleaseChannel};
+    use crate::test_support::{MockResponse, MockServer};
+
+    const DJANGO: &str = r#"{
+        "info": {"version": "5.1rc1"},
+        "releases": {
+            "5.0.6": [{"upload_time": "2024-05-07T10:00:00"}],
+            "5.1rc1": [{"upload_time": "2024-07-23T10:00:00"}],
+            "5.1.dev1": [{"upload_time": "2024-06-01T10:00:00"}],
+            "5.0.10": []
+        }
+    }"#;
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://pypi.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_skips_prerelease_by_default() {
+        let server = MockServer::start(|_| MockResponse::json(200, DJANGO)).await;
+
+        let (version, published_at) = get_latest_version(
+            &test_client(&server),
+            "django",
+            false,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "5.0.6");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-05-07T10:00:00+00:00".to_string())
+        );
+        assert_eq!(server.requests()[0].path, "/pypi/django/json");
+    }
+
+    #[tokio::test]
+    async fn test_includes_prerelease_when_enabled() {
+        let server = MockServer::start(|_| MockResponse::json(200, DJANGO)).await;
+
+        let (version, _) = get_latest_version(
+            &test_client(&server),
+            "django",
+            true,
+            &VersionFilter::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "5.1rc1");
+    }
+
+    #[tokio::test]
+    async fn test_applies_version_filter() {
+        let server = MockServer::start(|_| MockResponse::json(200, DJANGO)).await;
+        let filter = VersionFilter::from_source(&crate::models::SourceConfig {
+            include_prerelease: true,
+            version_filter_regex: Some(r"^5\.0\.".to_string()),
+            ..crate::models::SourceConfig::new(crate::models::SourceType::Pypi, "django")
+        })
+        .unwrap();
+
+        let (version, _) = get_latest_version(&test_client(&server), "django", true, &filter)
+            .await
+            .unwrap();
+        assert_eq!(version, "5.0.6");
+    }
+
+    #[tokio::test]
+    async fn test_channel() {
+        let server = MockServer::start(|_| MockResponse::json(200, DJANGO)).await;
+
+        for (channel, expected) in [
+            (ReleaseChannel::Stable, "5.0.6"),
+            (ReleaseChannel::Prerelease, "5.1rc1"),
+        ] {
+            let (version, _) = get_latest_version(
+                &test_client(&server),
+                "django",
+                true,
+                &VersionFilter::for_channel(channel),
+            )
+            .await
+            .unwrap();
+            assert_eq!(version, expected);
+        }
+    }
+}
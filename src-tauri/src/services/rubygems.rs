@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+#[derive(Deserialize)]
+struct GemLatestVersion {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct GemInfo {
+    version: String,
+    version_created_at: Option<String>,
+}
+
+fn gem_not_found(gem: &str) -> ServiceError {
+    ServiceError::NotFound(format!("Gem '{}' not found on RubyGems", gem))
+}
+
+/// 获取 gem 的最新版本
+pub async fn get_latest_version(
+    http: &HttpClient,
+    gem: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let url = format!("https://rubygems.org/api/v1/versions/{}/latest.json", gem);
+
+    let request = http.get(&url);
+    let response = http.send("RubyGems", request).await?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(gem_not_found(gem));
+    }
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("RubyGems", &response));
+    }
+
+    let latest: GemLatestVersion = response
+        .json()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to parse RubyGems response: {}", e)))?;
+
+    // 不存在的 gem 会返回 200 和 `{"version": "unknown"}`
+    if latest.version == "unknown" {
+        return Err(gem_not_found(gem));
+    }
+
+    let published_at = get_published_at(http, gem, &latest.version).await;
+
+    Ok((latest.version, published_at))
+}
+
+/// 从 gem 详情中读取最新版本的发布时间，失败时返回 None
+async fn get_published_at(http: &HttpClient, gem: &str, version: &str) -> Option<DateTime<Utc>> {
+    let url = format!("https://rubygems.org/api/v1/gems/{}.json", gem);
+
+    let request = http.get(&url);
+    let response = http.send("RubyGems", request).await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+
+    let info: GemInfo = response.json().await.ok()?;
+    if info.version != version {
+        return None;
+    }
+
+    info.version_created_at
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn test_client(server: &MockServer) -> HttpClient {
+        HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://rubygems.org", server.url())
+    }
+
+    #[tokio::test]
+    async fn test_latest_version_with_date() {
+        let server = MockServer::start(|request| {
+            if request.path.ends_with("/latest.json") {
+                MockResponse::json(200, r#"{"version": "7.1.3"}"#)
+            } else {
+                MockResponse::json(
+                    200,
+                    r#"{"name": "rails", "version": "7.1.3",
+                        "version_created_at": "2024-01-16T21:58:30.000Z"}"#,
+                )
+            }
+        })
+        .await;
+
+        let (version, published_at) = get_latest_version(&test_client(&server), "rails")
+            .await
+            .unwrap();
+
+        assert_eq!(version, "7.1.3");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-01-16T21:58:30+00:00".to_string())
+        );
+        assert_eq!(
+            server.requests()[0].path,
+            "/api/v1/versions/rails/latest.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_gem() {
+        let server =
+            MockServer::start(|_| MockResponse::json(200, r#"{"version": "unknown"}"#)).await;
+
+        let err = get_latest_version(&test_client(&server), "no-such-gem")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ServiceError::NotFound("Gem 'no-such-gem' not found on RubyGems".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gem_404() {
+        let server =
+            MockServer::start(|_| MockResponse::text(404, "This rubygem could not be found."))
+                .await;
+
+        let err = get_latest_version(&test_client(&server), "no-such-gem")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
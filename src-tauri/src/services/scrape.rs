@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+use super::error::ServiceError;
+use super::http::HttpClient;
+
+/// 允许下载的最大页面大小
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// 拆分 `URL|正则` 形式的标识符，正则本身可以包含 `|`
+pub fn split_spec(spec: &str) -> Result<(&str, &str), ServiceError> {
+    spec.split_once('|')
+        .map(|(url, pattern)| (url.trim(), pattern.trim()))
+        .filter(|(url, pattern)| !url.is_empty() && !pattern.is_empty())
+        .ok_or_else(|| {
+            ServiceError::Parse(format!(
+                "Invalid web scrape source '{}', expected 'URL|regex'",
+                spec
+            ))
+        })
+}
+
+/// 用正则从页面中提取版本号，返回第一个捕获组
+fn extract_version(body: &str, pattern: &str) -> Result<String, ServiceError> {
+    let regex = Regex::new(pattern)
+        .map_err(|e| ServiceError::Parse(format!("Invalid version pattern: {}", e)))?;
+
+    regex
+        .captures(body)
+        .and_then(|captures| captures.get(1))
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|version| !version.is_empty())
+        .ok_or_else(|| {
+            ServiceError::NotFound(format!("No match for pattern '{}' on page", pattern))
+        })
+}
+
+/// 下载页面内容，超过大小限制时报错
+async fn read_body(mut response: reqwest::Response) -> Result<String, ServiceError> {
+    let too_large = || {
+        ServiceError::Parse(format!(
+            "Page exceeds the {} KB size limit",
+            MAX_BODY_BYTES / 1024
+        ))
+    };
+
+    if response
+        .content_length()
+        .is_some_and(|len| len as usize > MAX_BODY_BYTES)
+    {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| ServiceError::Network(format!("Failed to read page: {}", e)))?
+    {
+        if body.len() + chunk.len() > MAX_BODY_BYTES {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// 从网页中抓取最新版本
+pub async fn get_latest_version(
+    http: &HttpClient,
+    url: &str,
+    pattern: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let request = http.get(url);
+    let response = http.send("Web page", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("Web page", &response));
+    }
+
+    let body = read_body(response).await?;
+    let version = extract_version(&body, pattern)?;
+
+    Ok((version, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    const PAGE: &str = r#"<html><body>
+        <h1>Download Tool</h1>
+        <p class="version">Current version: <strong>4.12.1</strong> (released 2024-05-02)</p>
+        <a href="/files/tool-4.12.1.dmg">macOS</a>
+    </body></html>"#;
+
+    #[test]
+    fn test_split_spec() {
+        assert_eq!(
+            split_spec("https://example.com/download | tool-(\\d+\\.\\d+)|x").unwrap(),
+            ("https://example.com/download", "tool-(\\d+\\.\\d+)|x")
+        );
+        assert!(split_spec("https://example.com/download").is_err());
+        assert!(split_spec("https://example.com/download|").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_from_static_html() {
+        let server = MockServer::start(|_| {
+            MockResponse::text(200, PAGE).with_header("Content-Type", "text/html")
+        })
+        .await;
+        let url = format!("{}/download", server.url());
+
+        let (version, published_at) = get_latest_version(
+            &HttpClient::new(&NetworkConfig::default()).unwrap(),
+            &url,
+            r"Current version: <strong>([\d.]+)</strong>",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "4.12.1");
+        assert_eq!(published_at, None);
+    }
+
+    #[test]
+    fn test_pattern_does_not_match() {
+        let err = extract_version(PAGE, r"Latest: v([\d.]+)").unwrap_err();
+        assert_eq!(
+            err,
+            ServiceError::NotFound("No match for pattern 'Latest: v([\\d.]+)' on page".to_string())
+        );
+
+        // 没有捕获组时同样视为未匹配
+        assert!(matches!(
+            extract_version(PAGE, r"\d+\.\d+\.\d+"),
+            Err(ServiceError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_page_too_large() {
+        let server =
+            MockServer::start(|_| MockResponse::text(200, "a".repeat(MAX_BODY_BYTES + 1))).await;
+
+        let err = get_latest_version(
+            &HttpClient::new(&NetworkConfig::default()).unwrap(),
+            server.url(),
+            r"(\d+)",
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::Parse(_)));
+    }
+}
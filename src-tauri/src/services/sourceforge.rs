@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::error::ServiceError;
+use super::feed::{parse_feed_date, parse_first_entry};
+use super::http::HttpClient;
+
+fn version_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"\d+(?:\.\d+)+(?:[-_.]?(?:alpha|beta|rc|a|b)\d*\b)?").expect("valid regex")
+    })
+}
+
+/// 从文件路径中提取版本号，如 `/KeePass 2.x/2.57/KeePass-2.57-Setup.exe`
+///
+/// 优先取最深一层完全是版本号的目录，没有时从文件名中查找
+fn extract_version(path: &str) -> Option<String> {
+    let mut segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let file_name = segments.pop()?;
+
+    let directory = segments.iter().rev().find_map(|segment| {
+        let segment = segment.trim_start_matches(['v', 'V']);
+        version_regex()
+            .find(segment)
+            .filter(|m| m.start() == 0 && m.end() == segment.len())
+            .map(|m| m.as_str().to_string())
+    });
+
+    directory.or_else(|| {
+        version_regex()
+            .find(file_name)
+            .map(|m| m.as_str().to_string())
+    })
+}
+
+/// 获取 SourceForge 项目最新上传文件的版本，`project` 为项目短名
+pub async fn get_latest_version(
+    http: &HttpClient,
+    project: &str,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let project = project.trim();
+    let url = format!("https://sourceforge.net/projects/{}/rss", project);
+
+    let request = http.get(&url);
+    let response = http.send("SourceForge", request).await?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::from_response("SourceForge", &response));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| ServiceError::Parse(format!("Failed to read SourceForge feed: {}", e)))?;
+
+    // 订阅按上传时间倒序，第一条即最新的文件
+    let entry = parse_first_entry(&body)?
+        .ok_or_else(|| ServiceError::NotFound(format!("No files found for {}", project)))?;
+    let version = extract_version(entry.title.trim()).ok_or_else(|| {
+        ServiceError::NotFound(format!("No version found in '{}'", entry.title.trim()))
+    })?;
+    let published_at = entry.date.as_deref().and_then(parse_feed_date);
+
+    Ok((version, published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    const KEEPASS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<rss xmlns:files="https://sourceforge.net/api/files.rdf#" version="2.0">
+  <channel>
+    <title>KeePass</title>
+    <item>
+      <title><![CDATA[/KeePass 2.x/2.57/KeePass-2.57-Setup.exe]]></title>
+      <pubDate>Tue, 09 Jul 2024 13:45:02 UT</pubDate>
+    </item>
+    <item>
+      <title><![CDATA[/KeePass 2.x/2.56/KeePass-2.56.zip]]></title>
+      <pubDate>Sun, 04 Feb 2024 09:00:00 UT</pubDate>
+    </item>
+  </channel>
+</rss>"#;
+
+    #[test]
+    fn test_extract_version() {
+        assert_eq!(
+            extract_version("/KeePass 2.x/2.57/KeePass-2.57-Setup.exe").as_deref(),
+            Some("2.57")
+        );
+        assert_eq!(
+            extract_version("/tool/v1.4.0-rc2/tool.tar.gz").as_deref(),
+            Some("1.4.0-rc2")
+        );
+        assert_eq!(
+            extract_version("/tool-3.2.1-win64.zip").as_deref(),
+            Some("3.2.1")
+        );
+        assert_eq!(extract_version("/README.md"), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|_| MockResponse::text(200, KEEPASS)).await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect("https://sourceforge.net", server.url());
+
+        let (version, published_at) = get_latest_version(&http, "keepass").await.unwrap();
+
+        assert_eq!(version, "2.57");
+        assert_eq!(
+            published_at.map(|dt| dt.to_rfc3339()),
+            Some("2024-07-09T13:45:02+00:00".to_string())
+        );
+        assert_eq!(server.requests()[0].path, "/projects/keepass/rss");
+    }
+}
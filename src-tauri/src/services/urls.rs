@@ -0,0 +1,313 @@
+use super::{conda, dockerhub, gitea, github, launchpad, maven, scrape, winget};
+use crate::models::{SourceConfig, SourceType};
+
+/// 由 GitHub API 地址得到网页地址：`https://host/api/v3` 对应 `https://host`，
+/// `https://api.host` 对应 `https://host`
+fn github_web_base_url(api_base_url: &str) -> String {
+    let api_base_url = api_base_url.trim().trim_end_matches('/');
+    if api_base_url == github::DEFAULT_API_BASE_URL {
+        return "https://github.com".to_string();
+    }
+    if let Some(host) = api_base_url.strip_suffix("/api/v3") {
+        return host.to_string();
+    }
+    match api_base_url.split_once("://api.") {
+        Some((scheme, host)) => format!("{}://{}", scheme, host),
+        None => api_base_url.to_string(),
+    }
+}
+
+/// 数据源对应的发布页面地址，点击更新通知或"打开发布页面"时打开
+///
+/// GitHub 数据源按 `github_api_base_url` 对应的网页地址生成，以支持 GitHub Enterprise；
+/// 自定义 JSON 和订阅源的地址是接口而不是网页，Git 仓库地址可能是 SSH 地址，返回 `None`
+pub fn release_url(source: &SourceConfig, github_api_base_url: &str) -> Option<String> {
+    let id = source.identifier.trim();
+    if id.is_empty() {
+        return None;
+    }
+
+    let url = match source.source_type {
+        SourceType::GithubRelease => {
+            let web_base_url = github_web_base_url(github_api_base_url);
+            format!("{}/{}/releases", web_base_url, id)
+        }
+        SourceType::GithubTags => {
+            let web_base_url = github_web_base_url(github_api_base_url);
+            format!("{}/{}/tags", web_base_url, id)
+        }
+        SourceType::Homebrew => format!("https://formulae.brew.sh/formula/{}", id),
+        SourceType::HomebrewCask => format!("https://formulae.brew.sh/cask/{}", id),
+        SourceType::Npm => format!("https://www.npmjs.com/package/{}", id),
+        SourceType::Pypi => format!("https://pypi.org/project/{}/", id),
+        SourceType::Cargo => format!("https://crates.io/crates/{}", id),
+        SourceType::DockerHub => {
+            let image = dockerhub::normalize_image(id);
+            match image.strip_prefix("library/") {
+                Some(official) => format!("https://hub.docker.com/_/{}", official),
+                None => format!("https://hub.docker.com/r/{}", image),
+            }
+        }
+        SourceType::RubyGems => format!("https://rubygems.org/gems/{}", id),
+        SourceType::NuGet => format!("https://www.nuget.org/packages/{}", id),
+        SourceType::GoModule => format!("https://pkg.go.dev/{}", id),
+        SourceType::Bitbucket => format!("https://bitbucket.org/{}/downloads/?tab=tags", id),
+        SourceType::Maven => {
+            let (group, artifact) = maven::parse_coordinates(id).ok()?;
+            format!(
+                "https://central.sonatype.com/artifact/{}/{}",
+                group, artifact
+            )
+        }
+        SourceType::Conda => {
+            let (channel, package) = conda::parse_spec(id).ok()?;
+            format!("https://anaconda.org/{}/{}", channel, package)
+        }
+        SourceType::Packagist => format!("https://packagist.org/packages/{}", id),
+        SourceType::Hex => format!("https://hex.pm/packages/{}", id),
+        SourceType::Flathub => format!("https://flathub.org/apps/{}", id),
+        SourceType::Aur => format!("https://aur.archlinux.org/packages/{}", id),
+        SourceType::SourceForge => format!("https://sourceforge.net/projects/{}/files/", id),
+        SourceType::Winget => format!(
+            "https://github.com/microsoft/winget-pkgs/tree/master/{}",
+            winget::manifest_path(id).ok()?
+        ),
+        SourceType::Cran => format!("https://cran.r-project.org/package={}", id),
+        SourceType::Gitea => {
+            let (base_url, owner_repo) = gitea::parse_identifier(id).ok()?;
+            format!("{}/{}/releases", base_url, owner_repo)
+        }
+        SourceType::Fdroid => format!("https://f-droid.org/packages/{}/", id),
+        SourceType::UbuntuPackage => match launchpad::parse_spec(id).ok()? {
+            launchpad::PackageSpec {
+                ppa: Some((owner, name)),
+                ..
+            } => format!("https://launchpad.net/~{}/+archive/ubuntu/{}", owner, name),
+            launchpad::PackageSpec { package, .. } => {
+                format!("https://launchpad.net/ubuntu/+source/{}", package)
+            }
+        },
+        SourceType::WebScrape => scrape::split_spec(id).ok()?.0.trim().to_string(),
+        SourceType::CustomJson | SourceType::Feed | SourceType::GitTags => return None,
+    };
+    Some(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(source_type: SourceType, identifier: &str) -> Option<String> {
+        release_url(
+            &SourceConfig::new(source_type, identifier),
+            github::DEFAULT_API_BASE_URL,
+        )
+    }
+
+    #[test]
+    fn test_release_url() {
+        let cases = [
+            (
+                SourceType::GithubRelease,
+                "tauri-apps/tauri",
+                "https://github.com/tauri-apps/tauri/releases",
+            ),
+            (
+                SourceType::GithubTags,
+                "owner/repo",
+                "https://github.com/owner/repo/tags",
+            ),
+            (
+                SourceType::Homebrew,
+                "wget",
+                "https://formulae.brew.sh/formula/wget",
+            ),
+            (
+                SourceType::HomebrewCask,
+                "firefox",
+                "https://formulae.brew.sh/cask/firefox",
+            ),
+            (
+                SourceType::Npm,
+                "@types/node",
+                "https://www.npmjs.com/package/@types/node",
+            ),
+            (
+                SourceType::Pypi,
+                "requests",
+                "https://pypi.org/project/requests/",
+            ),
+            (SourceType::Cargo, "serde", "https://crates.io/crates/serde"),
+            (
+                SourceType::DockerHub,
+                "nginx",
+                "https://hub.docker.com/_/nginx",
+            ),
+            (
+                SourceType::DockerHub,
+                "grafana/grafana:latest",
+                "https://hub.docker.com/r/grafana/grafana",
+            ),
+            (
+                SourceType::RubyGems,
+                "rails",
+                "https://rubygems.org/gems/rails",
+            ),
+            (
+                SourceType::NuGet,
+                "Newtonsoft.Json",
+                "https://www.nuget.org/packages/Newtonsoft.Json",
+            ),
+            (
+                SourceType::GoModule,
+                "golang.org/x/net",
+                "https://pkg.go.dev/golang.org/x/net",
+            ),
+            (
+                SourceType::Bitbucket,
+                "team/tool",
+                "https://bitbucket.org/team/tool/downloads/?tab=tags",
+            ),
+            (
+                SourceType::Maven,
+                "com.google.guava:guava",
+                "https://central.sonatype.com/artifact/com.google.guava/guava",
+            ),
+            (
+                SourceType::Conda,
+                "numpy",
+                "https://anaconda.org/conda-forge/numpy",
+            ),
+            (
+                SourceType::Conda,
+                "bioconda/samtools",
+                "https://anaconda.org/bioconda/samtools",
+            ),
+            (
+                SourceType::Packagist,
+                "laravel/framework",
+                "https://packagist.org/packages/laravel/framework",
+            ),
+            (
+                SourceType::Hex,
+                "phoenix",
+                "https://hex.pm/packages/phoenix",
+            ),
+            (
+                SourceType::Flathub,
+                "org.mozilla.firefox",
+                "https://flathub.org/apps/org.mozilla.firefox",
+            ),
+            (
+                SourceType::Aur,
+                "yay",
+                "https://aur.archlinux.org/packages/yay",
+            ),
+            (
+                SourceType::SourceForge,
+                "keepass",
+                "https://sourceforge.net/projects/keepass/files/",
+            ),
+            (
+                SourceType::Winget,
+                "Microsoft.PowerToys",
+                "https://github.com/microsoft/winget-pkgs/tree/master/manifests/m/Microsoft/PowerToys",
+            ),
+            (
+                SourceType::Cran,
+                "ggplot2",
+                "https://cran.r-project.org/package=ggplot2",
+            ),
+            (
+                SourceType::Gitea,
+                "https://codeberg.org/forgejo/forgejo",
+                "https://codeberg.org/forgejo/forgejo/releases",
+            ),
+            (
+                SourceType::Fdroid,
+                "org.fdroid.fdroid",
+                "https://f-droid.org/packages/org.fdroid.fdroid/",
+            ),
+            (
+                SourceType::UbuntuPackage,
+                "noble/openssl",
+                "https://launchpad.net/ubuntu/+source/openssl",
+            ),
+            (
+                SourceType::UbuntuPackage,
+                "ppa:mozillateam/ppa/jammy/firefox",
+                "https://launchpad.net/~mozillateam/+archive/ubuntu/ppa",
+            ),
+            (
+                SourceType::WebScrape,
+                "https://example.com/download | tool-(\\d+)",
+                "https://example.com/download",
+            ),
+        ];
+        for (source_type, identifier, expected) in cases {
+            assert_eq!(
+                url(source_type, identifier).as_deref(),
+                Some(expected),
+                "{}",
+                identifier
+            );
+        }
+    }
+
+    #[test]
+    fn test_github_enterprise_release_url() {
+        let source = SourceConfig::new(SourceType::GithubRelease, "corp/tool");
+        for (api_base_url, expected) in [
+            (
+                "https://ghe.corp.example.com/api/v3",
+                "https://ghe.corp.example.com/corp/tool/releases",
+            ),
+            (
+                "https://ghe.corp.example.com/api/v3/",
+                "https://ghe.corp.example.com/corp/tool/releases",
+            ),
+            (
+                "https://api.corp.ghe.com",
+                "https://corp.ghe.com/corp/tool/releases",
+            ),
+            (
+                "https://api.github.com/",
+                "https://github.com/corp/tool/releases",
+            ),
+        ] {
+            assert_eq!(
+                release_url(&source, api_base_url).as_deref(),
+                Some(expected),
+                "{}",
+                api_base_url
+            );
+        }
+
+        let tags = SourceConfig::new(SourceType::GithubTags, "corp/tool");
+        assert_eq!(
+            release_url(&tags, "https://ghe.corp.example.com/api/v3").as_deref(),
+            Some("https://ghe.corp.example.com/corp/tool/tags")
+        );
+    }
+
+    #[test]
+    fn test_release_url_unavailable() {
+        assert_eq!(url(SourceType::Npm, " "), None);
+        assert_eq!(url(SourceType::Maven, "guava"), None);
+        assert_eq!(
+            url(SourceType::CustomJson, "https://example.com/api|$.version"),
+            None
+        );
+        assert_eq!(
+            url(SourceType::Feed, "https://example.com/releases.atom"),
+            None
+        );
+        assert_eq!(
+            url(
+                SourceType::GitTags,
+                "https://git.kernel.org/pub/scm/git/git.git"
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+
+use super::error::ServiceError;
+use super::github;
+use super::http::HttpClient;
+
+/// winget 社区清单仓库
+const WINGET_PKGS_REPO: &str = "microsoft/winget-pkgs";
+
+/// 包标识对应的清单目录，如 `Microsoft.PowerToys` -> `manifests/m/Microsoft/PowerToys`
+pub(crate) fn manifest_path(package_id: &str) -> Result<String, ServiceError> {
+    let package_id = package_id.trim();
+    let segments: Vec<&str> = package_id.split('.').collect();
+    if segments.len() < 2 || segments.iter().any(|segment| segment.is_empty()) {
+        return Err(ServiceError::Api(format!(
+            "Invalid winget package '{}', expected Publisher.Package",
+            package_id
+        )));
+    }
+
+    let initial = package_id
+        .chars()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    Ok(format!("manifests/{}/{}", initial, segments.join("/")))
+}
+
+/// 按数字逐段比较的版本，winget 常见 `1.2.3.4` 这样的四段版本
+fn version_key(name: &str) -> Option<Vec<u64>> {
+    name.trim_start_matches(['v', 'V'])
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// 从版本目录中选出最大的版本，无法解析的目录（如子包）会被跳过
+fn select_latest_version(directories: &[String]) -> Option<&String> {
+    directories
+        .iter()
+        .filter_map(|name| Some((name, version_key(name)?)))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(name, _)| name)
+}
+
+/// 获取 winget 包的最新版本，发布时间取该版本清单目录最后一次提交的时间
+pub async fn get_latest_version(
+    http: &HttpClient,
+    package_id: &str,
+    github_token: Option<&str>,
+) -> Result<(String, Option<DateTime<Utc>>), ServiceError> {
+    let path = manifest_path(package_id)?;
+    let directories = github::list_directories(
+        http,
+        github::DEFAULT_API_BASE_URL,
+        WINGET_PKGS_REPO,
+        &path,
+        github_token,
+    )
+    .await?;
+
+    let latest = select_latest_version(&directories).ok_or_else(|| {
+        ServiceError::NotFound(format!("No versions found for {}", package_id.trim()))
+    })?;
+
+    let published_at = github::get_file_last_commit_date(
+        http,
+        github::DEFAULT_API_BASE_URL,
+        WINGET_PKGS_REPO,
+        &format!("{}/{}", path, latest),
+        github_token,
+    )
+    .await;
+
+    Ok((latest.clone(), published_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NetworkConfig;
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[test]
+    fn test_manifest_path() {
+        assert_eq!(
+            manifest_path("Microsoft.PowerToys").unwrap(),
+            "manifests/m/Microsoft/PowerToys"
+        );
+        assert_eq!(
+            manifest_path(" Microsoft.VisualStudio.2022.Community ").unwrap(),
+            "manifests/m/Microsoft/VisualStudio/2022/Community"
+        );
+        assert!(manifest_path("PowerToys").is_err());
+        assert!(manifest_path("Microsoft..PowerToys").is_err());
+    }
+
+    #[test]
+    fn test_select_latest_version() {
+        let directories: Vec<String> = ["0.9.1", "0.81.0", "0.81.0.1", "0.80.1", "Preview"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        assert_eq!(select_latest_version(&directories).unwrap(), "0.81.0.1");
+        assert!(select_latest_version(&["Preview".to_string()]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_version() {
+        let server = MockServer::start(|req| {
+            if req
+                .path
+                .starts_with("/repos/microsoft/winget-pkgs/contents/")
+            {
+                MockResponse::json(
+                    200,
+                    r#"[
+                        {"name": "0.80.1", "type": "dir"},
+                        {"name": "0.81.0", "type": "dir"},
+                        {"name": "0.9.1", "type": "dir"},
+                        {"name": "README.md", "type": "file"}
+                    ]"#,
+                )
+            } else {
+                MockResponse::json(
+                    200,
+                    r#"[{"commit": {"author": {"date": "2024-05-29T10:00:00Z"}}}]"#,
+                )
+            }
+        })
+        .await;
+        let http = HttpClient::new(&NetworkConfig::default())
+            .unwrap()
+            .redirect(github::DEFAULT_API_BASE_URL, server.url());
+
+        let (version, published_at) = get_latest_version(&http, "Microsoft.PowerToys", Some("t"))
+            .await
+            .unwrap();
+        assert_eq!(version, "0.81.0");
+        assert!(published_at.is_some());
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].path,
+            "/repos/microsoft/winget-pkgs/contents/manifests/m/Microsoft/PowerToys"
+        );
+        assert_eq!(requests[0].header("authorization"), Some("Bearer t"));
+        assert!(requests[1]
+            .path
+            .contains("path=manifests%2Fm%2FMicrosoft%2FPowerToys%2F0.81.0"));
+    }
+}
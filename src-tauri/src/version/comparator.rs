@@ -1,4 +1,10 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
 use super::parser::{parse_version, ParsedVersion};
+use super::pep440::Pep440Version;
+use crate::models::{NonSemverStrategy, SourceType};
 
 /// 版本比较结果
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,10 +19,184 @@ pub enum VersionComparison {
     Unknown,
 }
 
-/// 智能版本比较
+impl From<Ordering> for VersionComparison {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Greater => VersionComparison::Greater,
+            Ordering::Equal => VersionComparison::Equal,
+            Ordering::Less => VersionComparison::Less,
+        }
+    }
+}
+
+/// dpkg 的修订号比较：非数字部分逐字符比较（`~` 最小），数字部分按数值比较
+fn compare_debian_revision(a: &str, b: &str) -> Ordering {
+    fn order(c: Option<u8>) -> i32 {
+        match c {
+            None => 0,
+            Some(b'~') => -1,
+            Some(c) if c.is_ascii_digit() => 0,
+            Some(c) if c.is_ascii_alphabetic() => c as i32,
+            Some(c) => c as i32 + 256,
+        }
+    }
+
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let is_digit = |s: &[u8], i: usize| s.get(i).is_some_and(u8::is_ascii_digit);
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() || j < b.len() {
+        while (i < a.len() && !a[i].is_ascii_digit()) || (j < b.len() && !b[j].is_ascii_digit()) {
+            let (ac, bc) = (order(a.get(i).copied()), order(b.get(j).copied()));
+            if ac != bc {
+                return ac.cmp(&bc);
+            }
+            i += 1;
+            j += 1;
+        }
+
+        while a.get(i) == Some(&b'0') {
+            i += 1;
+        }
+        while b.get(j) == Some(&b'0') {
+            j += 1;
+        }
+
+        let mut first_diff = Ordering::Equal;
+        while is_digit(a, i) && is_digit(b, j) {
+            if first_diff == Ordering::Equal {
+                first_diff = a[i].cmp(&b[j]);
+            }
+            i += 1;
+            j += 1;
+        }
+        if is_digit(a, i) {
+            return Ordering::Greater;
+        }
+        if is_digit(b, j) {
+            return Ordering::Less;
+        }
+        if first_diff != Ordering::Equal {
+            return first_diff;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// 任一方为 Debian 风格版本时按 epoch、上游版本、修订号依次比较
+fn compare_debian(latest: &ParsedVersion, local: &ParsedVersion) -> Option<Ordering> {
+    if !matches!(latest, ParsedVersion::Debian { .. })
+        && !matches!(local, ParsedVersion::Debian { .. })
+    {
+        return None;
+    }
+
+    let key = |parsed: &ParsedVersion| match parsed {
+        ParsedVersion::Debian {
+            epoch,
+            upstream,
+            revision,
+        } => Some((*epoch, upstream.clone(), revision.clone().unwrap_or_default())),
+        other => other.clone().into_semver().map(|v| (0, v, String::new())),
+    };
+    let (l_epoch, l_upstream, l_revision) = key(latest)?;
+    let (r_epoch, r_upstream, r_revision) = key(local)?;
+
+    Some(
+        l_epoch
+            .cmp(&r_epoch)
+            .then_with(|| l_upstream.cmp(&r_upstream))
+            .then_with(|| compare_debian_revision(&l_revision, &r_revision)),
+    )
+}
+
+/// 四段版本的比较键，三段的 semver 补第四段为 0；第二项为是否为正式版本
+fn four_part_key(parsed: &ParsedVersion) -> Option<([u64; 4], bool)> {
+    match parsed {
+        ParsedVersion::FourPart(parts) => Some((*parts, true)),
+        ParsedVersion::Semantic(v) => Some(([v.major, v.minor, v.patch, 0], v.pre.is_empty())),
+        _ => None,
+    }
+}
+
+/// 任一方为四段版本时逐段比较，前四段相同时预发布版本较旧
+fn compare_four_part(latest: &ParsedVersion, local: &ParsedVersion) -> Option<Ordering> {
+    if !matches!(latest, ParsedVersion::FourPart(_))
+        && !matches!(local, ParsedVersion::FourPart(_))
+    {
+        return None;
+    }
+
+    let (l_parts, l_release) = four_part_key(latest)?;
+    let (r_parts, r_release) = four_part_key(local)?;
+    Some(l_parts.cmp(&r_parts).then(l_release.cmp(&r_release)))
+}
+
+/// 解析日期形式的版本，如 `2024-01-15`、`2024.01.15`、`2024/01/15`、`20240115`
 ///
-/// 优先使用 semver 进行语义化比较，对于非标准版本格式 fallback 到字符串比较
+/// 同一个日期在不同数据源中写法不同，会被解析成不同类型的版本
+fn parse_date_version(cleaned: &str) -> Option<NaiveDate> {
+    let all_digits = cleaned.chars().all(|c| c.is_ascii_digit());
+    let (year, month, day) = if cleaned.len() == 8 && all_digits {
+        (&cleaned[..4], &cleaned[4..6], &cleaned[6..])
+    } else {
+        let parts: Vec<&str> = cleaned.split(['-', '.', '/']).collect();
+        let [year, month, day] = parts.as_slice() else {
+            return None;
+        };
+        if year.len() != 4 {
+            return None;
+        }
+        (*year, *month, *day)
+    };
+    NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+}
+
+/// 按书写的数字逐段转换为 semver，用于日历版本和语义化版本混合比较
+///
+/// 如 `24.04` 被识别为日历版本，而 `24.10` 被识别为语义化版本，两者都按 `24.x.0` 比较
+fn parse_written_numeric(cleaned: &str) -> Option<semver::Version> {
+    if let ParsedVersion::Semantic(v) = parse_version(cleaned) {
+        return Some(v);
+    }
+    let parts: Vec<u64> = cleaned
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [major, minor] => Some(semver::Version::new(*major, *minor, 0)),
+        [major, minor, patch] => Some(semver::Version::new(*major, *minor, *patch)),
+        _ => None,
+    }
+}
+
+/// 按策略比较两个无法按语义化规则比较且不相等的版本
+fn compare_unequal_non_semver(
+    latest: &str,
+    local: &str,
+    strategy: NonSemverStrategy,
+) -> VersionComparison {
+    match strategy {
+        NonSemverStrategy::Optimistic => VersionComparison::Greater,
+        NonSemverStrategy::LexicographicGreater => latest.cmp(local).into(),
+        NonSemverStrategy::TreatDifferentAsUnknown => VersionComparison::Unknown,
+    }
+}
+
+/// 智能版本比较，非标准版本使用默认策略
 pub fn compare_versions(latest: &str, local: &Option<String>) -> VersionComparison {
+    compare_versions_with(latest, local, NonSemverStrategy::default())
+}
+
+/// 智能版本比较
+///
+/// 优先使用 semver 进行语义化比较，对于不相等的非标准版本按 `strategy` 处理
+pub fn compare_versions_with(
+    latest: &str,
+    local: &Option<String>,
+    strategy: NonSemverStrategy,
+) -> VersionComparison {
     let Some(local_ver) = local else {
         return VersionComparison::Unknown;
     };
@@ -24,34 +204,210 @@ pub fn compare_versions(latest: &str, local: &Option<String>) -> VersionComparis
     let latest_parsed = parse_version(latest);
     let local_parsed = parse_version(local_ver);
 
+    if let Some(ordering) = compare_debian(&latest_parsed, &local_parsed) {
+        return ordering.into();
+    }
+
+    if let Some(ordering) = compare_four_part(&latest_parsed, &local_parsed) {
+        return ordering.into();
+    }
+
     match (latest_parsed, local_parsed) {
         // 两者都是语义化版本，使用 semver 比较
-        (ParsedVersion::Semantic(l), ParsedVersion::Semantic(r)) => match l.cmp(&r) {
-            std::cmp::Ordering::Greater => VersionComparison::Greater,
-            std::cmp::Ordering::Equal => VersionComparison::Equal,
-            std::cmp::Ordering::Less => VersionComparison::Less,
-        },
-        // 两者都是非语义化版本，使用字符串比较
+        (ParsedVersion::Semantic(l), ParsedVersion::Semantic(r)) => l.cmp(&r).into(),
+        // 两者都是日历版本，按日期先后比较
+        (
+            ParsedVersion::Calendar {
+                year: ly,
+                month: lm,
+                micro: ld,
+            },
+            ParsedVersion::Calendar {
+                year: ry,
+                month: rm,
+                micro: rd,
+            },
+        ) => (ly, lm, ld.unwrap_or(0))
+            .cmp(&(ry, rm, rd.unwrap_or(0)))
+            .into(),
+        // 两者都是非语义化版本，相等时直接判断，否则按策略处理
         (ParsedVersion::NonSemantic(l), ParsedVersion::NonSemantic(r)) => {
             if l == r {
                 VersionComparison::Equal
             } else {
-                // 无法确定大小，标记为 Unknown（保守策略）
-                // 但考虑到用户体验，如果不相等我们假设有更新
-                VersionComparison::Greater
+                compare_unequal_non_semver(&l, &r, strategy)
             }
         }
-        // 混合类型，尝试字符串比较
+        // 混合类型，同样先尝试字符串相等，再尝试按日期比较，最后按书写的数字比较
         _ => {
             let latest_clean = super::parser::clean_version_prefix(latest);
             let local_clean = super::parser::clean_version_prefix(local_ver);
-            if latest_clean == local_clean {
-                VersionComparison::Equal
-            } else {
-                VersionComparison::Greater
+            if latest_clean.eq_ignore_ascii_case(&local_clean) {
+                return VersionComparison::Equal;
+            }
+            if let (Some(l), Some(r)) =
+                (parse_date_version(&latest_clean), parse_date_version(&local_clean))
+            {
+                return l.cmp(&r).into();
             }
+            match (
+                parse_written_numeric(&latest_clean),
+                parse_written_numeric(&local_clean),
+            ) {
+                (Some(l), Some(r)) => l.cmp(&r).into(),
+                _ => compare_unequal_non_semver(&latest_clean, &local_clean, strategy),
+            }
+        }
+    }
+}
+
+/// 更新的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionChange {
+    Major,
+    Minor,
+    Patch,
+    /// 新版本是预发布版本
+    Prerelease,
+    /// 非语义化版本，或仅构建信息等不同
+    Other,
+}
+
+/// 判断更新的类型，没有更新时返回 None
+pub fn classify_change(latest: &str, local: &Option<String>) -> Option<VersionChange> {
+    classify_change_with(latest, local, NonSemverStrategy::default())
+}
+
+/// 判断更新的类型，非标准版本按 `strategy` 判断是否有更新
+pub fn classify_change_with(
+    latest: &str,
+    local: &Option<String>,
+    strategy: NonSemverStrategy,
+) -> Option<VersionChange> {
+    if compare_versions_with(latest, local, strategy) != VersionComparison::Greater {
+        return None;
+    }
+
+    let (new_parsed, old_parsed) = (parse_version(latest), parse_version(local.as_deref()?));
+    if matches!(new_parsed, ParsedVersion::FourPart(_))
+        || matches!(old_parsed, ParsedVersion::FourPart(_))
+    {
+        return Some(classify_four_part(&new_parsed, &old_parsed));
+    }
+
+    let (ParsedVersion::Semantic(new), ParsedVersion::Semantic(old)) = (new_parsed, old_parsed)
+    else {
+        return Some(VersionChange::Other);
+    };
+
+    let change = if !new.pre.is_empty() {
+        VersionChange::Prerelease
+    } else if new.major != old.major {
+        VersionChange::Major
+    } else if new.minor != old.minor {
+        VersionChange::Minor
+    } else if new.patch != old.patch {
+        VersionChange::Patch
+    } else {
+        // 如 1.0.0-rc.1 -> 1.0.0
+        VersionChange::Other
+    };
+    Some(change)
+}
+
+/// 四段版本的更新类型，第三、四段的变化都视为补丁更新
+fn classify_four_part(new: &ParsedVersion, old: &ParsedVersion) -> VersionChange {
+    let (Some((new_parts, new_release)), Some((old_parts, _))) =
+        (four_part_key(new), four_part_key(old))
+    else {
+        return VersionChange::Other;
+    };
+
+    if !new_release {
+        VersionChange::Prerelease
+    } else if new_parts[0] != old_parts[0] {
+        VersionChange::Major
+    } else if new_parts[1] != old_parts[1] {
+        VersionChange::Minor
+    } else if new_parts[2..] != old_parts[2..] {
+        VersionChange::Patch
+    } else {
+        VersionChange::Other
+    }
+}
+
+/// 按 PEP 440 比较，任一版本不符合规范时返回 None
+fn compare_pep440(latest: &str, local: &str) -> Option<VersionComparison> {
+    let latest = Pep440Version::parse(latest)?;
+    let local = Pep440Version::parse(local)?;
+    Some(latest.cmp(&local).into())
+}
+
+/// 按数据源的版本规范比较：PyPI 使用 PEP 440，其余同 `compare_versions`
+pub fn compare_versions_for(
+    source_type: &SourceType,
+    strategy: NonSemverStrategy,
+    latest: &str,
+    local: &Option<String>,
+) -> VersionComparison {
+    if *source_type == SourceType::Pypi {
+        if let Some(comparison) = local.as_deref().and_then(|l| compare_pep440(latest, l)) {
+            return comparison;
         }
     }
+    compare_versions_with(latest, local, strategy)
+}
+
+/// 按数据源的版本规范检查是否有更新
+pub fn has_update_for(
+    source_type: &SourceType,
+    strategy: NonSemverStrategy,
+    latest: &str,
+    local: &Option<String>,
+) -> bool {
+    matches!(
+        compare_versions_for(source_type, strategy, latest, local),
+        VersionComparison::Greater
+    )
+}
+
+/// 按数据源的版本规范判断更新类型
+pub fn classify_change_for(
+    source_type: &SourceType,
+    strategy: NonSemverStrategy,
+    latest: &str,
+    local: &Option<String>,
+) -> Option<VersionChange> {
+    if *source_type != SourceType::Pypi {
+        return classify_change_with(latest, local, strategy);
+    }
+
+    let (Some(new), Some(old)) = (
+        Pep440Version::parse(latest),
+        local.as_deref().and_then(Pep440Version::parse),
+    ) else {
+        return classify_change_with(latest, local, strategy);
+    };
+    if new <= old {
+        return None;
+    }
+
+    let change = if new.is_prerelease() {
+        VersionChange::Prerelease
+    } else if new.epoch != old.epoch {
+        VersionChange::Other
+    } else if new.release_part(0) != old.release_part(0) {
+        VersionChange::Major
+    } else if new.release_part(1) != old.release_part(1) {
+        VersionChange::Minor
+    } else if new.release_part(2) != old.release_part(2) {
+        VersionChange::Patch
+    } else {
+        // post 版本或第四段以后的变化
+        VersionChange::Other
+    };
+    Some(change)
 }
 
 /// 检查是否有更新（简化接口）
@@ -129,6 +485,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_non_semver_strategies() {
+        let local = Some("2024-01-15".to_string());
+        let compare = |latest: &str, strategy| compare_versions_with(latest, &local, strategy);
+
+        // 默认策略：不相等的非标准版本无法判断
+        assert_eq!(compare_versions("2024-02-01", &local), VersionComparison::Unknown);
+        assert!(!has_update("2024-02-01", &local));
+
+        assert_eq!(
+            compare("2024-02-01", NonSemverStrategy::TreatDifferentAsUnknown),
+            VersionComparison::Unknown
+        );
+        assert_eq!(
+            compare("2024-02-01", NonSemverStrategy::Optimistic),
+            VersionComparison::Greater
+        );
+        assert_eq!(
+            compare("2023-12-31", NonSemverStrategy::Optimistic),
+            VersionComparison::Greater
+        );
+        assert_eq!(
+            compare("2024-02-01", NonSemverStrategy::LexicographicGreater),
+            VersionComparison::Greater
+        );
+        assert_eq!(
+            compare("2023-12-31", NonSemverStrategy::LexicographicGreater),
+            VersionComparison::Less
+        );
+        assert_eq!(
+            compare("2024-01-15", NonSemverStrategy::Optimistic),
+            VersionComparison::Equal
+        );
+    }
+
     #[test]
     fn test_prerelease_detection() {
         assert!(is_prerelease("1.0.0-alpha.1"));
@@ -148,6 +539,157 @@ mod tests {
         assert!(has_update("1.0.0-beta", &Some("1.0.0-alpha".to_string())));
     }
 
+    #[test]
+    fn test_four_part_comparison() {
+        assert!(has_update("1.2.3.5", &Some("1.2.3.4".to_string())));
+        assert!(has_update("1.2.3.4", &Some("1.2.3".to_string())));
+        assert!(has_update("1.2.4", &Some("1.2.3.9".to_string())));
+        assert!(!has_update("1.2.3.4", &Some("1.2.3.10".to_string())));
+        assert_eq!(
+            compare_versions("1.2.3.0", &Some("v1.2.3".to_string())),
+            VersionComparison::Equal
+        );
+        assert!(has_update("1.2.3.0", &Some("1.2.3-rc.1".to_string())));
+
+        assert_eq!(classify("1.2.3.5", "1.2.3.4"), Some(VersionChange::Patch));
+        assert_eq!(classify("1.3.0.0", "1.2.3.4"), Some(VersionChange::Minor));
+        assert_eq!(classify("2.0.0", "1.2.3.4"), Some(VersionChange::Major));
+    }
+
+    fn classify(latest: &str, local: &str) -> Option<VersionChange> {
+        classify_change(latest, &Some(local.to_string()))
+    }
+
+    #[test]
+    fn test_classify_change() {
+        assert_eq!(classify("2.0.0", "1.9.3"), Some(VersionChange::Major));
+        assert_eq!(classify("v1.10.0", "1.9.3"), Some(VersionChange::Minor));
+        assert_eq!(classify("1.9.4", "1.9.3"), Some(VersionChange::Patch));
+        assert_eq!(classify("2.0.0-beta.1", "1.9.3"), Some(VersionChange::Prerelease));
+        assert_eq!(classify("1.0.0", "1.0.0-rc.1"), Some(VersionChange::Other));
+        assert_eq!(
+            classify_change_with(
+                "2024-02-01",
+                &Some("2024-01-15".to_string()),
+                NonSemverStrategy::Optimistic
+            ),
+            Some(VersionChange::Other)
+        );
+    }
+
+    #[test]
+    fn test_classify_no_change() {
+        assert_eq!(classify("1.0.0", "1.0.0"), None);
+        assert_eq!(classify("1.0.0", "1.2.0"), None);
+        assert_eq!(classify_change("1.0.0", &None), None);
+    }
+
+    #[test]
+    fn test_calendar_versions() {
+        assert!(has_update("2024.1.2", &Some("2023.3.4".to_string())));
+        assert!(has_update("2021.12.17", &Some("2021.1.1".to_string())));
+        assert!(has_update("2021.12.17", &Some("2021.12.07".to_string())));
+        assert!(!has_update("2023.3.4", &Some("2024.1.2".to_string())));
+        assert_eq!(
+            compare_versions("2024.01.05", &Some("2024.1.5".to_string())),
+            VersionComparison::Equal
+        );
+    }
+
+    #[test]
+    fn test_two_digit_calendar_versions() {
+        // 24.04 被识别为日历版本，24.10 被识别为语义化版本
+        assert!(has_update("24.10", &Some("24.04".to_string())));
+        assert!(has_update("22.10", &Some("22.04".to_string())));
+        assert!(has_update("25.04", &Some("24.10".to_string())));
+        assert!(!has_update("24.04", &Some("24.10".to_string())));
+        assert_eq!(
+            compare_versions("24.04", &Some("24.10".to_string())),
+            VersionComparison::Less
+        );
+        assert_eq!(classify("24.10", "24.04"), Some(VersionChange::Other));
+    }
+
+    #[test]
+    fn test_mixed_date_versions() {
+        // 分别被解析为日历版本、非语义化版本和语义化版本的同一日期
+        for (latest, local) in [
+            ("2024.01.15", "2024-01-15"),
+            ("2024-01-15", "v2024.1.15"),
+            ("20240115", "2024-01-15"),
+            ("2024/01/15", "20240115"),
+        ] {
+            for strategy in [
+                NonSemverStrategy::Optimistic,
+                NonSemverStrategy::LexicographicGreater,
+                NonSemverStrategy::TreatDifferentAsUnknown,
+            ] {
+                assert_eq!(
+                    compare_versions_with(latest, &Some(local.to_string()), strategy),
+                    VersionComparison::Equal,
+                    "{} vs {}",
+                    latest,
+                    local
+                );
+            }
+            assert_eq!(classify(latest, local), None);
+        }
+
+        assert!(has_update("2024.01.16", &Some("2024-01-15".to_string())));
+        assert!(!has_update("20240114", &Some("2024-01-15".to_string())));
+        // 无效日期仍按策略处理
+        assert!(has_update("2024.02.30", &Some("2024-02-30".to_string())));
+    }
+
+    #[test]
+    fn test_pypi_uses_pep440() {
+        let pypi = SourceType::Pypi;
+        let strategy = NonSemverStrategy::default();
+        let local = |v: &str| Some(v.to_string());
+
+        assert!(has_update_for(&pypi, strategy, "1.0.post1", &local("1.0")));
+        assert!(has_update_for(&pypi, strategy, "2!1.0", &local("1.9")));
+        assert!(!has_update_for(&pypi, strategy, "1.0rc1", &local("1.0")));
+        assert!(has_update_for(&pypi, strategy, "1.0", &local("1.0rc1")));
+        assert!(!has_update_for(&pypi, strategy, "1.0.0", &local("1.0")));
+
+        assert_eq!(
+            classify_change_for(&pypi, strategy, "1.0.post1", &local("1.0")),
+            Some(VersionChange::Other)
+        );
+        assert_eq!(
+            classify_change_for(&pypi, strategy, "2.1.0.dev3", &local("2.0.0")),
+            Some(VersionChange::Prerelease)
+        );
+        assert_eq!(
+            classify_change_for(&pypi, strategy, "2.1.0", &local("2.0.5")),
+            Some(VersionChange::Minor)
+        );
+    }
+
+    #[test]
+    fn test_debian_versions() {
+        // epoch 优先
+        assert!(has_update("1:1.0", &Some("2.0".to_string())));
+        assert!(!has_update("2.0", &Some("1:1.0".to_string())));
+        // 上游版本相同时比较修订号
+        assert!(has_update("1.0-2", &Some("1.0-1".to_string())));
+        assert!(has_update("2.3.4-1ubuntu2", &Some("2.3.4-1ubuntu1".to_string())));
+        assert!(has_update("2.3.4-10", &Some("2.3.4-9".to_string())));
+        assert!(!has_update("2.3.4-1", &Some("2.3.4-1".to_string())));
+        // 上游版本更新时忽略修订号
+        assert!(has_update("2.4.0-1", &Some("2.3.4-5".to_string())));
+        assert!(has_update("2.3.4-1", &Some("2.3.4".to_string())));
+    }
+
+    #[test]
+    fn test_debian_revision_order() {
+        assert_eq!(compare_debian_revision("1~beta1", "1"), Ordering::Less);
+        assert_eq!(compare_debian_revision("1ubuntu2", "1ubuntu10"), Ordering::Less);
+        assert_eq!(compare_debian_revision("1+deb12u1", "1"), Ordering::Greater);
+        assert_eq!(compare_debian_revision("", "0"), Ordering::Equal);
+    }
+
     #[test]
     fn test_two_part_version() {
         assert!(has_update("1.10", &Some("1.9".to_string())));
@@ -1,5 +1,10 @@
 pub mod comparator;
 pub mod parser;
+pub mod pep440;
 
-pub use comparator::{compare_versions, has_update, is_prerelease, VersionComparison};
+pub use comparator::{
+    classify_change, classify_change_for, classify_change_with, compare_versions,
+    compare_versions_for, compare_versions_with, has_update, has_update_for, is_prerelease,
+    VersionChange, VersionComparison,
+};
 pub use parser::{clean_version_prefix, parse_version, ParsedVersion};
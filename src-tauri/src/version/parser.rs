@@ -5,19 +5,138 @@ use semver::Version;
 pub enum ParsedVersion {
     /// 标准 semver 版本
     Semantic(Version),
+    /// 日历版本（如 2024.1.2、24.04），`micro` 为日期或当月的序号
+    Calendar {
+        year: u32,
+        month: u32,
+        micro: Option<u64>,
+    },
+    /// Debian 风格的版本（如 1:2.3.4-1ubuntu2），包含 epoch 和打包修订号
+    Debian {
+        epoch: u64,
+        upstream: Version,
+        revision: Option<String>,
+    },
+    /// 四段数字版本（如 Windows 应用的 1.2.3.4），四段都参与比较
+    FourPart([u64; 4]),
     /// 非标准版本（日期、自定义格式等）
     NonSemantic(String),
 }
 
+impl ParsedVersion {
+    /// 转换为可排序的 semver，日历版本按 `year.month.micro` 转换，四段版本丢弃第四段
+    pub fn into_semver(self) -> Option<Version> {
+        match self {
+            ParsedVersion::Semantic(v) => Some(v),
+            ParsedVersion::Calendar { year, month, micro } => {
+                Some(Version::new(year as u64, month as u64, micro.unwrap_or(0)))
+            }
+            ParsedVersion::FourPart([major, minor, patch, _]) => {
+                Some(Version::new(major, minor, patch))
+            }
+            ParsedVersion::Debian { .. } | ParsedVersion::NonSemantic(_) => None,
+        }
+    }
+}
+
 /// 清理版本前缀 (v1.2.3 -> 1.2.3)
 pub fn clean_version_prefix(version: &str) -> String {
     version.trim().trim_start_matches('v').trim().to_string()
 }
 
+/// 识别日历版本：`YYYY.MM`、`YYYY.MM.DD`、`YYYY.MM.micro`，以及 Ubuntu 式的 `YY.0M`
+fn parse_calendar(cleaned: &str) -> Option<ParsedVersion> {
+    let parts: Vec<&str> = cleaned.split('.').collect();
+    let all_numeric = parts
+        .iter()
+        .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()));
+    if !(2..=3).contains(&parts.len()) || !all_numeric {
+        return None;
+    }
+
+    let month: u32 = parts[1].parse().ok()?;
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+
+    let year: u32 = match parts[0].len() {
+        4 => parts[0].parse().ok().filter(|y| (1900..=2999).contains(y))?,
+        // 两位年份只在月份补零时识别，避免把 10.15 之类的普通版本当成日历版本
+        2 if parts.len() == 2 && parts[1].len() == 2 && parts[1].starts_with('0') => {
+            2000 + parts[0].parse::<u32>().ok()?
+        }
+        _ => return None,
+    };
+
+    let micro = match parts.get(2) {
+        Some(part) => Some(part.parse().ok()?),
+        None => None,
+    };
+
+    Some(ParsedVersion::Calendar { year, month, micro })
+}
+
+/// 识别恰好四段纯数字的版本，如 `1.2.3.4`
+fn parse_four_part(cleaned: &str) -> Option<ParsedVersion> {
+    let parts: Vec<u64> = cleaned
+        .split('.')
+        .map(|p| {
+            if p.is_empty() || !p.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            p.parse().ok()
+        })
+        .collect::<Option<_>>()?;
+    let parts: [u64; 4] = parts.try_into().ok()?;
+    Some(ParsedVersion::FourPart(parts))
+}
+
+/// Debian 修订号以数字开头，以区分 `-beta`、`-rc.1` 之类的预发布标识
+fn is_debian_revision(revision: &str) -> bool {
+    revision.starts_with(|c: char| c.is_ascii_digit())
+        && revision
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '~'))
+}
+
+/// 识别 `epoch:upstream-revision` 形式的版本，epoch 和修订号至少要有一个
+fn parse_debian(cleaned: &str) -> Option<ParsedVersion> {
+    let (epoch, rest) = match cleaned.split_once(':') {
+        Some((epoch, rest)) => (Some(epoch.parse::<u64>().ok()?), rest),
+        None => (None, cleaned),
+    };
+    let (upstream, revision) = match rest.rsplit_once('-') {
+        Some((upstream, revision)) if is_debian_revision(revision) => (upstream, Some(revision)),
+        _ => (rest, None),
+    };
+    if epoch.is_none() && revision.is_none() {
+        return None;
+    }
+
+    let upstream = parse_version(upstream).into_semver()?;
+    Some(ParsedVersion::Debian {
+        epoch: epoch.unwrap_or(0),
+        upstream,
+        revision: revision.map(String::from),
+    })
+}
+
 /// 尝试解析为 semver，失败则返回原始字符串
 pub fn parse_version(version: &str) -> ParsedVersion {
     let cleaned = clean_version_prefix(version);
 
+    if let Some(debian) = parse_debian(&cleaned) {
+        return debian;
+    }
+
+    if let Some(calendar) = parse_calendar(&cleaned) {
+        return calendar;
+    }
+
+    if let Some(four_part) = parse_four_part(&cleaned) {
+        return four_part;
+    }
+
     // 尝试直接解析
     if let Ok(v) = Version::parse(&cleaned) {
         return ParsedVersion::Semantic(v);
@@ -35,7 +154,7 @@ pub fn parse_version(version: &str) -> ParsedVersion {
         }
     }
 
-    // 处理带有额外后缀的版本号，如 "1.2.3_1" 或 "1.2.3.4"
+    // 处理带有额外后缀的版本号，如 "1.2.3_1" 或 "1.2.3.4.5"
     // 但排除日期格式 (2024-01-15)
     if !cleaned.contains('-') || cleaned.matches('-').count() <= 1 {
         let parts: Vec<&str> = cleaned.split(|c| c == '.' || c == '_').collect();
@@ -118,6 +237,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_calendar() {
+        for (input, year, month, micro) in [
+            ("2024.1.2", 2024, 1, Some(2)),
+            ("2021.12.17", 2021, 12, Some(17)),
+            ("2024.01.05", 2024, 1, Some(5)),
+            ("v2023.3", 2023, 3, None),
+            ("24.04", 2024, 4, None),
+        ] {
+            match parse_version(input) {
+                ParsedVersion::Calendar {
+                    year: y,
+                    month: m,
+                    micro: d,
+                } => assert_eq!((y, m, d), (year, month, micro), "{}", input),
+                other => panic!("Expected Calendar version for {}, got {:?}", input, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_calendar_does_not_capture_semver() {
+        for input in ["10.15", "1.2.3", "22.10", "2024.13.1", "1.2024.1"] {
+            assert!(
+                !matches!(parse_version(input), ParsedVersion::Calendar { .. }),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_debian() {
+        match parse_version("1:2.3.4-1ubuntu2") {
+            ParsedVersion::Debian {
+                epoch,
+                upstream,
+                revision,
+            } => {
+                assert_eq!(epoch, 1);
+                assert_eq!(upstream, Version::new(2, 3, 4));
+                assert_eq!(revision.as_deref(), Some("1ubuntu2"));
+            }
+            other => panic!("Expected Debian version, got {:?}", other),
+        }
+
+        assert!(matches!(
+            parse_version("1.0-2"),
+            ParsedVersion::Debian { epoch: 0, .. }
+        ));
+        // 预发布标识仍按 semver 解析
+        assert!(matches!(
+            parse_version("1.0.0-rc.1"),
+            ParsedVersion::Semantic(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_four_part() {
+        assert!(matches!(
+            parse_version("v10.0.19041.1"),
+            ParsedVersion::FourPart([10, 0, 19041, 1])
+        ));
+        assert!(matches!(parse_version("1.2.3"), ParsedVersion::Semantic(_)));
+        assert!(matches!(
+            parse_version("1.2.3.4-beta"),
+            ParsedVersion::Semantic(_)
+        ));
+    }
+
     #[test]
     fn test_parse_non_semver() {
         match parse_version("2024-01-15") {
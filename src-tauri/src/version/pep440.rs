@@ -0,0 +1,170 @@
+use regex::Regex;
+use std::cmp::Ordering;
+use std::sync::OnceLock;
+
+/// 预发布阶段，按 a < b < rc 排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PreReleaseKind {
+    Alpha,
+    Beta,
+    ReleaseCandidate,
+}
+
+/// 排序键：(epoch, 发布号, 预发布, post, dev)
+type SortKey<'a> = (
+    u64,
+    &'a [u64],
+    (u8, Option<(PreReleaseKind, u64)>),
+    Option<u64>,
+    (u8, u64),
+);
+
+/// PEP 440 版本号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pep440Version {
+    pub epoch: u64,
+    pub release: Vec<u64>,
+    pub pre: Option<(PreReleaseKind, u64)>,
+    pub post: Option<u64>,
+    pub dev: Option<u64>,
+}
+
+fn pep440_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?x)^
+            v?
+            (?:(?P<epoch>\d+)!)?
+            (?P<release>\d+(?:\.\d+)*)
+            (?:[-_.]?(?P<pre_l>alpha|a|beta|b|preview|pre|rc|c)[-_.]?(?P<pre_n>\d+)?)?
+            (?:-(?P<post_n1>\d+)|[-_.]?(?P<post_l>post|rev|r)[-_.]?(?P<post_n2>\d+)?)?
+            (?:[-_.]?(?P<dev>dev)[-_.]?(?P<dev_n>\d+)?)?
+            (?:\+[a-z0-9]+(?:[-_.][a-z0-9]+)*)?
+            $",
+        )
+        .expect("valid PEP 440 regex")
+    })
+}
+
+impl Pep440Version {
+    /// 解析 PEP 440 版本号，不符合规范时返回 None
+    pub fn parse(version: &str) -> Option<Self> {
+        let normalized = version.trim().to_lowercase();
+        let caps = pep440_regex().captures(&normalized)?;
+        let number = |name: &str| caps.name(name).and_then(|m| m.as_str().parse::<u64>().ok());
+
+        let release = caps["release"]
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<Vec<u64>>>()?;
+
+        let pre = caps.name("pre_l").map(|label| {
+            let kind = match label.as_str() {
+                "a" | "alpha" => PreReleaseKind::Alpha,
+                "b" | "beta" => PreReleaseKind::Beta,
+                _ => PreReleaseKind::ReleaseCandidate,
+            };
+            (kind, number("pre_n").unwrap_or(0))
+        });
+
+        // `1.0-1` 与 `1.0.post` 都是 post 版本，省略序号时视为 0
+        let has_post = caps.name("post_n1").is_some() || caps.name("post_l").is_some();
+        let post = has_post.then(|| number("post_n1").or(number("post_n2")).unwrap_or(0));
+        let dev = caps.name("dev").map(|_| number("dev_n").unwrap_or(0));
+
+        Some(Self {
+            epoch: number("epoch").unwrap_or(0),
+            release,
+            pre,
+            post,
+            dev,
+        })
+    }
+
+    /// 是否为预发布或开发版本
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// 第 `index` 段发布号，缺省为 0
+    pub fn release_part(&self, index: usize) -> u64 {
+        self.release.get(index).copied().unwrap_or(0)
+    }
+
+    /// 去掉末尾的 0，使 `1.0` 与 `1.0.0` 相等
+    fn trimmed_release(&self) -> &[u64] {
+        let len = self
+            .release
+            .iter()
+            .rposition(|&part| part != 0)
+            .map_or(0, |index| index + 1);
+        &self.release[..len]
+    }
+
+    /// PEP 440 的排序键：仅有 dev 的版本排在所有预发布版本之前，
+    /// 正式版排在预发布之后，post 版本在正式版之后
+    fn sort_key(&self) -> SortKey<'_> {
+        let pre = match (self.pre, self.post, self.dev) {
+            (Some(pre), _, _) => (1, Some(pre)),
+            (None, None, Some(_)) => (0, None),
+            (None, _, _) => (2, None),
+        };
+        let dev = match self.dev {
+            Some(dev) => (0, dev),
+            None => (1, 0),
+        };
+        (self.epoch, self.trimmed_release(), pre, self.post, dev)
+    }
+}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str) -> Pep440Version {
+        Pep440Version::parse(version).unwrap_or_else(|| panic!("invalid version {}", version))
+    }
+
+    #[test]
+    fn test_parse() {
+        let version = v("2!1.2.3rc4.post5.dev6+local.7");
+        assert_eq!(version.epoch, 2);
+        assert_eq!(version.release, vec![1, 2, 3]);
+        assert_eq!(version.pre, Some((PreReleaseKind::ReleaseCandidate, 4)));
+        assert_eq!(version.post, Some(5));
+        assert_eq!(version.dev, Some(6));
+
+        assert_eq!(v("1.0-1").post, Some(1));
+        assert_eq!(v("1.0.post").post, Some(0));
+        assert_eq!(v("v1.0.0-beta.2").pre, Some((PreReleaseKind::Beta, 2)));
+        assert_eq!(v("1.0").post, None);
+        assert!(Pep440Version::parse("not-a-version").is_none());
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(v("1.0.post1") > v("1.0"));
+        assert!(v("2!1.0") > v("1.9"));
+        assert!(v("1.0rc1") < v("1.0"));
+        assert!(v("1.0.dev3") < v("1.0a1"));
+        assert!(v("1.0a1") < v("1.0b1"));
+        assert!(v("1.0b2") < v("1.0rc1"));
+        assert!(v("1.0rc1.dev1") < v("1.0rc1"));
+        assert!(v("1.0.post1.dev1") < v("1.0.post1"));
+        assert!(v("1.0.post1") < v("1.0.1"));
+        assert_eq!(v("1.0").cmp(&v("1.0.0")), Ordering::Equal);
+    }
+}
@@ -21,14 +21,19 @@ function App() {
 
   const {
     softwares,
+    backoffStatuses,
     isLoading,
     isChecking,
     fetchSoftwares,
+    fetchBackoffStatuses,
     addSoftware,
     updateSoftware,
     deleteSoftware,
+    duplicateSoftware,
     checkVersion,
     checkAllVersions,
+    validateSource,
+    fetchSourceToken,
   } = useSoftwareStore();
 
   const {
@@ -36,6 +41,7 @@ function App() {
     fetchSettings,
     saveSettings,
     clearCache,
+    sendTestNotification,
   } = useSettingsStore();
 
   // Auto refresh fromNow display every 10 seconds
@@ -68,7 +74,8 @@ function App() {
   useEffect(() => {
     fetchSoftwares();
     fetchSettings();
-  }, [fetchSoftwares, fetchSettings]);
+    fetchBackoffStatuses();
+  }, [fetchSoftwares, fetchSettings, fetchBackoffStatuses]);
 
   // Listen for backend scheduler updates
   useEffect(() => {
@@ -76,13 +83,14 @@ function App() {
       console.log("[Scheduler] Received version update from backend", event.payload);
       // 刷新软件列表以获取最新数据
       fetchSoftwares();
+      fetchBackoffStatuses();
       toast.success("自动刷新完成", { description: `已更新 ${event.payload.length} 个软件版本信息` });
     });
 
     return () => {
       unlisten.then((fn) => fn());
     };
-  }, [fetchSoftwares]);
+  }, [fetchSoftwares, fetchBackoffStatuses]);
 
   const handleAddSoftware = useCallback(
     async (form: SoftwareFormData) => {
@@ -132,6 +140,18 @@ function App() {
     [softwares, deleteSoftware]
   );
 
+  const handleDuplicateSoftware = useCallback(
+    async (id: string) => {
+      try {
+        const software = await duplicateSoftware(id);
+        toast.success("复制成功", { description: `已添加 ${software.name}` });
+      } catch (error) {
+        toast.error("复制失败", { description: String(error) });
+      }
+    },
+    [duplicateSoftware]
+  );
+
   const handleRefresh = useCallback(
     async (id: string) => {
       setRefreshingId(id);
@@ -184,6 +204,22 @@ function App() {
     }
   }, [clearCache]);
 
+  const handleTestNotification = useCallback(async () => {
+    try {
+      const results = await sendTestNotification();
+      for (const result of results) {
+        const label = result.channel === "webhook" ? "Webhook" : "系统通知";
+        if (result.success) {
+          toast.success(`${label}测试已发送`);
+        } else {
+          toast.error(`${label}测试失败`, { description: result.error ?? undefined });
+        }
+      }
+    } catch (error) {
+      toast.error("测试通知失败", { description: String(error) });
+    }
+  }, [sendTestNotification]);
+
   // Find the most recent check time
   const lastCheckedAt = softwares
     .filter((s) => s.lastCheckedAt)
@@ -210,9 +246,11 @@ function App() {
         ) : (
           <SoftwareTable
             softwares={softwares}
+            backoffStatuses={backoffStatuses}
             onRefresh={handleRefresh}
             onEdit={handleEdit}
             onDelete={handleDeleteSoftware}
+            onDuplicate={handleDuplicateSoftware}
             isRefreshing={refreshingId}
           />
         )}
@@ -229,6 +267,7 @@ function App() {
         open={addDialogOpen}
         onOpenChange={setAddDialogOpen}
         onSubmit={handleAddSoftware}
+        onValidateSource={validateSource}
       />
 
       <EditSoftwareDialog
@@ -236,6 +275,7 @@ function App() {
         onOpenChange={setEditDialogOpen}
         software={editingSoftware}
         onSubmit={handleUpdateSoftware}
+        onFetchSourceToken={fetchSourceToken}
       />
 
       <SettingsDialog
@@ -244,6 +284,7 @@ function App() {
         settings={settings}
         onSave={handleSaveSettings}
         onClearCache={handleClearCache}
+        onTestNotification={handleTestNotification}
       />
 
       <Toaster position="bottom-right" />
@@ -11,8 +11,26 @@ import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Switch } from "@/components/ui/switch";
-import type { AppSettings, ThemeMode, NotificationConfig } from "@/types/software";
-import { THEME_MODE_LABELS, DEFAULT_NOTIFICATION_CONFIG } from "@/types/software";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import type {
+  AppSettings,
+  ThemeMode,
+  NotificationConfig,
+  NonSemverStrategy,
+  NotificationStyle,
+} from "@/types/software";
+import {
+  THEME_MODE_LABELS,
+  NON_SEMVER_STRATEGY_LABELS,
+  NOTIFICATION_STYLE_LABELS,
+  DEFAULT_NOTIFICATION_CONFIG,
+} from "@/types/software";
 import { Sun, Moon, Monitor, Bell, AlertTriangle } from "lucide-react";
 
 interface SettingsDialogProps {
@@ -21,6 +39,7 @@ interface SettingsDialogProps {
   settings: AppSettings;
   onSave: (settings: AppSettings) => Promise<void>;
   onClearCache: () => Promise<void>;
+  onTestNotification: () => Promise<void>;
 }
 
 const THEME_MODES: { mode: ThemeMode; icon: typeof Sun; label: string }[] = [
@@ -35,24 +54,48 @@ export function SettingsDialog({
   settings,
   onSave,
   onClearCache,
+  onTestNotification,
 }: SettingsDialogProps) {
   const [ttlMinutes, setTtlMinutes] = useState(30);
+  const [errorTtlMinutes, setErrorTtlMinutes] = useState(5);
   const [autoRefreshEnabled, setAutoRefreshEnabled] = useState(true);
+  const [checkOnStartup, setCheckOnStartup] = useState(true);
   const [autoRefreshInterval, setAutoRefreshInterval] = useState(60);
   const [githubToken, setGithubToken] = useState("");
+  const [githubApiBaseUrl, setGithubApiBaseUrl] = useState("");
+  const [bitbucketToken, setBitbucketToken] = useState("");
+  const [giteaToken, setGiteaToken] = useState("");
+  const [userAgent, setUserAgent] = useState("");
+  const [contact, setContact] = useState("");
   const [theme, setTheme] = useState<ThemeMode>("system");
+  const [nonSemverStrategy, setNonSemverStrategy] = useState<NonSemverStrategy>(
+    "treat-different-as-unknown"
+  );
   const [notification, setNotification] = useState<NotificationConfig>(DEFAULT_NOTIFICATION_CONFIG);
+  const [metricsEnabled, setMetricsEnabled] = useState(false);
+  const [metricsPort, setMetricsPort] = useState(9464);
   const [isSaving, setIsSaving] = useState(false);
   const [isClearingCache, setIsClearingCache] = useState(false);
+  const [isTestingNotification, setIsTestingNotification] = useState(false);
 
   useEffect(() => {
     if (settings) {
       setTtlMinutes(settings.cache.ttlMinutes);
+      setErrorTtlMinutes(settings.cache.errorTtlMinutes ?? 5);
       setAutoRefreshEnabled(settings.cache.autoRefreshEnabled);
+      setCheckOnStartup(settings.cache.checkOnStartup ?? true);
       setAutoRefreshInterval(settings.cache.autoRefreshInterval);
       setGithubToken(settings.githubToken || "");
+      setGithubApiBaseUrl(settings.githubApiBaseUrl || "");
+      setBitbucketToken(settings.bitbucketToken || "");
+      setGiteaToken(settings.giteaToken || "");
+      setUserAgent(settings.network?.userAgent || "");
+      setContact(settings.network?.contact || "");
       setTheme(settings.theme || "system");
+      setNonSemverStrategy(settings.nonSemverStrategy || "treat-different-as-unknown");
       setNotification(settings.notification || DEFAULT_NOTIFICATION_CONFIG);
+      setMetricsEnabled(settings.metrics?.enabled ?? false);
+      setMetricsPort(settings.metrics?.port ?? 9464);
     }
   }, [settings]);
 
@@ -60,14 +103,33 @@ export function SettingsDialog({
     setIsSaving(true);
     try {
       await onSave({
+        // 保留对话框未编辑的设置项
+        ...settings,
         cache: {
           ttlMinutes,
+          errorTtlMinutes,
           autoRefreshEnabled,
           autoRefreshInterval,
+          checkOnStartup,
         },
         githubToken: githubToken || undefined,
+        githubApiBaseUrl: githubApiBaseUrl.trim() || undefined,
+        bitbucketToken: bitbucketToken.trim() || undefined,
+        giteaToken: giteaToken.trim() || undefined,
+        network: {
+          requestTimeoutSeconds: 15,
+          ...settings?.network,
+          userAgent: userAgent.trim() || undefined,
+          contact: contact.trim() || undefined,
+        },
         theme,
+        nonSemverStrategy,
         notification,
+        metrics: {
+          ...settings?.metrics,
+          enabled: metricsEnabled,
+          port: metricsPort,
+        },
       });
       onOpenChange(false);
     } catch (error) {
@@ -77,6 +139,15 @@ export function SettingsDialog({
     }
   };
 
+  const handleTestNotification = async () => {
+    setIsTestingNotification(true);
+    try {
+      await onTestNotification();
+    } finally {
+      setIsTestingNotification(false);
+    }
+  };
+
   const handleClearCache = async () => {
     setIsClearingCache(true);
     try {
@@ -118,6 +189,34 @@ export function SettingsDialog({
             </div>
           </div>
 
+          <div className="space-y-4 border-t pt-4">
+            <h3 className="text-sm font-medium">版本比较</h3>
+
+            <div className="space-y-2">
+              <Label htmlFor="nonSemverStrategy">非标准版本号</Label>
+              <Select
+                value={nonSemverStrategy}
+                onValueChange={(v) => setNonSemverStrategy(v as NonSemverStrategy)}
+              >
+                <SelectTrigger id="nonSemverStrategy">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {(Object.keys(NON_SEMVER_STRATEGY_LABELS) as NonSemverStrategy[]).map(
+                    (strategy) => (
+                      <SelectItem key={strategy} value={strategy}>
+                        {NON_SEMVER_STRATEGY_LABELS[strategy]}
+                      </SelectItem>
+                    )
+                  )}
+                </SelectContent>
+              </Select>
+              <p className="text-xs text-muted-foreground">
+                日期、自定义格式等无法按语义化版本比较且不相等时的处理方式
+              </p>
+            </div>
+          </div>
+
           <div className="space-y-4 border-t pt-4">
             <h3 className="text-sm font-medium">缓存设置</h3>
 
@@ -136,6 +235,21 @@ export function SettingsDialog({
               </p>
             </div>
 
+            <div className="space-y-2">
+              <Label htmlFor="error-ttl">失败结果缓存（分钟）</Label>
+              <Input
+                id="error-ttl"
+                type="number"
+                min={0}
+                max={1440}
+                value={errorTtlMinutes}
+                onChange={(e) => setErrorTtlMinutes(Number(e.target.value))}
+              />
+              <p className="text-xs text-muted-foreground">
+                获取失败的数据源在此时间内不再重复请求，应短于缓存有效期
+              </p>
+            </div>
+
             <div className="flex items-center justify-between">
               <div className="space-y-0.5">
                 <Label htmlFor="autoRefresh">自动刷新</Label>
@@ -150,6 +264,20 @@ export function SettingsDialog({
               />
             </div>
 
+            <div className="flex items-center justify-between">
+              <div className="space-y-0.5">
+                <Label htmlFor="checkOnStartup">启动时检查</Label>
+                <p className="text-xs text-muted-foreground">
+                  打开应用时立即检查所有软件，需开启自动刷新，下次启动生效
+                </p>
+              </div>
+              <Switch
+                id="checkOnStartup"
+                checked={checkOnStartup}
+                onCheckedChange={setCheckOnStartup}
+              />
+            </div>
+
             {autoRefreshEnabled && (
               <div className="space-y-2">
                 <Label htmlFor="interval">刷新间隔（分钟）</Label>
@@ -190,6 +318,82 @@ export function SettingsDialog({
                 配置 Token 可将 API 限额从 60 次/小时提升至 5000 次/小时
               </p>
             </div>
+
+            <div className="space-y-2">
+              <Label htmlFor="github-api-base-url">API 地址（可选）</Label>
+              <Input
+                id="github-api-base-url"
+                value={githubApiBaseUrl}
+                onChange={(e) => setGithubApiBaseUrl(e.target.value)}
+                placeholder="https://api.github.com"
+              />
+              <p className="text-xs text-muted-foreground">
+                使用 GitHub Enterprise 时填写，例如 https://ghe.example.com/api/v3
+              </p>
+            </div>
+          </div>
+
+          <div className="space-y-4 border-t pt-4">
+            <h3 className="text-sm font-medium">Bitbucket</h3>
+
+            <div className="space-y-2">
+              <Label htmlFor="bitbucket-token">凭据（可选）</Label>
+              <Input
+                id="bitbucket-token"
+                type="password"
+                value={bitbucketToken}
+                onChange={(e) => setBitbucketToken(e.target.value)}
+                placeholder="用户名:应用密码 或 Access Token"
+              />
+              <p className="text-xs text-muted-foreground">
+                访问私有仓库时需要配置
+              </p>
+            </div>
+          </div>
+
+          <div className="space-y-4 border-t pt-4">
+            <h3 className="text-sm font-medium">Gitea/Forgejo</h3>
+
+            <div className="space-y-2">
+              <Label htmlFor="gitea-token">Access Token（可选）</Label>
+              <Input
+                id="gitea-token"
+                type="password"
+                value={giteaToken}
+                onChange={(e) => setGiteaToken(e.target.value)}
+                placeholder="在实例的 设置 → 应用 中生成"
+              />
+              <p className="text-xs text-muted-foreground">
+                访问私有仓库时需要配置
+              </p>
+            </div>
+          </div>
+
+          <div className="space-y-4 border-t pt-4">
+            <h3 className="text-sm font-medium">网络</h3>
+
+            <div className="space-y-2">
+              <Label htmlFor="user-agent">User-Agent（可选）</Label>
+              <Input
+                id="user-agent"
+                value={userAgent}
+                onChange={(e) => setUserAgent(e.target.value)}
+                placeholder="app-version-gui"
+              />
+            </div>
+
+            <div className="space-y-2">
+              <Label htmlFor="contact">联系方式（可选）</Label>
+              <Input
+                id="contact"
+                value={contact}
+                onChange={(e) => setContact(e.target.value)}
+                placeholder="you@example.com"
+              />
+              <p className="text-xs text-muted-foreground">
+                通过 From 请求头发送，部分数据源按 User-Agent 限流时便于联系
+              </p>
+            </div>
           </div>
 
           <div className="space-y-4 border-t pt-4">
@@ -244,6 +448,32 @@ export function SettingsDialog({
 
             {notification.enabled && (
               <>
+                <div className="space-y-2">
+                  <Label htmlFor="notificationStyle">通知方式</Label>
+                  <Select
+                    value={notification.style ?? "individual"}
+                    onValueChange={(v) =>
+                      setNotification((prev) => ({ ...prev, style: v as NotificationStyle }))
+                    }
+                  >
+                    <SelectTrigger id="notificationStyle">
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {(Object.keys(NOTIFICATION_STYLE_LABELS) as NotificationStyle[]).map(
+                        (style) => (
+                          <SelectItem key={style} value={style}>
+                            {NOTIFICATION_STYLE_LABELS[style]}
+                          </SelectItem>
+                        )
+                      )}
+                    </SelectContent>
+                  </Select>
+                  <p className="text-xs text-muted-foreground">
+                    汇总时一次检查发现的所有更新合并为一条系统通知
+                  </p>
+                </div>
+
                 <div className="space-y-3 pl-2 border-l-2 border-muted">
                   <p className="text-xs text-muted-foreground">通知版本类型：</p>
                   <div className="flex items-center justify-between">
@@ -311,9 +541,24 @@ export function SettingsDialog({
                         }))
                       }
                       placeholder="22"
-                      className="w-20"
+                      className="w-16"
                     />
-                    <span className="text-sm text-muted-foreground">点 至</span>
+                    <span className="text-sm text-muted-foreground">:</span>
+                    <Input
+                      type="number"
+                      min={0}
+                      max={59}
+                      value={notification.silentStartMinute ?? ""}
+                      onChange={(e) =>
+                        setNotification((prev) => ({
+                          ...prev,
+                          silentStartMinute: e.target.value ? Number(e.target.value) : null,
+                        }))
+                      }
+                      placeholder="00"
+                      className="w-16"
+                    />
+                    <span className="text-sm text-muted-foreground">至</span>
                     <Input
                       type="number"
                       min={0}
@@ -326,17 +571,145 @@ export function SettingsDialog({
                         }))
                       }
                       placeholder="8"
-                      className="w-20"
+                      className="w-16"
+                    />
+                    <span className="text-sm text-muted-foreground">:</span>
+                    <Input
+                      type="number"
+                      min={0}
+                      max={59}
+                      value={notification.silentEndMinute ?? ""}
+                      onChange={(e) =>
+                        setNotification((prev) => ({
+                          ...prev,
+                          silentEndMinute: e.target.value ? Number(e.target.value) : null,
+                        }))
+                      }
+                      placeholder="00"
+                      className="w-16"
+                    />
+                  </div>
+                  <p className="text-xs text-muted-foreground">
+                    按本地时间，在此时段内不发送通知（留空则不限制）
+                  </p>
+                </div>
+
+                <div className="space-y-2">
+                  <Label>通知频率上限</Label>
+                  <div className="flex items-center gap-2">
+                    <span className="text-sm text-muted-foreground">每小时最多</span>
+                    <Input
+                      type="number"
+                      min={0}
+                      value={notification.maxPerHour ?? 20}
+                      onChange={(e) =>
+                        setNotification((prev) => ({
+                          ...prev,
+                          maxPerHour: Number(e.target.value) || 0,
+                        }))
+                      }
+                      className="w-16"
+                    />
+                    <span className="text-sm text-muted-foreground">条，单个软件最多</span>
+                    <Input
+                      type="number"
+                      min={0}
+                      value={notification.maxPerSoftwarePerHour ?? 2}
+                      onChange={(e) =>
+                        setNotification((prev) => ({
+                          ...prev,
+                          maxPerSoftwarePerHour: Number(e.target.value) || 0,
+                        }))
+                      }
+                      className="w-16"
                     />
-                    <span className="text-sm text-muted-foreground">点</span>
+                    <span className="text-sm text-muted-foreground">条</span>
                   </div>
                   <p className="text-xs text-muted-foreground">
-                    在此时段内不发送通知（留空则不限制）
+                    防止版本比较出错时短时间内收到大量通知，0 表示不限制
                   </p>
                 </div>
+
+                <div className="space-y-2 pl-2 border-l-2 border-muted">
+                  <div className="flex items-center justify-between">
+                    <Label htmlFor="webhookEnabled" className="text-sm font-normal">
+                      Webhook 通知
+                    </Label>
+                    <Switch
+                      id="webhookEnabled"
+                      checked={notification.webhook?.enabled ?? false}
+                      onCheckedChange={(checked) =>
+                        setNotification((prev) => ({
+                          ...prev,
+                          webhook: { url: prev.webhook?.url ?? "", enabled: checked },
+                        }))
+                      }
+                    />
+                  </div>
+                  {notification.webhook?.enabled && (
+                    <Input
+                      id="webhookUrl"
+                      value={notification.webhook.url}
+                      onChange={(e) =>
+                        setNotification((prev) => ({
+                          ...prev,
+                          webhook: { enabled: true, url: e.target.value },
+                        }))
+                      }
+                      placeholder="https://hooks.slack.com/services/..."
+                    />
+                  )}
+                  <p className="text-xs text-muted-foreground">
+                    同时推送到 Slack/Discord 兼容的 incoming webhook
+                  </p>
+                </div>
+
+                <div className="flex items-center gap-2">
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={handleTestNotification}
+                    disabled={isTestingNotification}
+                  >
+                    {isTestingNotification ? "发送中..." : "发送测试通知"}
+                  </Button>
+                  <span className="text-xs text-muted-foreground">使用已保存的设置</span>
+                </div>
               </>
             )}
           </div>
+
+          <div className="space-y-4 border-t pt-4">
+            <h3 className="text-sm font-medium">指标服务</h3>
+
+            <div className="flex items-center justify-between">
+              <div className="space-y-0.5">
+                <Label htmlFor="metricsEnabled">Prometheus 指标</Label>
+                <p className="text-xs text-muted-foreground">
+                  在本机提供 /metrics，供监控面板抓取更新状态
+                </p>
+              </div>
+              <Switch
+                id="metricsEnabled"
+                checked={metricsEnabled}
+                onCheckedChange={setMetricsEnabled}
+              />
+            </div>
+
+            {metricsEnabled && (
+              <div className="space-y-2">
+                <Label htmlFor="metricsPort">端口</Label>
+                <Input
+                  id="metricsPort"
+                  type="number"
+                  min={1}
+                  max={65535}
+                  value={metricsPort}
+                  onChange={(e) => setMetricsPort(Number(e.target.value))}
+                />
+              </div>
+            )}
+          </div>
         </div>
 
         <DialogFooter>
@@ -10,6 +10,7 @@ import {
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
 import {
   Select,
   SelectContent,
@@ -17,26 +18,110 @@ import {
   SelectTrigger,
   SelectValue,
 } from "@/components/ui/select";
-import type { SoftwareFormData, SourceType } from "@/types/software";
+import type {
+  SoftwareFormData,
+  SourceConfig,
+  ReleaseChannel,
+  SourceType,
+  VersionFrom,
+  VersionPreview,
+} from "@/types/software";
+import {
+  PRERELEASE_SOURCE_TYPES,
+  RELEASE_CHANNEL_SOURCE_TYPES,
+  VERSION_FILTER_SOURCE_TYPES,
+} from "@/types/software";
 
 interface AddSoftwareDialogProps {
   open: boolean;
   onOpenChange: (open: boolean) => void;
   onSubmit: (form: SoftwareFormData) => Promise<void>;
+  onValidateSource?: (source: SourceConfig) => Promise<VersionPreview>;
 }
 
+type PreviewState =
+  | { status: "idle" }
+  | { status: "loading" }
+  | { status: "ok"; preview: VersionPreview }
+  | { status: "error"; message: string };
+
 export function AddSoftwareDialog({
   open,
   onOpenChange,
   onSubmit,
+  onValidateSource,
 }: AddSoftwareDialogProps) {
   const [url, setUrl] = useState("");
   const [name, setName] = useState("");
   const [sourceType, setSourceType] = useState<SourceType>("github-release");
   const [identifier, setIdentifier] = useState("");
+  const [includePrerelease, setIncludePrerelease] = useState(false);
+  const [versionFilter, setVersionFilter] = useState("");
+  const [versionExclude, setVersionExclude] = useState("");
+  const [requiredAsset, setRequiredAsset] = useState("");
+  const [tagPrefix, setTagPrefix] = useState("");
+  const [versionFrom, setVersionFrom] = useState<VersionFrom>("tag-name");
+  const [channel, setChannel] = useState<ReleaseChannel>("any");
+  const [versionExtract, setVersionExtract] = useState("");
+  const [authToken, setAuthToken] = useState("");
+  const [compareByDate, setCompareByDate] = useState(false);
   const [localCommand, setLocalCommand] = useState("");
   const [versionArg, setVersionArg] = useState("--version");
+  const [versionRegex, setVersionRegex] = useState("");
+  const [commandTimeout, setCommandTimeout] = useState("");
+  const [workingDir, setWorkingDir] = useState("");
+  const [outputJsonPointer, setOutputJsonPointer] = useState("");
+  const [useShell, setUseShell] = useState(false);
+  const [versionFile, setVersionFile] = useState("");
+  const [jsonPointer, setJsonPointer] = useState("");
+  const [checkInterval, setCheckInterval] = useState("");
   const [isSubmitting, setIsSubmitting] = useState(false);
+  const [preview, setPreview] = useState<PreviewState>({ status: "idle" });
+
+  // 只在支持的数据源上提交版本筛选
+  const versionFilterFields = VERSION_FILTER_SOURCE_TYPES.includes(sourceType)
+    ? {
+        versionFilterRegex: versionFilter.trim() || null,
+        versionExcludeRegex: versionExclude.trim() || null,
+      }
+    : {};
+  const channelField = RELEASE_CHANNEL_SOURCE_TYPES.includes(sourceType) ? { channel } : {};
+  const requiredAssetField =
+    sourceType === "github-release" ? { requiredAsset: requiredAsset.trim() || null } : {};
+  const tagPrefixField =
+    sourceType === "github-tags" ? { tagPrefix: tagPrefix.trim() || null } : {};
+  const releaseVersionFields =
+    sourceType === "github-release"
+      ? { versionFrom, versionExtractRegex: versionExtract.trim() || null }
+      : {};
+  const isGithubSource = sourceType === "github-release" || sourceType === "github-tags";
+  const authTokenField = isGithubSource ? { authToken: authToken.trim() || null } : {};
+
+  // 标识符失焦时预览远程最新版本
+  const handleValidateSource = async () => {
+    if (!onValidateSource || !identifier.trim()) {
+      setPreview({ status: "idle" });
+      return;
+    }
+
+    setPreview({ status: "loading" });
+    try {
+      const result = await onValidateSource({
+        type: sourceType,
+        identifier: identifier.trim(),
+        includePrerelease: PRERELEASE_SOURCE_TYPES.includes(sourceType) && includePrerelease,
+        ...versionFilterFields,
+        ...channelField,
+        ...requiredAssetField,
+        ...tagPrefixField,
+        ...releaseVersionFields,
+        ...authTokenField,
+      });
+      setPreview({ status: "ok", preview: result });
+    } catch (error) {
+      setPreview({ status: "error", message: String(error) });
+    }
+  };
 
   // 解析 GitHub URL
   const parseGitHubUrl = (inputUrl: string) => {
@@ -89,13 +174,38 @@ export function AddSoftwareDialog({
         source: {
           type: sourceType,
           identifier,
+          includePrerelease: PRERELEASE_SOURCE_TYPES.includes(sourceType) && includePrerelease,
+          ...versionFilterFields,
+          ...channelField,
+          ...requiredAssetField,
+          ...tagPrefixField,
+          ...releaseVersionFields,
+          ...authTokenField,
+          compareBy: compareByDate ? "published-date" : "version",
         },
-        localVersionConfig: localCommand
+        localVersionConfig: versionFile.trim()
+          ? {
+              source: {
+                type: "file-read",
+                path: versionFile.trim(),
+                jsonPointer: jsonPointer.trim() || undefined,
+              },
+              command: "",
+            }
+          : localCommand
           ? {
               command: localCommand,
-              versionArg: versionArg || undefined,
+              versionArg: useShell ? undefined : versionArg || undefined,
+              versionRegex: versionRegex.trim() || undefined,
+              timeoutSeconds: Number(commandTimeout) > 0 ? Number(commandTimeout) : undefined,
+              workingDir: workingDir.trim() || undefined,
+              useShell: useShell || undefined,
+              source: outputJsonPointer.trim()
+                ? { type: "command-json", jsonPointer: outputJsonPointer.trim() }
+                : undefined,
             }
           : undefined,
+        checkIntervalMinutes: Number(checkInterval) > 0 ? Number(checkInterval) : undefined,
       };
 
       await onSubmit(form);
@@ -113,8 +223,27 @@ export function AddSoftwareDialog({
     setName("");
     setSourceType("github-release");
     setIdentifier("");
+    setIncludePrerelease(false);
+    setVersionFilter("");
+    setVersionExclude("");
+    setRequiredAsset("");
+    setTagPrefix("");
+    setVersionFrom("tag-name");
+    setChannel("any");
+    setVersionExtract("");
+    setAuthToken("");
+    setCompareByDate(false);
     setLocalCommand("");
     setVersionArg("--version");
+    setVersionRegex("");
+    setCommandTimeout("");
+    setWorkingDir("");
+    setOutputJsonPointer("");
+    setUseShell(false);
+    setVersionFile("");
+    setJsonPointer("");
+    setCheckInterval("");
+    setPreview({ status: "idle" });
   };
 
   const getIdentifierPlaceholder = () => {
@@ -124,12 +253,56 @@ export function AddSoftwareDialog({
         return "owner/repo (如 facebook/react)";
       case "homebrew":
         return "formula 名称 (如 git)";
+      case "homebrew-cask":
+        return "cask 名称 (如 visual-studio-code)";
       case "npm":
         return "包名 (如 react, @types/node)";
       case "pypi":
         return "包名 (如 django, requests)";
       case "cargo":
         return "crate 名称 (如 tokio, serde)";
+      case "docker-hub":
+        return "镜像名 (如 nginx, bitnami/redis)";
+      case "rubygems":
+        return "gem 名称 (如 rails, rake)";
+      case "nuget":
+        return "包 ID (如 Newtonsoft.Json)";
+      case "go-module":
+        return "模块路径 (如 golang.org/x/tools/gopls)";
+      case "custom-json":
+        return "URL|$.路径 (如 https://example.com/v.json|$.data.version)";
+      case "feed":
+        return "Feed 地址，可追加 |正则 (如 https://github.com/o/r/releases.atom)";
+      case "web-scrape":
+        return "URL|正则，取第一个捕获组 (如 https://example.com/download|v([\d.]+))";
+      case "bitbucket":
+        return "workspace/repo (如 atlassian/python-bitbucket)";
+      case "maven":
+        return "groupId:artifactId (如 com.google.guava:guava)";
+      case "conda":
+        return "conda-forge/numpy";
+      case "packagist":
+        return "vendor/package";
+      case "hex":
+        return "phoenix";
+      case "flathub":
+        return "org.mozilla.firefox";
+      case "aur":
+        return "yay";
+      case "sourceforge":
+        return "keepass";
+      case "winget":
+        return "Microsoft.PowerToys";
+      case "cran":
+        return "ggplot2";
+      case "gitea":
+        return "https://codeberg.org/forgejo/forgejo";
+      case "git-tags":
+        return "https://git.example.com/project.git";
+      case "fdroid":
+        return "org.fdroid.fdroid";
+      case "ubuntu-package":
+        return "noble/openssl 或 ppa:mozillateam/ppa/noble/firefox";
     }
   };
 
@@ -181,9 +354,31 @@ export function AddSoftwareDialog({
                 <SelectItem value="github-release">GitHub Release</SelectItem>
                 <SelectItem value="github-tags">GitHub Tags</SelectItem>
                 <SelectItem value="homebrew">Homebrew</SelectItem>
+                <SelectItem value="homebrew-cask">Homebrew Cask</SelectItem>
                 <SelectItem value="npm">npm Registry</SelectItem>
                 <SelectItem value="pypi">PyPI</SelectItem>
                 <SelectItem value="cargo">crates.io (Cargo)</SelectItem>
+                <SelectItem value="docker-hub">Docker Hub</SelectItem>
+                <SelectItem value="rubygems">RubyGems</SelectItem>
+                <SelectItem value="nuget">NuGet</SelectItem>
+                <SelectItem value="go-module">Go Module</SelectItem>
+                <SelectItem value="custom-json">自定义 JSON</SelectItem>
+                <SelectItem value="feed">RSS/Atom 订阅</SelectItem>
+                <SelectItem value="web-scrape">网页抓取</SelectItem>
+                <SelectItem value="bitbucket">Bitbucket</SelectItem>
+                <SelectItem value="maven">Maven Central</SelectItem>
+                <SelectItem value="conda">Conda</SelectItem>
+                <SelectItem value="packagist">Packagist</SelectItem>
+                <SelectItem value="hex">Hex.pm</SelectItem>
+                <SelectItem value="flathub">Flathub</SelectItem>
+                <SelectItem value="aur">AUR</SelectItem>
+                <SelectItem value="sourceforge">SourceForge</SelectItem>
+                <SelectItem value="winget">Winget</SelectItem>
+                <SelectItem value="cran">CRAN</SelectItem>
+                <SelectItem value="gitea">Gitea/Forgejo</SelectItem>
+                <SelectItem value="git-tags">Git Tags（需要安装 git）</SelectItem>
+                <SelectItem value="fdroid">F-Droid</SelectItem>
+                <SelectItem value="ubuntu-package">Ubuntu 软件包/PPA</SelectItem>
               </SelectContent>
             </Select>
           </div>
@@ -194,11 +389,176 @@ export function AddSoftwareDialog({
               id="identifier"
               value={identifier}
               onChange={(e) => setIdentifier(e.target.value)}
+              onBlur={handleValidateSource}
               placeholder={getIdentifierPlaceholder()}
               required
             />
+            {preview.status === "loading" && (
+              <p className="text-xs text-muted-foreground">正在获取最新版本...</p>
+            )}
+            {preview.status === "ok" && (
+              <p className="text-xs text-muted-foreground">
+                最新版本: {preview.preview.latestVersion}
+                {preview.preview.assetUrl && (
+                  <span className="block truncate">附件: {preview.preview.assetUrl}</span>
+                )}
+              </p>
+            )}
+            {preview.status === "error" && (
+              <p className="text-xs text-destructive">{preview.message}</p>
+            )}
+          </div>
+
+          {PRERELEASE_SOURCE_TYPES.includes(sourceType) && (
+            <div className="flex items-center justify-between">
+              <div className="space-y-0.5">
+                <Label htmlFor="include-prerelease">包含预发布版本</Label>
+                <p className="text-xs text-muted-foreground">
+                  GitHub 会从 release 列表中选取最新版本，跳过草稿
+                </p>
+              </div>
+              <Switch
+                id="include-prerelease"
+                checked={includePrerelease}
+                onCheckedChange={setIncludePrerelease}
+              />
+            </div>
+          )}
+
+          <div className="flex items-center justify-between">
+            <div className="space-y-0.5">
+              <Label htmlFor="compare-by-date">按发布时间判断更新</Label>
+              <p className="text-xs text-muted-foreground">
+                适用于版本号是 commit hash 或无法比较的数据源，发布时间晚于上次确认时提示更新
+              </p>
+            </div>
+            <Switch
+              id="compare-by-date"
+              checked={compareByDate}
+              onCheckedChange={setCompareByDate}
+            />
           </div>
 
+          {VERSION_FILTER_SOURCE_TYPES.includes(sourceType) && (
+            <div className="grid grid-cols-2 gap-2">
+              <div className="space-y-2">
+                <Label htmlFor="version-filter">版本筛选正则（可选）</Label>
+                <Input
+                  id="version-filter"
+                  value={versionFilter}
+                  onChange={(e) => setVersionFilter(e.target.value)}
+                  placeholder="如 ^v\d+\.\d+\.\d+$"
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="version-exclude">排除版本正则（可选）</Label>
+                <Input
+                  id="version-exclude"
+                  value={versionExclude}
+                  onChange={(e) => setVersionExclude(e.target.value)}
+                  placeholder="如 nightly"
+                />
+              </div>
+            </div>
+          )}
+
+          {RELEASE_CHANNEL_SOURCE_TYPES.includes(sourceType) && (
+            <div className="space-y-2">
+              <Label htmlFor="release-channel">发布渠道</Label>
+              <Select value={channel} onValueChange={(v) => setChannel(v as ReleaseChannel)}>
+                <SelectTrigger id="release-channel">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="any">不限</SelectItem>
+                  <SelectItem value="stable">仅正式版本</SelectItem>
+                  <SelectItem value="prerelease">仅预发布版本</SelectItem>
+                </SelectContent>
+              </Select>
+              <p className="text-xs text-muted-foreground">
+                限定渠道后忽略“包含预发布版本”开关，只在该渠道的版本中取最新
+              </p>
+            </div>
+          )}
+
+          {sourceType === "github-release" && (
+            <div className="space-y-2">
+              <Label htmlFor="required-asset">必需的附件（可选）</Label>
+              <Input
+                id="required-asset"
+                value={requiredAsset}
+                onChange={(e) => setRequiredAsset(e.target.value)}
+                placeholder="如 *-linux-x86_64.tar.gz"
+              />
+              <p className="text-xs text-muted-foreground">
+                跳过还没有上传匹配附件的 release，支持 * 和 ? 通配符
+              </p>
+            </div>
+          )}
+
+          {sourceType === "github-release" && (
+            <div className="grid grid-cols-2 gap-4">
+              <div className="space-y-2">
+                <Label htmlFor="version-from">版本号来源</Label>
+                <Select
+                  value={versionFrom}
+                  onValueChange={(v) => setVersionFrom(v as VersionFrom)}
+                >
+                  <SelectTrigger id="version-from">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="tag-name">Tag 名</SelectItem>
+                    <SelectItem value="release-name">Release 标题</SelectItem>
+                  </SelectContent>
+                </Select>
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="version-extract">提取正则（可选）</Label>
+                <Input
+                  id="version-extract"
+                  value={versionExtract}
+                  onChange={(e) => setVersionExtract(e.target.value)}
+                  placeholder="如 Release (\S+)"
+                />
+              </div>
+              <p className="col-span-2 text-xs text-muted-foreground">
+                有捕获组时取第一个捕获组作为版本号，读取不到版本号的 release 会被跳过
+              </p>
+            </div>
+          )}
+
+          {sourceType === "github-tags" && (
+            <div className="space-y-2">
+              <Label htmlFor="tag-prefix">Tag 前缀（可选）</Label>
+              <Input
+                id="tag-prefix"
+                value={tagPrefix}
+                onChange={(e) => setTagPrefix(e.target.value)}
+                placeholder="如 package-a/"
+              />
+              <p className="text-xs text-muted-foreground">
+                monorepo 中只比较以此开头的 tag，比较前去掉前缀
+              </p>
+            </div>
+          )}
+
+          {isGithubSource && (
+            <div className="space-y-2">
+              <Label htmlFor="auth-token">仓库 Token（可选）</Label>
+              <Input
+                id="auth-token"
+                type="password"
+                value={authToken}
+                onChange={(e) => setAuthToken(e.target.value)}
+                placeholder="ghp_xxxx..."
+              />
+              <p className="text-xs text-muted-foreground">
+                只用于此仓库，优先于设置中的全局 GitHub Token
+              </p>
+            </div>
+          )}
+
           <div className="border-t pt-4">
             <p className="text-sm text-muted-foreground mb-3">
               本地版本检测（可选）
@@ -213,16 +573,105 @@ export function AddSoftwareDialog({
               />
             </div>
             {localCommand && (
+              <div className="flex items-center justify-between mt-2">
+                <div className="space-y-0.5">
+                  <Label htmlFor="use-shell">通过 shell 执行</Label>
+                  <p className="text-xs text-muted-foreground">
+                    支持管道等语法，如 pip show foo | grep Version；命令会被 shell 解析，请只填写可信的命令
+                  </p>
+                </div>
+                <Switch id="use-shell" checked={useShell} onCheckedChange={setUseShell} />
+              </div>
+            )}
+            {localCommand && !useShell && (
               <div className="space-y-2 mt-2">
                 <Label htmlFor="versionArg">版本参数</Label>
                 <Input
                   id="versionArg"
                   value={versionArg}
                   onChange={(e) => setVersionArg(e.target.value)}
-                  placeholder="--version"
+                  placeholder="--version（多个参数用空格分隔）"
+                />
+              </div>
+            )}
+            {localCommand && (
+              <div className="space-y-2 mt-2">
+                <Label htmlFor="versionRegex">版本提取正则（可选）</Label>
+                <Input
+                  id="versionRegex"
+                  value={versionRegex}
+                  onChange={(e) => setVersionRegex(e.target.value)}
+                  placeholder="取第一个捕获组，如 git version (\S+)"
+                />
+              </div>
+            )}
+            {localCommand && (
+              <div className="space-y-2 mt-2">
+                <Label htmlFor="commandTimeout">命令超时（秒，可选）</Label>
+                <Input
+                  id="commandTimeout"
+                  type="number"
+                  min={1}
+                  value={commandTimeout}
+                  onChange={(e) => setCommandTimeout(e.target.value)}
+                  placeholder="默认 10 秒"
                 />
               </div>
             )}
+            {localCommand && (
+              <div className="space-y-2 mt-2">
+                <Label htmlFor="workingDir">工作目录（可选）</Label>
+                <Input
+                  id="workingDir"
+                  value={workingDir}
+                  onChange={(e) => setWorkingDir(e.target.value)}
+                  placeholder="命令执行时所在的目录"
+                />
+              </div>
+            )}
+            {localCommand && (
+              <div className="space-y-2 mt-2">
+                <Label htmlFor="outputJsonPointer">输出 JSON 路径（可选）</Label>
+                <Input
+                  id="outputJsonPointer"
+                  value={outputJsonPointer}
+                  onChange={(e) => setOutputJsonPointer(e.target.value)}
+                  placeholder="命令输出 JSON 时使用，如 /version"
+                />
+              </div>
+            )}
+            <div className="space-y-2 mt-2">
+              <Label htmlFor="versionFile">版本文件（代替本地命令）</Label>
+              <Input
+                id="versionFile"
+                value={versionFile}
+                onChange={(e) => setVersionFile(e.target.value)}
+                placeholder="如 /opt/tool/VERSION 或 package.json 的路径"
+              />
+            </div>
+            {versionFile && (
+              <div className="space-y-2 mt-2">
+                <Label htmlFor="jsonPointer">JSON 路径（可选）</Label>
+                <Input
+                  id="jsonPointer"
+                  value={jsonPointer}
+                  onChange={(e) => setJsonPointer(e.target.value)}
+                  placeholder="如 $.version，留空则按版本号格式匹配文件内容"
+                />
+              </div>
+            )}
+          </div>
+
+          <div className="space-y-2">
+            <Label htmlFor="checkInterval">检查间隔（分钟，可选）</Label>
+            <Input
+              id="checkInterval"
+              type="number"
+              min={1}
+              value={checkInterval}
+              onChange={(e) => setCheckInterval(e.target.value)}
+              placeholder="留空使用全局自动刷新间隔"
+            />
           </div>
 
           <DialogFooter>
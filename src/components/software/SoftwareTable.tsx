@@ -1,5 +1,5 @@
 import { useState } from "react";
-import { RefreshCw, MoreHorizontal, Pencil, Trash2 } from "lucide-react";
+import { RefreshCw, MoreHorizontal, Pencil, Copy, Trash2, AlertTriangle } from "lucide-react";
 import {
   Table,
   TableBody,
@@ -25,22 +25,26 @@ import {
 } from "@/components/ui/dialog";
 import { VersionBadge } from "./VersionBadge";
 import { fromNow } from "@/lib/time";
-import type { Software } from "@/types/software";
-import { getUpdateStatus } from "@/types/software";
+import type { BackoffStatus, Software } from "@/types/software";
+import { getPublishedDateStatus, getUpdateStatus, isSnoozed } from "@/types/software";
 
 interface SoftwareTableProps {
   softwares: Software[];
+  backoffStatuses?: BackoffStatus[];
   onRefresh: (id: string) => void;
   onEdit: (software: Software) => void;
   onDelete: (id: string) => void;
+  onDuplicate: (id: string) => void;
   isRefreshing: string | null;
 }
 
 export function SoftwareTable({
   softwares,
+  backoffStatuses = [],
   onRefresh,
   onEdit,
   onDelete,
+  onDuplicate,
   isRefreshing,
 }: SoftwareTableProps) {
   const [deleteTarget, setDeleteTarget] = useState<Software | null>(null);
@@ -86,9 +90,16 @@ export function SoftwareTable({
             return dateB - dateA;
           })
           .map((software) => {
-          const status = getUpdateStatus(
-            software.latestVersion,
-            software.localVersion
+          const updateStatus =
+            software.source.compareBy === "published-date"
+              ? getPublishedDateStatus(software)
+              : getUpdateStatus(software.latestVersion, software.localVersion);
+          const status =
+            updateStatus === "update-available" && isSnoozed(software)
+              ? "snoozed"
+              : updateStatus;
+          const backoff = backoffStatuses.find(
+            (b) => b.softwareId === software.id
           );
           return (
             <TableRow key={software.id}>
@@ -129,7 +140,19 @@ export function SoftwareTable({
               </TableCell>
               <TableCell>{software.localVersion || "-"}</TableCell>
               <TableCell>
-                <VersionBadge status={status} />
+                <div className="flex items-center gap-1">
+                  <VersionBadge status={status} />
+                  {software.lastError && (
+                    <span title={`${fromNow(software.lastErrorAt)}检查失败：${software.lastError}`}>
+                      <AlertTriangle className="h-4 w-4 text-yellow-500" />
+                    </span>
+                  )}
+                </div>
+                {backoff && (
+                  <p className="text-xs text-muted-foreground mt-1">
+                    连续失败 {backoff.consecutiveFailures} 次，{fromNow(backoff.nextRetryAt)}重试
+                  </p>
+                )}
               </TableCell>
               <TableCell>
                 <div className="flex items-center gap-1">
@@ -156,6 +179,10 @@ export function SoftwareTable({
                         <Pencil className="h-4 w-4 mr-2" />
                         编辑
                       </DropdownMenuItem>
+                      <DropdownMenuItem onClick={() => onDuplicate(software.id)}>
+                        <Copy className="h-4 w-4 mr-2" />
+                        复制
+                      </DropdownMenuItem>
                       <DropdownMenuItem
                         className="text-destructive"
                         onClick={() => handleDeleteClick(software)}
@@ -11,6 +11,8 @@ export function VersionBadge({ status }: VersionBadgeProps) {
       return <Badge variant="success">最新</Badge>;
     case "update-available":
       return <Badge variant="warning">可更新</Badge>;
+    case "snoozed":
+      return <Badge variant="secondary">已暂缓</Badge>;
     case "unknown":
     default:
       return <Badge variant="secondary">未知</Badge>;
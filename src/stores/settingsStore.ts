@@ -1,6 +1,13 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
-import type { AppSettings, CacheConfig } from "@/types/software";
+import type {
+  AppSettings,
+  CacheConfig,
+  GithubRateLimit,
+  NotificationChannel,
+  NotificationTestResult,
+  SchedulerStatus,
+} from "@/types/software";
 import { DEFAULT_NOTIFICATION_CONFIG } from "@/types/software";
 
 interface SettingsState {
@@ -14,6 +21,14 @@ interface SettingsState {
   updateCacheConfig: (config: Partial<CacheConfig>) => void;
   setGithubToken: (token: string | undefined) => void;
   clearCache: () => Promise<void>;
+  fetchGithubRateLimit: () => Promise<GithubRateLimit>;
+  // 按已保存的设置发送测试通知，不指定渠道时测试所有已配置的渠道
+  sendTestNotification: (channel?: NotificationChannel) => Promise<NotificationTestResult[]>;
+  pauseScheduler: () => Promise<void>;
+  resumeScheduler: () => Promise<void>;
+  fetchSchedulerStatus: () => Promise<SchedulerStatus>;
+  // 下一次自动检查时间 (ISO 8601)，未调度时为 null
+  fetchNextCheckTime: () => Promise<string | null>;
 }
 
 const defaultSettings: AppSettings = {
@@ -80,4 +95,28 @@ export const useSettingsStore = create<SettingsState>((set) => ({
       throw error;
     }
   },
+
+  fetchGithubRateLimit: async () => {
+    return invoke<GithubRateLimit>("get_github_rate_limit");
+  },
+
+  sendTestNotification: async (channel) => {
+    return invoke<NotificationTestResult[]>("send_test_notification", { channel });
+  },
+
+  pauseScheduler: async () => {
+    await invoke("pause_scheduler");
+  },
+
+  resumeScheduler: async () => {
+    await invoke("resume_scheduler");
+  },
+
+  fetchSchedulerStatus: async () => {
+    return invoke<SchedulerStatus>("scheduler_status");
+  },
+
+  fetchNextCheckTime: async () => {
+    return invoke<string | null>("get_next_check_time");
+  },
 }));
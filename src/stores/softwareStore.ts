@@ -1,30 +1,97 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  BackoffStatus,
+  BatchAddResult,
+  ImportResult,
+  NotificationLogEntry,
   Software,
   SoftwareFormData,
+  SourceConfig,
   VersionCheckResult,
+  VersionHistoryEntry,
+  VersionPreview,
 } from "@/types/software";
 
 interface SoftwareState {
   softwares: Software[];
+  backoffStatuses: BackoffStatus[];
   isLoading: boolean;
   isChecking: boolean;
   error: string | null;
 
   // Actions
   fetchSoftwares: () => Promise<void>;
-  addSoftware: (form: SoftwareFormData) => Promise<Software>;
+  fetchSoftware: (id: string) => Promise<Software>;
+  fetchBackoffStatuses: () => Promise<void>;
+  fetchNotificationHistory: (
+    softwareId?: string,
+    limit?: number
+  ) => Promise<NotificationLogEntry[]>;
+  fetchVersionHistory: (id: string, limit?: number) => Promise<VersionHistoryEntry[]>;
+  // allowDuplicate 为 true 时允许添加与已有软件相同的数据源
+  addSoftware: (form: SoftwareFormData, allowDuplicate?: boolean) => Promise<Software>;
+  // 批量添加，返回每一条的成功或失败
+  addSoftwares: (forms: SoftwareFormData[]) => Promise<BatchAddResult>;
   updateSoftware: (id: string, form: SoftwareFormData) => Promise<Software>;
+  // 移入回收站，可通过 restoreSoftware 恢复
   deleteSoftware: (id: string) => Promise<void>;
+  // 复制软件，名称后加 " (copy)"，不保留通知和错误状态
+  duplicateSoftware: (id: string) => Promise<Software>;
+  fetchDeletedSoftwares: () => Promise<Software[]>;
+  restoreSoftware: (id: string) => Promise<Software>;
+  // 清空回收站，返回删除的数量
+  purgeDeleted: () => Promise<number>;
+  // 有可用更新的已启用软件数量，用于托盘角标
+  countUpdates: () => Promise<number>;
+  exportSoftwares: () => Promise<string>;
+  importSoftwares: (json: string, merge: boolean, validate: boolean) => Promise<ImportResult>;
   toggleSoftware: (id: string, enabled: boolean) => Promise<void>;
+  // 批量启用或禁用，返回实际更新的数量
+  toggleSoftwares: (ids: string[], enabled: boolean) => Promise<number>;
+  // 启用或禁用所有软件（不包括回收站）
+  setAllEnabled: (enabled: boolean) => Promise<number>;
+  // 暂缓当前最新版本的提醒，until 为 ISO 8601 时间
+  snoozeSoftware: (id: string, until: string) => Promise<Software>;
   checkVersion: (id: string, forceRefresh?: boolean) => Promise<VersionCheckResult>;
-  checkAllVersions: () => Promise<VersionCheckResult[]>;
+  refreshLocalVersion: (id: string) => Promise<VersionCheckResult>;
+  validateSource: (source: SourceConfig) => Promise<VersionPreview>;
+  fetchReleaseNotes: (id: string) => Promise<string | null>;
+  fetchSourceUrl: (id: string) => Promise<string>;
+  // 软件列表不包含钥匙串中的数据源 token，编辑时单独读取
+  fetchSourceToken: (id: string) => Promise<string | null>;
+  fetchSoftwaresByTag: (tag: string) => Promise<Software[]>;
+  reorderSoftwares: (orderedIds: string[]) => Promise<void>;
+  searchSoftwares: (query: string) => Promise<Software[]>;
+  // forceRefresh 为 true 时跳过缓存，重新获取所有启用的软件
+  checkAllVersions: (forceRefresh?: boolean) => Promise<VersionCheckResult[]>;
+  // 只重新检查超过 maxAgeMinutes 分钟未检查的启用软件
+  checkStaleVersions: (maxAgeMinutes: number) => Promise<VersionCheckResult[]>;
+  // 取消进行中的全部检查，checkAllVersions 会返回已完成的部分结果
+  cancelCheck: () => Promise<void>;
   clearError: () => void;
 }
 
+// 将批量检查结果合并到软件列表中
+function applyCheckResults(softwares: Software[], results: VersionCheckResult[]): Software[] {
+  return softwares.map((s) => {
+    const result = results.find((r) => r.softwareId === s.id);
+    if (result) {
+      return {
+        ...s,
+        latestVersion: result.latestVersion,
+        localVersion: result.localVersion,
+        publishedAt: result.publishedAt,
+        lastCheckedAt: new Date().toISOString(),
+      };
+    }
+    return s;
+  });
+}
+
 export const useSoftwareStore = create<SoftwareState>((set) => ({
   softwares: [],
+  backoffStatuses: [],
   isLoading: false,
   isChecking: false,
   error: null,
@@ -39,9 +106,45 @@ export const useSoftwareStore = create<SoftwareState>((set) => ({
     }
   },
 
-  addSoftware: async (form) => {
+  fetchSoftware: async (id) => {
+    try {
+      const software = await invoke<Software>("get_software", { id });
+      set((state) => ({
+        softwares: state.softwares.map((s) => (s.id === id ? software : s)),
+      }));
+      return software;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  fetchBackoffStatuses: async () => {
+    try {
+      const backoffStatuses = await invoke<BackoffStatus[]>("get_backoff_statuses");
+      set({ backoffStatuses });
+    } catch (error) {
+      set({ error: String(error) });
+    }
+  },
+
+  fetchNotificationHistory: async (softwareId, limit = 50) => {
+    return invoke<NotificationLogEntry[]>("get_notification_history", {
+      softwareId: softwareId ?? null,
+      limit,
+    });
+  },
+
+  fetchVersionHistory: async (id, limit = 50) => {
+    return invoke<VersionHistoryEntry[]>("get_version_history", { id, limit });
+  },
+
+  addSoftware: async (form, allowDuplicate = false) => {
     try {
-      const software = await invoke<Software>("add_software", { form });
+      const software = await invoke<Software>("add_software", {
+        form,
+        allowDuplicate,
+      });
       set((state) => ({ softwares: [...state.softwares, software] }));
       return software;
     } catch (error) {
@@ -50,6 +153,17 @@ export const useSoftwareStore = create<SoftwareState>((set) => ({
     }
   },
 
+  addSoftwares: async (forms) => {
+    try {
+      const result = await invoke<BatchAddResult>("add_softwares", { forms });
+      set((state) => ({ softwares: [...state.softwares, ...result.added] }));
+      return result;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
   updateSoftware: async (id, form) => {
     try {
       const software = await invoke<Software>("update_software", { id, form });
@@ -75,6 +189,72 @@ export const useSoftwareStore = create<SoftwareState>((set) => ({
     }
   },
 
+  duplicateSoftware: async (id) => {
+    try {
+      const software = await invoke<Software>("duplicate_software", { id });
+      set((state) => ({ softwares: [...state.softwares, software] }));
+      return software;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  fetchDeletedSoftwares: async () => {
+    const softwares = await invoke<Software[]>("get_all_softwares", {
+      includeDeleted: true,
+    });
+    return softwares.filter((s) => s.deletedAt);
+  },
+
+  restoreSoftware: async (id) => {
+    try {
+      const software = await invoke<Software>("restore_software", { id });
+      set((state) => ({
+        softwares: [...state.softwares, software].sort(
+          (a, b) => (a.sortOrder ?? 0) - (b.sortOrder ?? 0)
+        ),
+      }));
+      return software;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  purgeDeleted: async () => {
+    try {
+      return await invoke<number>("purge_deleted");
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  countUpdates: async () => {
+    return invoke<number>("count_updates");
+  },
+
+  exportSoftwares: async () => {
+    return invoke<string>("export_softwares");
+  },
+
+  importSoftwares: async (json, merge, validate) => {
+    try {
+      const result = await invoke<ImportResult>("import_softwares", {
+        json,
+        merge,
+        validate,
+      });
+      const softwares = await invoke<Software[]>("get_all_softwares");
+      set({ softwares });
+      return result;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
   toggleSoftware: async (id, enabled) => {
     try {
       await invoke("toggle_software", { id, enabled });
@@ -89,6 +269,47 @@ export const useSoftwareStore = create<SoftwareState>((set) => ({
     }
   },
 
+  toggleSoftwares: async (ids, enabled) => {
+    try {
+      const updated = await invoke<number>("toggle_softwares", { ids, enabled });
+      set((state) => ({
+        softwares: state.softwares.map((s) =>
+          ids.includes(s.id) ? { ...s, enabled } : s
+        ),
+      }));
+      return updated;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  setAllEnabled: async (enabled) => {
+    try {
+      const updated = await invoke<number>("set_all_enabled", { enabled });
+      set((state) => ({
+        softwares: state.softwares.map((s) => ({ ...s, enabled })),
+      }));
+      return updated;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  snoozeSoftware: async (id, until) => {
+    try {
+      const software = await invoke<Software>("snooze_software", { id, until });
+      set((state) => ({
+        softwares: state.softwares.map((s) => (s.id === id ? software : s)),
+      }));
+      return software;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
   checkVersion: async (id, forceRefresh = false) => {
     set({ isChecking: true });
     try {
@@ -120,28 +341,69 @@ export const useSoftwareStore = create<SoftwareState>((set) => ({
     }
   },
 
-  checkAllVersions: async () => {
+  refreshLocalVersion: async (id) => {
+    try {
+      // 只重新读取本地版本，不请求远程
+      const result = await invoke<VersionCheckResult>("refresh_local_version", { id });
+      set((state) => ({
+        softwares: state.softwares.map((s) =>
+          s.id === id ? { ...s, localVersion: result.localVersion } : s
+        ),
+      }));
+      return result;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  validateSource: async (source) => {
+    // 只做预览，失败由调用方展示，不写入全局错误
+    return invoke<VersionPreview>("validate_source", { source });
+  },
+
+  fetchReleaseNotes: async (id) => {
+    return invoke<string | null>("get_release_notes", { id });
+  },
+
+  fetchSourceUrl: async (id) => {
+    return invoke<string>("get_source_url", { id });
+  },
+
+  fetchSourceToken: async (id) => {
+    return invoke<string | null>("get_source_token", { id });
+  },
+
+  fetchSoftwaresByTag: async (tag) => {
+    return invoke<Software[]>("get_softwares_by_tag", { tag });
+  },
+
+  searchSoftwares: async (query) => {
+    return invoke<Software[]>("search_softwares", { query });
+  },
+
+  reorderSoftwares: async (orderedIds) => {
+    try {
+      const softwares = await invoke<Software[]>("reorder_softwares", { orderedIds });
+      set({ softwares });
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  checkAllVersions: async (forceRefresh = false) => {
     set({ isChecking: true });
     try {
-      const results = await invoke<VersionCheckResult[]>("check_all_versions");
+      const results = await invoke<VersionCheckResult[]>("check_all_versions", {
+        forceRefresh,
+      });
 
       // Update local state with the results
-      set((state) => {
-        const updatedSoftwares = state.softwares.map((s) => {
-          const result = results.find((r) => r.softwareId === s.id);
-          if (result) {
-            return {
-              ...s,
-              latestVersion: result.latestVersion,
-              localVersion: result.localVersion,
-              publishedAt: result.publishedAt,
-              lastCheckedAt: new Date().toISOString(),
-            };
-          }
-          return s;
-        });
-        return { softwares: updatedSoftwares, isChecking: false };
-      });
+      set((state) => ({
+        softwares: applyCheckResults(state.softwares, results),
+        isChecking: false,
+      }));
 
       return results;
     } catch (error) {
@@ -150,5 +412,26 @@ export const useSoftwareStore = create<SoftwareState>((set) => ({
     }
   },
 
+  checkStaleVersions: async (maxAgeMinutes) => {
+    set({ isChecking: true });
+    try {
+      const results = await invoke<VersionCheckResult[]>("check_stale_versions", {
+        maxAgeMinutes,
+      });
+      set((state) => ({
+        softwares: applyCheckResults(state.softwares, results),
+        isChecking: false,
+      }));
+      return results;
+    } catch (error) {
+      set({ error: String(error), isChecking: false });
+      throw error;
+    }
+  },
+
+  cancelCheck: async () => {
+    await invoke("cancel_check");
+  },
+
   clearError: () => set({ error: null }),
 }));
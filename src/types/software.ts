@@ -3,23 +3,94 @@ export type SourceType =
   | "github-release"
   | "github-tags"
   | "homebrew"
+  | "homebrew-cask"
   | "npm"
   | "pypi"
-  | "cargo";
+  | "cargo"
+  | "docker-hub"
+  | "rubygems"
+  | "nuget"
+  | "go-module"
+  | "custom-json"
+  | "feed"
+  | "web-scrape"
+  | "bitbucket"
+  | "maven"
+  | "conda"
+  | "packagist"
+  | "hex"
+  | "flathub"
+  | "aur"
+  | "sourceforge"
+  | "winget"
+  | "cran"
+  | "gitea"
+  | "git-tags"
+  | "fdroid"
+  | "ubuntu-package";
 
 // 数据源配置
 export interface SourceConfig {
   type: SourceType;
   // GitHub: "owner/repo", Homebrew: "formula-name"
   identifier: string;
+  // GitHub Release：从 release 列表选取并包含预发布版本
+  includePrerelease?: boolean;
+  // 只保留匹配此正则的版本
+  versionFilterRegex?: string | null;
+  // 排除匹配此正则的版本，如 nightly
+  versionExcludeRegex?: string | null;
+  // GitHub Release：要求带有匹配此通配符的附件，如 *-linux-x86_64.tar.gz
+  requiredAsset?: string | null;
+  // GitHub Tags：只考虑以此开头的 tag，如 monorepo 中的 package-a/
+  tagPrefix?: string | null;
+  // GitHub Release / Tags：覆盖全局 GitHub Token，用于单独授权的私有仓库
+  authToken?: string | null;
+  // 判断更新的依据，默认比较版本号
+  compareBy?: CompareMode;
+  // GitHub Release：从 tag 名还是 release 标题读取版本号，默认 tag 名
+  versionFrom?: VersionFrom;
+  // GitHub Release：从上述字段中提取版本号的正则，有捕获组时取第一个捕获组
+  versionExtractRegex?: string | null;
+  // 从版本列表中选取的数据源只取该渠道的版本，默认不限
+  channel?: ReleaseChannel;
 }
 
+// GitHub Release 版本号的来源字段
+export type VersionFrom = "tag-name" | "release-name";
+
+// 发布渠道：不限、只取正式版本、只取预发布版本
+export type ReleaseChannel = "any" | "stable" | "prerelease";
+
+// 判断更新的依据：版本号，或发布时间（适用于 commit hash 等无法比较的版本）
+export type CompareMode = "version" | "published-date";
+
+// 本地版本的获取方式：执行命令、按 JSON 解析命令输出或读取文件
+export type LocalVersionSource =
+  | { type: "command" }
+  | { type: "command-json"; jsonPointer: string }
+  | { type: "file-read"; path: string; jsonPointer?: string };
+
 // 本地版本检测配置
 export interface LocalVersionConfig {
+  // 获取方式，默认执行命令
+  source?: LocalVersionSource;
   // 本地命令名称，如 "git", "node"
   command: string;
-  // 版本参数，默认 "--version"
+  // 版本参数，默认 "--version"；多个参数用空格分隔
   versionArg?: string;
+  // 参数列表，非空时优先于 versionArg
+  args?: string[];
+  // 命令的工作目录
+  workingDir?: string;
+  // 额外的环境变量
+  env?: Record<string, string>;
+  // 自定义版本提取正则，取第一个捕获组
+  versionRegex?: string;
+  // 命令超时时间（秒），默认 10 秒
+  timeoutSeconds?: number;
+  // 通过 shell 执行命令，支持管道等语法
+  useShell?: boolean;
 }
 
 // 软件条目
@@ -39,6 +110,24 @@ export interface Software {
   // 通知相关字段
   lastNotifiedVersion: string | null;
   lastNotifiedAt: string | null;
+  // 单独的检查间隔（分钟），为空时使用全局自动刷新间隔
+  checkIntervalMinutes?: number | null;
+  // 分组标签
+  tags?: string[];
+  // 列表中的显示顺序
+  sortOrder?: number;
+  // 暂缓提醒的截止时间 (ISO 8601) 和对应的版本
+  snoozedUntil?: string | null;
+  snoozedVersion?: string | null;
+  // 移入回收站的时间 (ISO 8601)
+  deletedAt?: string | null;
+  // 最新版本中匹配 requiredAsset 的附件下载地址
+  assetUrl?: string | null;
+  // 最近一次检查失败的原因和时间 (ISO 8601)，检查成功后清除
+  lastError?: string | null;
+  lastErrorAt?: string | null;
+  // 按发布时间判断更新时，已确认的发布时间
+  seenPublishedAt?: string | null;
 }
 
 // 新建/编辑软件表单
@@ -46,8 +135,13 @@ export interface SoftwareFormData {
   name: string;
   source: SourceConfig;
   localVersionConfig?: LocalVersionConfig;
+  checkIntervalMinutes?: number;
+  tags?: string[];
 }
 
+// 更新类型
+export type VersionChange = "major" | "minor" | "patch" | "prerelease" | "other";
+
 // 版本检查结果
 export interface VersionCheckResult {
   softwareId: string;
@@ -55,16 +149,133 @@ export interface VersionCheckResult {
   localVersion: string | null;
   publishedAt: string | null;
   hasUpdate: boolean;
+  // 更新类型，没有更新时为 null
+  changeKind?: VersionChange | null;
+}
+
+// 数据源预览结果（添加前校验，不保存）
+export interface VersionPreview {
+  latestVersion: string;
+  publishedAt: string | null;
+  assetUrl?: string | null;
+}
+
+// 连续检查失败的软件的退避状态
+export interface BackoffStatus {
+  softwareId: string;
+  consecutiveFailures: number;
+  // 下一次重试时间 (ISO 8601)
+  nextRetryAt: string;
+}
+
+// 后台调度器状态
+export interface SchedulerStatus {
+  running: boolean;
+  paused: boolean;
+  intervalMinutes: number;
+}
+
+// 通知渠道
+export type NotificationChannel = "desktop" | "webhook";
+
+// 已发送的通知记录
+export interface NotificationLogEntry {
+  id: number;
+  softwareId: string;
+  version: string;
+  // 发送时间 (ISO 8601)
+  sentAt: string;
+  channel: NotificationChannel;
+}
+
+// 测试通知在单个渠道上的发送结果
+export interface NotificationTestResult {
+  channel: NotificationChannel;
+  success: boolean;
+  error: string | null;
+}
+
+// 最新版本的变化记录
+export interface VersionHistoryEntry {
+  id: number;
+  softwareId: string;
+  version: string;
+  // 观察到该版本的时间 (ISO 8601)
+  observedAt: string;
+}
+
+// 导入失败的条目
+export interface ImportFailure {
+  id: string;
+  name: string;
+  error: string;
+}
+
+// 导入结果
+export interface ImportResult {
+  imported: number;
+  failed: ImportFailure[];
+}
+
+// 批量添加失败的条目，index 为提交时的位置
+export interface BatchAddFailure {
+  index: number;
+  name: string;
+  error: string;
+}
+
+// 批量添加结果
+export interface BatchAddResult {
+  added: Software[];
+  failed: BatchAddFailure[];
+}
+
+// GitHub API 限额
+export interface GithubRateLimit {
+  limit: number;
+  remaining: number;
+  // 限额重置时间
+  resetAt: string | null;
 }
 
 // 缓存配置
 export interface CacheConfig {
   // 缓存有效期（分钟）
   ttlMinutes: number;
+  // 获取失败的结果缓存时间（分钟）
+  errorTtlMinutes?: number;
   // 是否启用自动刷新
   autoRefreshEnabled: boolean;
   // 自动刷新间隔（分钟）
   autoRefreshInterval: number;
+  // 启动时立即检查所有软件，默认开启
+  checkOnStartup?: boolean;
+}
+
+// 网络配置
+export interface NetworkConfig {
+  // 单个请求的超时时间（秒）
+  requestTimeoutSeconds: number;
+  // 批量检查时错开请求的最大随机延迟（秒），0 表示不错开
+  checkJitterSeconds?: number;
+  // 网络错误或 5xx 响应时 GET 请求的最大重试次数
+  maxRetries?: number;
+  // 首次重试前的等待时间（毫秒），之后每次翻倍
+  retryBaseDelayMs?: number;
+  // 自定义 User-Agent，为空时使用 app-version-gui
+  userAgent?: string;
+  // 联系方式（如邮箱），通过 From 请求头发送
+  contact?: string;
+}
+
+// Prometheus 指标服务配置
+export interface MetricsConfig {
+  // 是否提供 /metrics，默认关闭
+  enabled: boolean;
+  // 监听端口
+  port: number;
+  // 监听地址，默认只允许本机访问
+  bindAddress?: string;
 }
 
 // 主题模式类型
@@ -77,7 +288,35 @@ export const THEME_MODE_LABELS: Record<ThemeMode, string> = {
   system: "跟随系统",
 };
 
+// 非标准版本（日期、自定义格式等）不相等时的比较策略
+export type NonSemverStrategy =
+  | "optimistic"
+  | "lexicographic-greater"
+  | "treat-different-as-unknown";
+
+// 比较策略显示名称
+export const NON_SEMVER_STRATEGY_LABELS: Record<NonSemverStrategy, string> = {
+  optimistic: "不相等即视为有更新",
+  "lexicographic-greater": "按字符串大小比较",
+  "treat-different-as-unknown": "无法判断，不提示更新",
+};
+
+// 系统通知方式：每个软件一条，或汇总为一条
+export type NotificationStyle = "individual" | "digest";
+
+// 通知方式显示名称
+export const NOTIFICATION_STYLE_LABELS: Record<NotificationStyle, string> = {
+  individual: "每个软件单独通知",
+  digest: "汇总为一条通知",
+};
+
 // 通知配置
+// Webhook 通知配置（Slack/Discord 兼容）
+export interface WebhookConfig {
+  url: string;
+  enabled: boolean;
+}
+
 export interface NotificationConfig {
   enabled: boolean;
   notifyOnMajor: boolean;
@@ -86,7 +325,16 @@ export interface NotificationConfig {
   notifyOnPrerelease: boolean;
   silentStartHour: number | null;
   silentEndHour: number | null;
+  // 静默时段的分钟部分 (0-59)，为空视为 0
+  silentStartMinute?: number | null;
+  silentEndMinute?: number | null;
   testMode: boolean;
+  webhook?: WebhookConfig;
+  // 一次检查发现多个更新时的系统通知方式
+  style?: NotificationStyle;
+  // 每小时最多通知的次数（全局 / 每个软件），0 表示不限制
+  maxPerHour?: number;
+  maxPerSoftwarePerHour?: number;
 }
 
 // 默认通知配置
@@ -99,6 +347,10 @@ export const DEFAULT_NOTIFICATION_CONFIG: NotificationConfig = {
   silentStartHour: 22,
   silentEndHour: 8,
   testMode: false,
+  webhook: { url: "", enabled: false },
+  style: "individual",
+  maxPerHour: 20,
+  maxPerSoftwarePerHour: 2,
 };
 
 // 应用设置
@@ -106,14 +358,36 @@ export interface AppSettings {
   cache: CacheConfig;
   // 可选的 GitHub Token（提高 API 限额）
   githubToken?: string;
+  // GitHub API 地址（GitHub Enterprise 使用，留空为公共 GitHub）
+  githubApiBaseUrl?: string;
+  // Bitbucket 凭据（"用户名:应用密码" 或 Access Token）
+  bitbucketToken?: string;
+  // Gitea/Forgejo 的 Access Token（访问私有仓库时使用）
+  giteaToken?: string;
   // 主题模式
   theme: ThemeMode;
+  // 非标准版本的比较策略
+  nonSemverStrategy?: NonSemverStrategy;
   // 通知配置
   notification: NotificationConfig;
+  // 网络配置
+  network?: NetworkConfig;
+  // 指标服务配置
+  metrics?: MetricsConfig;
 }
 
 // 更新状态
-export type UpdateStatus = "up-to-date" | "update-available" | "unknown";
+export type UpdateStatus = "up-to-date" | "update-available" | "snoozed" | "unknown";
+
+// 最新版本的提醒是否处于暂缓期，出现其他版本后失效
+export function isSnoozed(software: Software, now: Date = new Date()): boolean {
+  return (
+    !!software.snoozedUntil &&
+    !!software.latestVersion &&
+    software.snoozedVersion === software.latestVersion &&
+    new Date(software.snoozedUntil).getTime() > now.getTime()
+  );
+}
 
 // 获取更新状态
 export function getUpdateStatus(
@@ -132,12 +406,79 @@ export function getUpdateStatus(
   return "update-available";
 }
 
+// 按发布时间判断更新：最新发布晚于已确认的发布，且本地版本不是最新版本
+export function getPublishedDateStatus(software: Software): UpdateStatus {
+  if (!software.publishedAt || !software.seenPublishedAt) {
+    return "unknown";
+  }
+  const latest = software.latestVersion?.replace(/^v/, "");
+  if (latest && latest === software.localVersion?.replace(/^v/, "")) {
+    return "up-to-date";
+  }
+  return new Date(software.publishedAt) > new Date(software.seenPublishedAt)
+    ? "update-available"
+    : "up-to-date";
+}
+
+// 支持“包含预发布版本”选项的数据源
+export const PRERELEASE_SOURCE_TYPES: SourceType[] = [
+  "github-release",
+  "github-tags",
+  "npm",
+  "pypi",
+  "hex",
+  "git-tags",
+];
+
+// 支持版本筛选正则的数据源（从版本列表中选取最新版本）
+export const VERSION_FILTER_SOURCE_TYPES: SourceType[] = [
+  "github-release",
+  "github-tags",
+  "npm",
+  "pypi",
+  "git-tags",
+];
+
+// 支持限定发布渠道的数据源
+export const RELEASE_CHANNEL_SOURCE_TYPES: SourceType[] = [
+  ...VERSION_FILTER_SOURCE_TYPES,
+  "hex",
+  "nuget",
+  "docker-hub",
+  "maven",
+  "gitea",
+  "bitbucket",
+  "ubuntu-package",
+];
+
 // 数据源类型显示名称
 export const SOURCE_TYPE_LABELS: Record<SourceType, string> = {
   "github-release": "GitHub Release",
   "github-tags": "GitHub Tags",
   homebrew: "Homebrew",
+  "homebrew-cask": "Homebrew Cask",
   npm: "npm Registry",
   pypi: "PyPI",
   cargo: "crates.io (Cargo)",
+  "docker-hub": "Docker Hub",
+  rubygems: "RubyGems",
+  nuget: "NuGet",
+  "go-module": "Go Module",
+  "custom-json": "自定义 JSON",
+  feed: "RSS/Atom 订阅",
+  "web-scrape": "网页抓取",
+  bitbucket: "Bitbucket",
+  maven: "Maven Central",
+  conda: "Conda",
+  packagist: "Packagist",
+  hex: "Hex.pm",
+  flathub: "Flathub",
+  aur: "AUR",
+  sourceforge: "SourceForge",
+  winget: "Winget",
+  cran: "CRAN",
+  gitea: "Gitea/Forgejo",
+  "git-tags": "Git Tags",
+  fdroid: "F-Droid",
+  "ubuntu-package": "Ubuntu/PPA",
 };